@@ -0,0 +1,170 @@
+//! Batch resolution and benchmarking.
+//!
+//! ```bash
+//! cargo run --release --example cli -- batch <input> [--options options.json]
+//! cargo run --release --example cli -- bench <input> [--options options.json] [--iterations 10]
+//! ```
+//!
+//! `<input>` is either
+//! * a `package.json`, every dependency is resolved from its directory, or
+//! * a text file with one `<directory> <specifier>` pair per line, `#` starts a comment.
+//!
+//! `options.json` is deserialized into [rspack_resolver::ResolveOptions].
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+use rspack_resolver::{ResolveOptions, Resolver};
+
+const USAGE: &str =
+    "usage: cli <batch|bench> <input> [--options <options.json>] [--iterations <n>]";
+
+fn main() -> ExitCode {
+    match cli() {
+        Ok(code) => code,
+        Err(message) => {
+            eprintln!("{message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn cli() -> Result<ExitCode, String> {
+    let mut args = env::args().skip(1);
+    let command = args.next().ok_or(USAGE)?;
+    let input = PathBuf::from(args.next().ok_or(USAGE)?);
+
+    let mut options = ResolveOptions::default();
+    let mut iterations = 10usize;
+    while let Some(arg) = args.next() {
+        let value = args.next().ok_or(USAGE)?;
+        match arg.as_str() {
+            "--options" => {
+                let json = fs::read_to_string(&value).map_err(|e| format!("{value}: {e}"))?;
+                options = serde_json::from_str(&json).map_err(|e| format!("{value}: {e}"))?;
+            }
+            "--iterations" => {
+                iterations = value.parse().map_err(|e| format!("{value}: {e}"))?;
+            }
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+
+    let requests = read_requests(&input).map_err(|e| format!("{}: {e}", input.display()))?;
+    let resolver = Resolver::new(options);
+    match command.as_str() {
+        "batch" => Ok(batch(&resolver, &requests)),
+        "bench" => {
+            bench(&resolver, &requests, iterations.max(1));
+            Ok(ExitCode::SUCCESS)
+        }
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+fn read_requests(input: &Path) -> Result<Vec<(PathBuf, String)>, String> {
+    let content = fs::read_to_string(input).map_err(|e| e.to_string())?;
+    if input.file_name().is_some_and(|name| name == "package.json") {
+        let json: serde_json::Value = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+        let dir = input.canonicalize().map_err(|e| e.to_string())?;
+        let dir = dir.parent().unwrap().to_path_buf();
+        let requests =
+            ["dependencies", "devDependencies", "peerDependencies", "optionalDependencies"]
+                .into_iter()
+                .filter_map(|field| json.get(field).and_then(serde_json::Value::as_object))
+                .flat_map(|deps| deps.keys())
+                .map(|name| (dir.clone(), name.clone()))
+                .collect();
+        return Ok(requests);
+    }
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let (dir, specifier) = line
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| format!("expected `<directory> <specifier>`, got `{line}`"))?;
+            Ok((PathBuf::from(dir), specifier.trim().to_string()))
+        })
+        .collect()
+}
+
+fn batch(resolver: &Resolver, requests: &[(PathBuf, String)]) -> ExitCode {
+    let start = Instant::now();
+    let failures = run(resolver, requests);
+    let elapsed = start.elapsed();
+
+    for (dir, specifier, error) in &failures {
+        println!("FAIL {} {specifier}: {error}", dir.display());
+    }
+    println!("requests: {}", requests.len());
+    println!("resolved: {}", requests.len() - failures.len());
+    println!("failed:   {}", failures.len());
+    println!("elapsed:  {elapsed:?} ({:?} per request)", per_request(elapsed, requests.len()));
+    print_cache_stats(resolver);
+    if failures.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn bench(resolver: &Resolver, requests: &[(PathBuf, String)], iterations: usize) {
+    let mut cold = Vec::with_capacity(iterations);
+    let mut warm = Vec::with_capacity(iterations);
+    let mut failures = 0;
+    for _ in 0..iterations {
+        resolver.clear_cache();
+        let start = Instant::now();
+        failures = run(resolver, requests).len();
+        cold.push(start.elapsed());
+
+        let start = Instant::now();
+        run(resolver, requests);
+        warm.push(start.elapsed());
+    }
+
+    println!("requests:   {} ({failures} failed)", requests.len());
+    println!("iterations: {iterations}");
+    for (name, timings) in [("cold", &cold), ("warm", &warm)] {
+        let min = timings.iter().min().copied().unwrap_or_default();
+        let max = timings.iter().max().copied().unwrap_or_default();
+        let mean = timings.iter().sum::<Duration>() / u32::try_from(timings.len()).unwrap();
+        println!(
+            "{name}: mean {mean:?} min {min:?} max {max:?} ({:?} per request)",
+            per_request(mean, requests.len())
+        );
+    }
+    print_cache_stats(resolver);
+}
+
+fn run(resolver: &Resolver, requests: &[(PathBuf, String)]) -> Vec<(PathBuf, String, String)> {
+    requests
+        .iter()
+        .filter_map(|(dir, specifier)| {
+            resolver
+                .resolve(dir, specifier)
+                .err()
+                .map(|error| (dir.clone(), specifier.clone(), error.to_string()))
+        })
+        .collect()
+}
+
+fn per_request(elapsed: Duration, count: usize) -> Duration {
+    u32::try_from(count)
+        .ok()
+        .filter(|count| *count > 0)
+        .map_or(Duration::ZERO, |count| elapsed / count)
+}
+
+fn print_cache_stats(resolver: &Resolver) {
+    let stats = resolver.cache_stats();
+    println!(
+        "cache:    {} paths, {} package.json, {} tsconfig",
+        stats.paths, stats.package_jsons, stats.tsconfigs
+    );
+}
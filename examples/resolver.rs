@@ -1,4 +1,4 @@
-///! See documentation at <https://docs.rs/rspack_resolver>
+//! See documentation at <https://docs.rs/rspack_resolver>
 use std::{env, path::PathBuf};
 
 use rspack_resolver::{AliasValue, ResolveOptions, Resolver};
@@ -0,0 +1,41 @@
+/* C API of rspack_resolver, built as the `rspack_ffi_resolver` shared library. */
+
+#ifndef RSPACK_RESOLVER_H
+#define RSPACK_RESOLVER_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* All strings are NUL-terminated UTF-8.
+ *
+ * `options_json` is the JSON form of `ResolveOptions` with snake_case keys,
+ * NULL or "" for the defaults.
+ *
+ * Results are JSON strings owned by the caller, release them with `rspack_string_free`:
+ * `{ "path", "query", "fragment", "package_json", "module_type" }` on success,
+ * `{ "error": "message" }` on failure. */
+
+typedef struct RspackResolver RspackResolver;
+
+/* Resolves `specifier` in `directory` with a one-off resolver. */
+char *rspack_resolve(const char *directory, const char *specifier, const char *options_json);
+
+/* Creates a resolver whose caches are kept between calls, NULL when the options are invalid or it panicked.
+ * It can be shared between threads. */
+RspackResolver *rspack_resolver_new(const char *options_json);
+
+char *rspack_resolver_resolve(const RspackResolver *resolver, const char *directory, const char *specifier);
+
+/* Clears the caches of `resolver`, for when files have changed. */
+void rspack_resolver_clear_cache(const RspackResolver *resolver);
+
+void rspack_resolver_free(RspackResolver *resolver);
+
+void rspack_string_free(char *value);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* RSPACK_RESOLVER_H */
@@ -0,0 +1,308 @@
+//! C API of the resolver for non-Node embedders, declared in `rspack_resolver.h`.
+//!
+//! All strings are NUL-terminated UTF-8.
+//! The options are the JSON form of [ResolveOptions] with snake_case keys, a null or empty string for the defaults.
+//!
+//! Results are JSON strings owned by the caller, released with [rspack_string_free]:
+//! the serialized [rspack_resolver::Resolution] `{ "path", "query", "fragment", "package_json", "module_type" }` on success,
+//! `{ "error": "message" }` on failure.
+//!
+//! Panics are caught at every entry point and reported as errors instead of unwinding into the caller,
+//! build with the `release-ffi` profile because the `release` profile aborts on panic.
+
+use std::{
+    any::Any,
+    ffi::{c_char, CStr, CString},
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    ptr,
+};
+
+use rspack_resolver::{ResolveOptions, Resolver};
+use serde_json::Value;
+
+/// A resolver whose caches are kept between calls, created by [rspack_resolver_new].
+///
+/// It can be shared between threads.
+pub struct RspackResolver(Resolver);
+
+/// Reads the string argument `name`.
+///
+/// # Safety
+///
+/// `value` is null or a valid NUL-terminated string.
+unsafe fn str_arg<'a>(value: *const c_char, name: &str) -> Result<&'a str, String> {
+    if value.is_null() {
+        return Err(format!("{name} is null"));
+    }
+    // SAFETY: `value` is a non-null NUL-terminated string, guaranteed by the caller.
+    unsafe { CStr::from_ptr(value) }.to_str().map_err(|_| format!("{name} is not valid UTF-8"))
+}
+
+/// Parses the options JSON, null or empty for [ResolveOptions::default].
+///
+/// # Safety
+///
+/// `options_json` is null or a valid NUL-terminated string.
+unsafe fn options_arg(options_json: *const c_char) -> Result<ResolveOptions, String> {
+    if options_json.is_null() {
+        return Ok(ResolveOptions::default());
+    }
+    // SAFETY: `options_json` is null or a NUL-terminated string, guaranteed by the caller.
+    let json = unsafe { str_arg(options_json, "options_json") }?;
+    if json.trim().is_empty() {
+        return Ok(ResolveOptions::default());
+    }
+    serde_json::from_str(json).map_err(|err| format!("invalid options_json: {err}"))
+}
+
+/// Resolves `specifier` in `directory`.
+///
+/// # Safety
+///
+/// `directory` and `specifier` are null or valid NUL-terminated strings.
+unsafe fn resolve(
+    resolver: &Resolver,
+    directory: *const c_char,
+    specifier: *const c_char,
+) -> Result<Value, String> {
+    // SAFETY: guaranteed by the caller.
+    let directory = unsafe { str_arg(directory, "directory") }?;
+    // SAFETY: guaranteed by the caller.
+    let specifier = unsafe { str_arg(specifier, "specifier") }?;
+    let resolution =
+        resolver.resolve(Path::new(directory), specifier).map_err(|err| err.to_string())?;
+    serde_json::to_value(&resolution).map_err(|err| err.to_string())
+}
+
+/// Runs `f`, catching a panic so it does not unwind across the C ABI.
+fn catch_panic<T, F: FnOnce() -> T>(f: F) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| panic_message(&*payload))
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    let message = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic");
+    format!("panicked: {message}")
+}
+
+/// Converts the result into a JSON string owned by the caller.
+fn into_json(result: Result<Value, String>) -> *mut c_char {
+    let value = result.unwrap_or_else(|error| serde_json::json!({ "error": error }));
+    // Serialized JSON escapes NUL, `CString::new` does not fail.
+    CString::new(value.to_string()).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Resolves `specifier` in `directory` with a one-off resolver created from `options_json`.
+///
+/// Use [rspack_resolver_new] to keep the caches between resolutions.
+///
+/// # Safety
+///
+/// Every argument is null or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn rspack_resolve(
+    directory: *const c_char,
+    specifier: *const c_char,
+    options_json: *const c_char,
+) -> *mut c_char {
+    let result = catch_panic(|| {
+        // SAFETY: guaranteed by the caller.
+        unsafe { options_arg(options_json) }.and_then(|options| {
+            // SAFETY: guaranteed by the caller.
+            unsafe { resolve(&Resolver::new(options), directory, specifier) }
+        })
+    });
+    into_json(result.and_then(|result| result))
+}
+
+/// Creates a resolver from `options_json`, null when the options are invalid or it panicked.
+///
+/// Release it with [rspack_resolver_free].
+///
+/// # Safety
+///
+/// `options_json` is null or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn rspack_resolver_new(options_json: *const c_char) -> *mut RspackResolver {
+    let resolver = catch_panic(|| {
+        // SAFETY: guaranteed by the caller.
+        unsafe { options_arg(options_json) }.map(|options| RspackResolver(Resolver::new(options)))
+    });
+    match resolver {
+        Ok(Ok(resolver)) => Box::into_raw(Box::new(resolver)),
+        Ok(Err(_)) | Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Resolves `specifier` in `directory` with `resolver`.
+///
+/// # Safety
+///
+/// `resolver` is created by [rspack_resolver_new] and not yet freed,
+/// `directory` and `specifier` are null or valid NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn rspack_resolver_resolve(
+    resolver: *const RspackResolver,
+    directory: *const c_char,
+    specifier: *const c_char,
+) -> *mut c_char {
+    // SAFETY: `resolver` is null or alive, guaranteed by the caller.
+    let Some(resolver) = (unsafe { resolver.as_ref() }) else {
+        return into_json(Err("resolver is null".to_string()));
+    };
+    let result = catch_panic(|| {
+        // SAFETY: guaranteed by the caller.
+        unsafe { resolve(&resolver.0, directory, specifier) }
+    });
+    into_json(result.and_then(|result| result))
+}
+
+/// Clears the caches of `resolver`, for when files have changed.
+///
+/// # Safety
+///
+/// `resolver` is null or created by [rspack_resolver_new] and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn rspack_resolver_clear_cache(resolver: *const RspackResolver) {
+    // SAFETY: `resolver` is null or alive, guaranteed by the caller.
+    if let Some(resolver) = unsafe { resolver.as_ref() } {
+        // A panic leaves the caches partially cleared, which is harmless.
+        let _ = catch_panic(|| resolver.0.clear_cache());
+    }
+}
+
+/// Releases a resolver created by [rspack_resolver_new].
+///
+/// # Safety
+///
+/// `resolver` is null or created by [rspack_resolver_new] and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn rspack_resolver_free(resolver: *mut RspackResolver) {
+    if !resolver.is_null() {
+        // SAFETY: `resolver` was created by `Box::into_raw` in `rspack_resolver_new`.
+        drop(unsafe { Box::from_raw(resolver) });
+    }
+}
+
+/// Releases a result string.
+///
+/// # Safety
+///
+/// `value` is null or returned by [rspack_resolve] or [rspack_resolver_resolve] and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn rspack_string_free(value: *mut c_char) {
+    if !value.is_null() {
+        // SAFETY: `value` was created by `CString::into_raw` in `into_json`.
+        drop(unsafe { CString::from_raw(value) });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        ffi::{c_char, CStr, CString},
+        path::PathBuf,
+        ptr,
+    };
+
+    use serde_json::Value;
+
+    use super::{
+        rspack_resolve, rspack_resolver_clear_cache, rspack_resolver_free, rspack_resolver_new,
+        rspack_resolver_resolve, rspack_string_free,
+    };
+
+    fn fixture() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../fixtures/enhanced_resolve/test/fixtures")
+            .canonicalize()
+            .unwrap()
+    }
+
+    fn c_string(value: &str) -> CString {
+        CString::new(value).unwrap()
+    }
+
+    fn take_json(value: *mut c_char) -> Value {
+        assert!(!value.is_null());
+        // SAFETY: `value` is a result string.
+        let json = unsafe { CStr::from_ptr(value) }.to_str().unwrap().to_string();
+        // SAFETY: `value` is a result string and not used afterwards.
+        unsafe { rspack_string_free(value) };
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[test]
+    fn resolve() {
+        let f = fixture();
+        let directory = c_string(f.to_str().unwrap());
+        let options = c_string(r#"{ "extensions": [".js"] }"#);
+
+        // SAFETY: the arguments are NUL-terminated strings.
+        let json = take_json(unsafe {
+            rspack_resolve(directory.as_ptr(), c_string("./main1").as_ptr(), options.as_ptr())
+        });
+        assert_eq!(json["path"], f.join("main1.js").to_str().unwrap());
+        assert_eq!(json["query"], Value::Null);
+
+        // SAFETY: the arguments are NUL-terminated strings or null.
+        let json = take_json(unsafe {
+            rspack_resolve(directory.as_ptr(), c_string("./missing").as_ptr(), ptr::null())
+        });
+        assert_eq!(json["error"], "Cannot find module './missing'");
+
+        // SAFETY: the arguments are NUL-terminated strings or null.
+        let json = take_json(unsafe {
+            rspack_resolve(
+                ptr::null(),
+                c_string("./main1").as_ptr(),
+                c_string(r#"{ "extensions": true }"#).as_ptr(),
+            )
+        });
+        assert!(json["error"].as_str().unwrap().starts_with("invalid options_json"));
+    }
+
+    #[test]
+    fn panic() {
+        let result = super::catch_panic(|| -> Value { panic!("boom") });
+        assert_eq!(result, Err("panicked: boom".to_string()));
+        let result = super::catch_panic(|| -> Value { panic!("{}", String::from("boom")) });
+        assert_eq!(result, Err("panicked: boom".to_string()));
+        let json = super::into_json(result);
+        assert_eq!(take_json(json)["error"], "panicked: boom");
+    }
+
+    #[test]
+    fn resolver() {
+        let f = fixture();
+        let directory = c_string(f.to_str().unwrap());
+
+        // SAFETY: the argument is a NUL-terminated string.
+        assert!(unsafe { rspack_resolver_new(c_string(r#"{ "extensions": true }"#).as_ptr()) }
+            .is_null());
+
+        // SAFETY: the argument is a NUL-terminated string.
+        let resolver = unsafe { rspack_resolver_new(c_string("").as_ptr()) };
+        assert!(!resolver.is_null());
+        // SAFETY: `resolver` is alive and the arguments are NUL-terminated strings.
+        let json = take_json(unsafe {
+            rspack_resolver_resolve(resolver, directory.as_ptr(), c_string("m1/a").as_ptr())
+        });
+        assert_eq!(json["path"], f.join("node_modules/m1/a.js").to_str().unwrap());
+        // SAFETY: `resolver` is alive and not used afterwards.
+        unsafe {
+            rspack_resolver_clear_cache(resolver);
+            rspack_resolver_free(resolver);
+        }
+
+        // SAFETY: the arguments are NUL-terminated strings.
+        let json = take_json(unsafe {
+            rspack_resolver_resolve(ptr::null(), directory.as_ptr(), c_string("m1/a").as_ptr())
+        });
+        assert_eq!(json["error"], "resolver is null");
+    }
+}
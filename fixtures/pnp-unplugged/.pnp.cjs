@@ -0,0 +1,67 @@
+#!/usr/bin/env node
+/* eslint-disable */
+// @ts-nocheck
+"use strict";
+
+const RAW_RUNTIME_STATE =
+'{\
+  "__info": [\
+    "This file is automatically generated. Do not touch it, or risk",\
+    "your modifications being lost."\
+  ],\
+  "dependencyTreeRoots": [\
+    {\
+      "name": "pnp-unplugged",\
+      "reference": "workspace:."\
+    }\
+  ],\
+  "enableTopLevelFallback": true,\
+  "ignorePatternData": null,\
+  "fallbackExclusionList": [\
+    ["pnp-unplugged", ["workspace:."]]\
+  ],\
+  "fallbackPool": [\
+  ],\
+  "packageRegistryData": [\
+    [null, [\
+      [null, {\
+        "packageLocation": "./",\
+        "packageDependencies": [\
+          ["is-buffer", "npm:1.1.6"],\
+          ["kind-of", "npm:3.2.2"]\
+        ],\
+        "linkType": "SOFT"\
+      }]\
+    ]],\
+    ["is-buffer", [\
+      ["npm:1.1.6", {\
+        "packageLocation": "./.yarn/unplugged/is-buffer-npm-1.1.6-08199d9ccc/node_modules/is-buffer/",\
+        "packageDependencies": [\
+          ["is-buffer", "npm:1.1.6"]\
+        ],\
+        "linkType": "HARD"\
+      }]\
+    ]],\
+    ["kind-of", [\
+      ["npm:3.2.2", {\
+        "packageLocation": "./.yarn/cache/kind-of-npm-3.2.2-7deaffa5f9-7e34bc29d4.zip/node_modules/kind-of/",\
+        "packageDependencies": [\
+          ["kind-of", "npm:3.2.2"],\
+          ["is-buffer", "npm:1.1.6"]\
+        ],\
+        "linkType": "HARD"\
+      }]\
+    ]],\
+    ["pnp-unplugged", [\
+      ["workspace:.", {\
+        "packageLocation": "./",\
+        "packageDependencies": [\
+          ["pnp-unplugged", "workspace:."],\
+          ["is-buffer", "npm:1.1.6"],\
+          ["kind-of", "npm:3.2.2"]\
+        ],\
+        "linkType": "SOFT"\
+      }]\
+    ]]\
+  ]\
+}';
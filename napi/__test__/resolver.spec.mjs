@@ -4,13 +4,15 @@ import { fileURLToPath } from 'node:url'
 import test from 'ava'
 
 let ResolverFactory
+let isBuiltin
+let nodejsBuiltins
 
 if (process.env.WASI_TEST) {
   const wasi = await import('../resolver.wasi.cjs')
-  ResolverFactory = wasi.ResolverFactory
+  ;({ ResolverFactory, isBuiltin, nodejsBuiltins } = wasi)
 } else {
   const napi = await import('../index.js')
-  ResolverFactory = napi.ResolverFactory
+  ;({ ResolverFactory, isBuiltin, nodejsBuiltins } = napi)
 }
 
 const currentDir = join(fileURLToPath(import.meta.url), '..')
@@ -236,26 +238,22 @@ test('resolve pnpm package', (t) => {
   const resolver = new ResolverFactory({
     aliasFields: ['browser'],
   })
-  t.deepEqual(resolver.sync(pnpmProjectPath, 'styled-components'), {
-    path: join(
+  t.is(
+    resolver.sync(pnpmProjectPath, 'styled-components').path,
+    join(
       rootDir,
       'node_modules/.pnpm/styled-components@6.1.1_react-dom@18.3.1_react@18.3.1__react@18.3.1/node_modules/styled-components/dist/styled-components.browser.cjs.js'
-    ),
-  })
-  t.deepEqual(
+    )
+  )
+  t.is(
     resolver.sync(
       join(
         rootDir,
         'node_modules/.pnpm/styled-components@6.1.1_react-dom@18.3.1_react@18.3.1__react@18.3.1/node_modules/styled-components'
       ),
       'react'
-    ),
-    {
-      path: join(
-        rootDir,
-        'node_modules/.pnpm/react@18.3.1/node_modules/react/index.js'
-      ),
-    }
+    ).path,
+    join(rootDir, 'node_modules/.pnpm/react@18.3.1/node_modules/react/index.js')
   )
 })
 
@@ -264,16 +262,72 @@ test('resolve recursive symbol link', (t) => {
   const workspaceProjectPath = join(rootDir, 'fixtures', 'pnpm-workspace')
   const resolver = new ResolverFactory({})
 
-  t.deepEqual(
+  t.is(
     resolver.sync(
       join(workspaceProjectPath, './packages/app'),
       './node_modules/@monorepo/lib/node_modules/react/package.json'
-    ),
+    ).path,
+    join(rootDir, 'node_modules/.pnpm/react@18.3.1/node_modules/react/package.json')
+  )
+})
+
+test('builtin modules', (t) => {
+  t.true(nodejsBuiltins().includes('fs'))
+  t.true(isBuiltin('fs'))
+  t.true(isBuiltin('node:fs'))
+  t.false(isBuiltin('node:xxx'))
+  t.false(isBuiltin('xxx'))
+
+  t.false(new ResolverFactory({}).isBuiltin('fs'))
+  const resolver = new ResolverFactory({ builtinModules: true })
+  t.true(resolver.isBuiltin('fs'))
+  t.true(resolver.isBuiltin('node:fs'))
+  t.false(resolver.isBuiltin('xxx'))
+})
+
+test('path hash', (t) => {
+  const resolver = new ResolverFactory({})
+  const main1 = resolver.sync(enhancedResolveRoot, './main1')
+  t.is(main1.pathHash, resolver.sync(enhancedResolveRoot, './main1.js?query').pathHash)
+  t.not(main1.pathHash, resolver.sync(enhancedResolveRoot, './a').pathHash)
+})
+
+test('detailed error', async (t) => {
+  const resolver = new ResolverFactory({})
+  t.is(
+    resolver.syncDetailed(enhancedResolveRoot, './main1').path,
+    join(enhancedResolveRoot, 'main1.js')
+  )
+  for (const result of [
+    resolver.syncDetailed(enhancedResolveRoot, './missing'),
+    await resolver.asyncDetailed(enhancedResolveRoot, './missing'),
+  ]) {
+    t.is(result.path, undefined)
+    t.is(result.error.code, 'MODULE_NOT_FOUND')
+    t.is(result.error.message, "Cannot find module './missing'")
+    t.deepEqual(result.error.data, { specifier: './missing' })
+  }
+})
+
+test('dispose', (t) => {
+  const resolver = new ResolverFactory({})
+  const path = join(enhancedResolveRoot, 'main1.js')
+  t.is(resolver.sync(enhancedResolveRoot, './main1').path, path)
+  resolver.dispose()
+  t.is(resolver.sync(enhancedResolveRoot, './main1').path, path)
+})
+
+test('in-memory files', (t) => {
+  const dir = join(enhancedResolveRoot, 'in-memory')
+  const resolver = new ResolverFactory(
+    { extensions: ['.ts', '.js'] },
     {
-      path: join(
-        rootDir,
-        'node_modules/.pnpm/react@18.3.1/node_modules/react/package.json'
-      ),
+      [join(dir, 'package.json')]: '{ "main": "./src/main" }',
+      [join(dir, 'src/main.ts')]: 'export {}',
     }
   )
+  t.is(resolver.sync(enhancedResolveRoot, './in-memory').path, join(dir, 'src/main.ts'))
+  t.is(resolver.sync(dir, '../main1').path, join(enhancedResolveRoot, 'main1.js'))
+  resolver.dispose()
+  t.is(resolver.sync(enhancedResolveRoot, './in-memory').path, join(dir, 'src/main.ts'))
 })
@@ -1,16 +1,65 @@
 /* auto-generated by NAPI-RS */
 /* eslint-disable */
 export declare class ResolverFactory {
-  constructor(options?: NapiResolveOptions | undefined | null)
+  /** `files` maps absolute paths to file contents, layered over the real file system. */
+  constructor(options?: NapiResolveOptions | undefined | null, files?: Record<string, string> | undefined | null)
   static default(): ResolverFactory
   /** Clone the resolver using the same underlying cache. */
   cloneWithOptions(options: NapiResolveOptions): ResolverFactory
   /** Clear the underlying cache. */
   clearCache(): void
+  /**
+   * Drop the cached entries of `path` and everything under it, keeping the rest of the cache.
+   *
+   * Use this when files under `path` changed, e.g. a symlink was re-linked to another target.
+   */
+  invalidate(path: string): void
+  /**
+   * Returns the cached paths dropped by `invalidate` since the last call, sorted.
+   *
+   * Only the modules whose file or missing dependencies are among them need to be resolved again.
+   */
+  takeInvalidated(): Array<string>
+  /**
+   * Drop the underlying cache and Yarn PnP caches now instead of when the resolver is garbage collected.
+   *
+   * The resolver can still be used afterwards, starting with empty caches.
+   * A cache shared with resolvers created by `cloneWithOptions` is dropped once all of them are disposed,
+   * resolutions in progress keep it alive until they finish.
+   */
+  dispose(): void
+  /**
+   * Returns whether `specifier` resolves to a Node.js builtin module with the `builtinModules` option of this resolver.
+   *
+   * Always returns `false` when `builtinModules` is disabled.
+   */
+  isBuiltin(specifier: string): boolean
   /** Synchronously resolve `specifier` at an absolute path to a `directory`. */
   sync(directory: string, request: string): ResolveResult
   /** Asynchronously resolve `specifier` at an absolute path to a `directory`. */
   async(directory: string, request: string): Promise<ResolveResult>
+  /**
+   * Synchronously resolve `specifier` at an absolute path to a `directory`,
+   * returning the error as an object with a stable `code`.
+   */
+  syncDetailed(directory: string, request: string): DetailedResolveResult
+  /**
+   * Asynchronously resolve `specifier` at an absolute path to a `directory`,
+   * returning the error as an object with a stable `code`.
+   */
+  asyncDetailed(directory: string, request: string): Promise<DetailedResolveResult>
+}
+
+/** Either `path` or `error` is set. */
+export interface DetailedResolveResult {
+  path?: string
+  error?: ResolveErrorObject
+  /** "type" field in the package.json file */
+  moduleType?: string
+  /** Hash of the path without query and fragment as a hex string, cheap to use as a map key */
+  pathHash?: string
+  /** Path relative to `relativePathBase` with query and fragment */
+  relativePath?: string
 }
 
 export declare const enum EnforceExtension {
@@ -19,6 +68,25 @@ export declare const enum EnforceExtension {
   Disabled = 2
 }
 
+/**
+ * Route tracing output to a file path or a JS callback instead of stderr.
+ *
+ * The callback receives one formatted log line per call.
+ * `OXC_LOG` still controls which events are emitted.
+ *
+ * Must be called before the first `ResolverFactory` is constructed,
+ * returns `false` if tracing has already been initialized.
+ * Throws if another global tracing subscriber is already installed in the process.
+ */
+export declare function initTracing(output: string | ((message: string) => void)): boolean
+
+/**
+ * Returns whether `specifier` is a Node.js builtin module, with or without the `node:` prefix.
+ *
+ * This is the same as Node.js `module.isBuiltin`.
+ */
+export declare function isBuiltin(specifier: string): boolean
+
 /**
  * Module Resolution Options
  *
@@ -33,6 +101,33 @@ export interface NapiResolveOptions {
    * Default `None`
    */
   tsconfig?: TsconfigOptions
+  /**
+   * TypeScript configuration files scoped by directory.
+   *
+   * Requests from under a directory use the tsconfig of the longest matching directory,
+   * requests from elsewhere fall back to `tsconfig` and `tsconfigDiscovery`.
+   *
+   * Default `[]`
+   */
+  tsconfigScopes?: Array<TsconfigScope>
+  /**
+   * Find the closest `tsconfig.json` or `jsconfig.json` above the importing directory
+   * and apply its `paths`.
+   *
+   * Only used when `tsconfig` is not set and no `tsconfigScopes` matches.
+   *
+   * Default `false`
+   */
+  tsconfigDiscovery?: boolean
+  /**
+   * When tsconfig `paths` are tried.
+   *
+   * * `First`: before everything else, including aliases and package exports.
+   * * `Fallback`: only when the request cannot be resolved otherwise, before `fallback`.
+   *
+   * Default `First`
+   */
+  tsconfigPathsPrecedence?: TsconfigPathsPrecedence
   /**
    * Alias for [ResolveOptions::alias] and [ResolveOptions::fallback].
    *
@@ -42,6 +137,20 @@ export interface NapiResolveOptions {
    * A trailing $ can also be added to the given object's keys to signify an exact match.
    */
   alias?: Record<string, Array<string | undefined | null>>
+  /**
+   * Aliases whose keys are regular expressions matched against the whole specifier, tried after `alias`.
+   * Values may reference capture groups with `$1` or `${name}`.
+   *
+   * Panics on invalid patterns.
+   */
+  regexAlias?: Record<string, Array<string | undefined | null>>
+  /**
+   * Skip `alias` and `regexAlias` when the importing directory is inside a node_modules directory,
+   * so application aliases do not rewrite imports of third-party packages.
+   *
+   * Default `false`
+   */
+  aliasSkipNodeModules?: boolean
   /**
    * A list of alias fields in description files.
    * Specify a field, such as `browser`, to be parsed according to [this specification](https://github.com/defunctzombie/package-browser-field-spec).
@@ -50,9 +159,19 @@ export interface NapiResolveOptions {
    * Default `[]`
    */
   aliasFields?: (string | string[])[]
+  /**
+   * Overrides the alias fields of packages by their package.json "name".
+   *
+   * `null` ignores the alias fields of the package,
+   * an object keyed like the "browser" field replaces them, where `null` values ignore the key.
+   *
+   * Default `{}`
+   */
+  aliasFieldOverrides?: Record<string, Record<string, string | undefined | null> | undefined | null>
   /**
    * Condition names for exports field which defines entry points of a package.
    * The key order in the exports field is significant. During condition matching, earlier entries have higher priority and take precedence over later entries.
+   * The `"..."` item is expanded to the default values.
    *
    * Default `[]`
    */
@@ -63,6 +182,23 @@ export interface NapiResolveOptions {
    * Default `["package.json"]`
    */
   descriptionFiles?: Array<string>
+  /**
+   * Description files parsed as JSONC, allowing comments and trailing commas.
+   * Files not listed, including `package.json` by default, are parsed as strict JSON.
+   *
+   * Default `[]`
+   */
+  jsoncDescriptionFiles?: Array<string>
+  /**
+   * Stop looking up description files in parent directories at this boundary,
+   * instead of walking up to the file system root.
+   *
+   * Either an absolute path, or `'node_modules'` to stop below the nearest `node_modules` directory.
+   * Importers outside of the path are not limited.
+   *
+   * Default `None`
+   */
+  descriptionFilesBoundary?: string
   /**
    * If true, it will not allow extension-less files.
    * So by default `require('./foo')` works if `./foo` has a `.js` extension,
@@ -101,16 +237,64 @@ export interface NapiResolveOptions {
    * Attempt to resolve these extensions in order.
    * If multiple files share the same name but have different extensions,
    * will resolve the one with the extension listed first in the array and skip the rest.
+   * The `"..."` item is expanded to the default values.
    *
    * Default `[".js", ".json", ".node"]`
    */
   extensions?: Array<string>
+  /**
+   * Try `extensions` in the order of how often each of them resolved a file, most frequent first.
+   * The result is not deterministic when a specifier matches files with different extensions.
+   *
+   * Default `false`
+   */
+  adaptiveExtensions?: boolean
+  /**
+   * Read a directory once instead of looking up each candidate in it
+   * when `extensions` and `extensionInfixes` produce at least this many candidates.
+   * File names are compared case-insensitively, names with non-ASCII characters are always looked up.
+   *
+   * Default `None`
+   */
+  directoryListingThreshold?: number
+  /**
+   * Infixes tried in order between the file name and each extension,
+   * e.g. `["ios", "native"]` tries `Button.ios.js` and `Button.native.js` before `Button.js`.
+   *
+   * Default `[]`
+   */
+  extensionInfixes?: Array<string>
+  /**
+   * Platform suffixes of native addons, e.g. `["linux-x64-gnu", "linux-x64"]`.
+   *
+   * When `foo.node` is not found, `foo.{suffix}.node` and `prebuilds/{suffix}/foo.node`
+   * are tried for each suffix in order.
+   *
+   * Default `[]`
+   */
+  nativeAddonSuffixes?: Array<string>
   /**
    * Redirect module requests when normal resolving fails.
    *
    * Default `[]`
    */
   fallback?: Record<string, Array<string | undefined | null>>
+  /**
+   * Only consult `fallback` for bare specifiers such as `path` or `@scope/pkg`,
+   * so a mistyped relative or absolute request is not redirected to a polyfill.
+   *
+   * Default `false`
+   */
+  fallbackBareOnly?: boolean
+  /**
+   * Option sets tried in order, sharing the cache, until one of them resolves.
+   *
+   * For example, resolve with ESM conditions first, then with CommonJS conditions.
+   * Unlike the other options, unset fields of a fallback use the defaults instead of the outer option set.
+   *
+   * Default `[]`
+   */
+  fallbackOptions?: Array<NapiResolveOptions>
   /**
    * Request passed to resolve is already fully specified and extensions or main files are not resolved for it (they are still resolved for internal requests).
    *
@@ -119,12 +303,40 @@ export interface NapiResolveOptions {
    * Default `false`
    */
   fullySpecified?: boolean
+  /**
+   * Try a relative or absolute specifier containing `#` as a literal path first,
+   * before treating the `#` as the start of a fragment.
+   *
+   * Default `true`
+   */
+  fragmentAsPath?: boolean
+  /**
+   * Return paths with `/` separators on Windows, e.g. `C:/project/index.js` and `//server/share/index.js` for a UNC path,
+   * for comparing with POSIX-style paths in JavaScript.
+   *
+   * Has no effect on other platforms.
+   *
+   * Default `false`
+   */
+  forwardSlashes?: boolean
+  /**
+   * Also return `relativePath`, the resolved path relative to this absolute directory,
+   * with `..` for paths outside of it.
+   *
+   * Useful for machine-independent paths in persistent caches, combined with `forwardSlashes` on Windows.
+   * No relative path is returned for a path on another drive or UNC share.
+   *
+   * Default `None`
+   */
+  relativePathBase?: string
   /**
    * A list of main fields in description files
+   * Can be a path to json object such as `["spm", "main"]` or `"spm.main"`.
+   * The `"..."` item is expanded to the default values.
    *
    * Default `["main"]`.
    */
-  mainFields?: string | string[]
+  mainFields?: string | (string | string[])[]
   /**
    * The filename to be used while resolving directories.
    *
@@ -137,12 +349,28 @@ export interface NapiResolveOptions {
    * Default `["node_modules"]`
    */
   modules?: string | string[]
+  /**
+   * Stop looking up `modules` in parent directories after this directory,
+   * instead of walking up to the file system root.
+   *
+   * Either an absolute path, `'git'` for the nearest directory containing `.git`, or `'workspace'` for `workspaceRoot`.
+   * Importers outside of the boundary are not limited.
+   *
+   * Default `None`
+   */
+  modulesBoundary?: string
   /**
    * Resolve to a context instead of a file.
    *
    * Default `false`
    */
   resolveToContext?: boolean
+  /**
+   * How requests ending in `/`, e.g. `./dir/`, are resolved.
+   *
+   * Default `Index`
+   */
+  trailingSlash?: TrailingSlash
   /**
    * Prefer to resolve module requests as relative requests instead of using modules from node_modules directories.
    *
@@ -161,6 +389,15 @@ export interface NapiResolveOptions {
    * Default `[]`
    */
   restrictions?: Array<Restriction>
+  /**
+   * A list of allowed root directories.
+   *
+   * When not empty, resolving to a path whose real path is outside all of these directories fails,
+   * even when the path escapes through a symlink.
+   *
+   * Default `[]`
+   */
+  allowedRoots?: Array<string>
   /**
    * A list of directories where requests of server-relative URLs (starting with '/') are resolved.
    * On non-Windows systems these requests are resolved as an absolute path first.
@@ -168,6 +405,60 @@ export interface NapiResolveOptions {
    * Default `[]`
    */
   roots?: Array<string>
+  /**
+   * Resolve `~/foo` and `/foo` against a project or package root, as in Parcel, Vite and Nuxt.
+   *
+   * The root is the configured `root`, or else the directory of the nearest package.json of the importer,
+   * so these specifiers keep working inside nested packages.
+   *
+   * Default `None`
+   */
+  rootPrefixes?: RootPrefixOptions
+  /**
+   * Root directory of a monorepo.
+   *
+   * When set, the "packages" globs of its pnpm-workspace.yaml, or the "workspaces" globs of its package.json,
+   * are expanded to find workspace packages,
+   * and bare specifiers naming a workspace package resolve to its directory before `node_modules` are looked up.
+   *
+   * Default `None`
+   */
+  workspaceRoot?: string
+  /**
+   * Directory of the application's root package.json whose Parcel style "alias" field is applied.
+   *
+   * The field maps specifiers to other specifiers, to paths relative to the package.json, or to `false` to ignore the module,
+   * e.g. `{ "alias": { "react": "preact/compat", "fs": false } }`.
+   * It is applied before `alias`, so the manifest takes the highest priority.
+   *
+   * <https://parceljs.org/features/dependency-resolution/#aliases>
+   *
+   * Default `None`
+   */
+  aliasRoot?: string
+  /**
+   * Package names which always resolve from the `node_modules` of `dedupeRoot`,
+   * regardless of the copy nearest to the importer, as in Vite's `resolve.dedupe`.
+   *
+   * Avoids duplicated instances of packages such as React in linked package setups.
+   *
+   * Default `[]`
+   */
+  dedupe?: Array<string>
+  /**
+   * The directory the `dedupe` packages resolve from, `dedupe` has no effect when it is not set.
+   *
+   * Default `None`
+   */
+  dedupeRoot?: string
+  /**
+   * Only resolve bare specifiers declared in the dependency fields of the importer's nearest package.json.
+   *
+   * Self-references and Node.js builtin modules are always allowed.
+   *
+   * Default `None`
+   */
+  strictDependencies?: StrictDependencies
   /**
    * Whether to resolve symlinks to their symlinked location.
    * When enabled, symlinked resources are resolved to their real path, not their symlinked location.
@@ -176,6 +467,14 @@ export interface NapiResolveOptions {
    * Default `true`
    */
   symlinks?: boolean
+  /**
+   * What to do when a real path leaves the given roots, e.g. for globally linked packages with `npm link`.
+   *
+   * Only used when `symlinks` is enabled.
+   *
+   * Default `None`, the real path is always used.
+   */
+  symlinkEscape?: SymlinkEscapeOptions
   /**
    * Whether to parse [module.builtinModules](https://nodejs.org/api/module.html#modulebuiltinmodules) or not.
    * For example, "zlib" will throw [crate::ResolveError::Builtin] when set to true.
@@ -183,6 +482,40 @@ export interface NapiResolveOptions {
    * Default `false`
    */
   builtinModules?: boolean
+  /**
+   * Recognize the builtin modules of this Node.js version, e.g. `"22.5.0"` for `node:sqlite`.
+   * When set, `node:` specifiers which are not builtin modules of the version are resolved like other specifiers.
+   *
+   * Default `None`
+   */
+  builtinModulesNodeVersion?: string
+  /**
+   * Maximum number of paths a single resolution may probe on the file system,
+   * the resolution fails with a list of directories where most lookups were spent when exceeded.
+   *
+   * Default `None`
+   */
+  fsOperationBudget?: number
+  /**
+   * Bypass all caches, reading everything from the file system again for every request.
+   *
+   * For debugging suspected stale cache results, this is much slower.
+   *
+   * Default `false`
+   */
+  disableCache?: boolean
+}
+
+/** Node.js builtin modules, the same list as `require('module').builtinModules`. */
+export declare function nodejsBuiltins(): Array<string>
+
+/** Resolve error with a stable code, see [DetailedResolveResult]. */
+export interface ResolveErrorObject {
+  /** Stable code of the error, such as `MODULE_NOT_FOUND` or `ERR_PACKAGE_PATH_NOT_EXPORTED` */
+  code: string
+  message: string
+  /** Values the message is formatted from, such as `specifier` or `packageJsonPath` */
+  data: Record<string, string>
 }
 
 export interface ResolveResult {
@@ -190,6 +523,10 @@ export interface ResolveResult {
   error?: string
   /** "type" field in the package.json file */
   moduleType?: string
+  /** Hash of the path without query and fragment as a hex string, cheap to use as a map key */
+  pathHash?: string
+  /** Path relative to `relativePathBase` with query and fragment */
+  relativePath?: string
 }
 
 /**
@@ -201,8 +538,50 @@ export interface Restriction {
   regex?: string
 }
 
+/** Root Prefix Options */
+export interface RootPrefixOptions {
+  /** Resolve `~/foo` against the root. */
+  tilde?: boolean
+  /** Resolve `/foo` against the root, before `roots` and the file system root are tried. */
+  slash?: boolean
+  /** The root directory, the directory of the nearest package.json of the importer when unset. */
+  root?: string
+}
+
+/**
+ * Strict Dependencies Options
+ *
+ * Packages in "dependencies" are always allowed.
+ */
+export interface StrictDependencies {
+  /** Allow packages in "devDependencies", default `true`. */
+  devDependencies?: boolean
+  /** Allow packages in "peerDependencies", default `true`. */
+  peerDependencies?: boolean
+  /** Allow packages in "optionalDependencies", default `true`. */
+  optionalDependencies?: boolean
+}
+
 export declare function sync(path: string, request: string): ResolveResult
 
+/** Symlink Escape Options */
+export interface SymlinkEscapeOptions {
+  /** Project or workspace roots, a real path outside all of them is an escape. */
+  roots: Array<string>
+  /**
+   * * `'canonical'`: keep the real path, the default.
+   * * `'original'`: keep the original symlinked path.
+   * * `'error'`: fail the resolution.
+   */
+  policy?: 'canonical' | 'original' | 'error'
+}
+
+export declare const enum TrailingSlash {
+  Index = 0,
+  Context = 1,
+  Strict = 2
+}
+
 /**
  * Tsconfig Options
  *
@@ -223,5 +602,21 @@ export interface TsconfigOptions {
    * * `string[]`: manually provided relative or absolute path.
    */
   references?: 'auto' | string[]
+  /**
+   * The tsconfig content used in place of reading `config_file`,
+   * which then only gives its virtual location: `extends` and relative paths resolve against its directory.
+   */
+  content?: string
 }
 
+export declare const enum TsconfigPathsPrecedence {
+  First = 0,
+  Fallback = 1
+}
+
+/** A tsconfig for requests from under `directory`, see `tsconfigScopes`. */
+export interface TsconfigScope {
+  /** Absolute path to the directory. */
+  directory: string
+  tsconfig: TsconfigOptions
+}
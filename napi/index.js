@@ -363,4 +363,7 @@ if (!nativeBinding) {
 
 module.exports.ResolverFactory = nativeBinding.ResolverFactory
 module.exports.EnforceExtension = nativeBinding.EnforceExtension
+module.exports.initTracing = nativeBinding.initTracing
+module.exports.isBuiltin = nativeBinding.isBuiltin
+module.exports.nodejsBuiltins = nativeBinding.nodejsBuiltins
 module.exports.sync = nativeBinding.sync
@@ -0,0 +1,76 @@
+use std::{
+    cell::Cell,
+    io,
+    path::{Path, PathBuf},
+};
+
+use oxc_resolver::{FileMetadata, FileSystem, FileSystemOs};
+
+/// Size in bytes of a package.json or tsconfig above which the async methods parse it on the rayon thread pool.
+///
+/// The async methods run on the libuv thread pool, which Node.js file system calls and other async resolutions wait for.
+pub const LARGE_MANIFEST_SIZE: usize = 256 * 1024;
+
+thread_local! {
+    /// Whether reading a manifest above [LARGE_MANIFEST_SIZE] fails on this thread, see [on_libuv_thread].
+    static DEFER_LARGE_MANIFESTS: Cell<bool> = const { Cell::new(false) };
+    /// Whether a manifest above [LARGE_MANIFEST_SIZE] failed to be read on this thread.
+    static DEFERRED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `resolve` on a libuv thread, returning `None` when it read a manifest above [LARGE_MANIFEST_SIZE].
+///
+/// The read fails without caching the manifest, the caller resolves again on the rayon thread pool to parse it there.
+/// Smaller manifests are parsed in place.
+pub fn on_libuv_thread<T, F: FnOnce() -> T>(resolve: F) -> Option<T> {
+    DEFER_LARGE_MANIFESTS.with(|defer| defer.set(true));
+    DEFERRED.with(|deferred| deferred.set(false));
+    let output = resolve();
+    DEFER_LARGE_MANIFESTS.with(|defer| defer.set(false));
+    (!DEFERRED.with(Cell::get)).then_some(output)
+}
+
+/// [FileSystemOs] failing to read large manifests in [on_libuv_thread].
+#[derive(Default)]
+pub struct DeferringFileSystem(FileSystemOs);
+
+impl FileSystem for DeferringFileSystem {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.0.read(path)
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        let string = self.0.read_to_string(path)?;
+        if string.len() > LARGE_MANIFEST_SIZE
+            && path.extension().is_some_and(|extension| extension == "json")
+            && DEFER_LARGE_MANIFESTS.with(Cell::get)
+        {
+            DEFERRED.with(|deferred| deferred.set(true));
+            return Err(io::Error::new(
+                io::ErrorKind::Interrupted,
+                "deferred to the rayon thread pool",
+            ));
+        }
+        Ok(string)
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.0.metadata(path)
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.0.symlink_metadata(path)
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        self.0.canonicalize(path)
+    }
+
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+        self.0.read_link(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        self.0.read_dir(path)
+    }
+}
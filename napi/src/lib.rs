@@ -3,19 +3,27 @@ extern crate napi_derive;
 extern crate oxc_resolver;
 
 use std::{
+    collections::HashMap,
+    mem,
     path::{Path, PathBuf},
     sync::Arc,
 };
 
-use napi::{bindgen_prelude::AsyncTask, Task};
+use napi::{bindgen_prelude::AsyncTask, Either, Env, JsObject, Task};
 use napi_derive::napi;
-use oxc_resolver::{ResolveOptions, Resolver};
+use oxc_resolver::{
+    FileSystem, OverlayFileSystem, ResolveError, ResolveOptions, Resolver, ResolverGeneric,
+};
 
 use self::{
+    file_system::{on_libuv_thread, DeferringFileSystem},
     options::{NapiResolveOptions, StrOrStrList},
     tracing::init_tracing,
 };
 
+pub use self::tracing::init_tracing_with_output;
+
+mod file_system;
 mod options;
 mod tracing;
 
@@ -25,9 +33,20 @@ pub struct ResolveResult {
     pub error: Option<String>,
     /// "type" field in the package.json file
     pub module_type: Option<String>,
+    /// Hash of the path without query and fragment as a hex string, cheap to use as a map key
+    pub path_hash: Option<String>,
+    /// Path relative to `relativePathBase` with query and fragment
+    pub relative_path: Option<String>,
 }
 
-fn resolve(resolver: &Resolver, path: &Path, request: &str) -> ResolveResult {
+/// Resolver of [ResolverFactory], with the in-memory files passed to its constructor.
+type FactoryResolver = ResolverGeneric<OverlayFileSystem<DeferringFileSystem>>;
+
+fn resolve<Fs: FileSystem>(
+    resolver: &ResolverGeneric<Fs>,
+    path: &Path,
+    request: &str,
+) -> ResolveResult {
     match resolver.resolve(path, request) {
         Ok(resolution) => ResolveResult {
             path: Some(resolution.full_path().to_string_lossy().to_string()),
@@ -37,11 +56,161 @@ fn resolve(resolver: &Resolver, path: &Path, request: &str) -> ResolveResult {
                 .and_then(|p| p.r#type.as_ref())
                 .and_then(|t| t.as_str())
                 .map(|t| t.to_string()),
+            path_hash: Some(format!("{:016x}", resolution.path_hash())),
+            relative_path: resolution
+                .relative_full_path()
+                .map(|path| path.to_string_lossy().to_string()),
+        },
+        Err(err) => ResolveResult {
+            path: None,
+            module_type: None,
+            path_hash: None,
+            relative_path: None,
+            error: Some(err.to_string()),
+        },
+    }
+}
+
+/// Resolve error with a stable code, see [DetailedResolveResult].
+#[napi(object)]
+pub struct ResolveErrorObject {
+    /// Stable code of the error, such as `MODULE_NOT_FOUND` or `ERR_PACKAGE_PATH_NOT_EXPORTED`
+    pub code: String,
+    pub message: String,
+    /// Values the message is formatted from, such as `specifier` or `packageJsonPath`
+    pub data: HashMap<String, String>,
+}
+
+impl From<ResolveError> for ResolveErrorObject {
+    fn from(error: ResolveError) -> Self {
+        fn path(path: &Path) -> String {
+            path.to_string_lossy().to_string()
+        }
+        let data: Vec<(&str, String)> = match &error {
+            ResolveError::NotFound(specifier) | ResolveError::Builtin(specifier) => {
+                vec![("specifier", specifier.clone())]
+            }
+            ResolveError::MatchedAliasNotFound(specifier, alias_key) => {
+                vec![("specifier", specifier.clone()), ("aliasKey", alias_key.clone())]
+            }
+            ResolveError::Ignored(p)
+            | ResolveError::NotADirectory(p)
+            | ResolveError::TsconfigNotFound(p)
+            | ResolveError::TsconfigSelfReference(p)
+            | ResolveError::OutsideAllowedRoots(p)
+            | ResolveError::InvalidPackageConfig(p)
+            | ResolveError::InvalidPackageConfigDefault(p)
+            | ResolveError::InvalidPackageConfigDirectory(p) => vec![("path", path(p))],
+            ResolveError::UndeclaredDependency(package_name, package_json_path) => vec![
+                ("packageName", package_name.clone()),
+                ("packageJsonPath", path(package_json_path)),
+            ],
+            ResolveError::ExtensionAlias(file_name, tried, directory) => vec![
+                ("fileName", file_name.clone()),
+                ("triedFileNames", tried.clone()),
+                ("directory", path(directory)),
+            ],
+            ResolveError::JSON(json) => vec![
+                ("path", path(&json.path)),
+                ("line", json.line.to_string()),
+                ("column", json.column.to_string()),
+            ],
+            ResolveError::Restriction(p, restriction) => {
+                vec![("path", path(p)), ("restriction", path(restriction))]
+            }
+            ResolveError::SymlinkEscape(p, real_path) => {
+                vec![("path", path(p)), ("realPath", path(real_path))]
+            }
+            ResolveError::InvalidModuleSpecifier(specifier, package_json_path)
+            | ResolveError::PackageImportNotDefined(specifier, package_json_path) => {
+                vec![("specifier", specifier.clone()), ("packageJsonPath", path(package_json_path))]
+            }
+            ResolveError::InvalidPackageTarget(target, subpath, package_json_path) => vec![
+                ("target", target.clone()),
+                ("subpath", subpath.clone()),
+                ("packageJsonPath", path(package_json_path)),
+            ],
+            ResolveError::PackagePathNotExported(subpath, package_json_path) => {
+                vec![("subpath", subpath.clone()), ("packageJsonPath", path(package_json_path))]
+            }
+            ResolveError::PackagePathBlocked(subpath, key, package_json_path) => vec![
+                ("subpath", subpath.clone()),
+                ("key", key.clone()),
+                ("packageJsonPath", path(package_json_path)),
+            ],
+            ResolveError::BinNotFound(bin_name, package_json_path) => {
+                vec![("binName", bin_name.clone()), ("packageJsonPath", path(package_json_path))]
+            }
+            ResolveError::FsOperationBudgetExceeded(budget, _) => {
+                vec![("budget", budget.to_string())]
+            }
+            _ => vec![],
+        };
+        Self {
+            code: error.code().to_string(),
+            message: error.to_string(),
+            data: data.into_iter().map(|(key, value)| (key.to_string(), value)).collect(),
+        }
+    }
+}
+
+/// Either `path` or `error` is set.
+#[napi(object)]
+pub struct DetailedResolveResult {
+    pub path: Option<String>,
+    pub error: Option<ResolveErrorObject>,
+    /// "type" field in the package.json file
+    pub module_type: Option<String>,
+    /// Hash of the path without query and fragment as a hex string, cheap to use as a map key
+    pub path_hash: Option<String>,
+    /// Path relative to `relativePathBase` with query and fragment
+    pub relative_path: Option<String>,
+}
+
+fn resolve_detailed<Fs: FileSystem>(
+    resolver: &ResolverGeneric<Fs>,
+    path: &Path,
+    request: &str,
+) -> DetailedResolveResult {
+    match resolver.resolve(path, request) {
+        Ok(resolution) => DetailedResolveResult {
+            path: Some(resolution.full_path().to_string_lossy().to_string()),
+            error: None,
+            module_type: resolution
+                .package_json()
+                .and_then(|p| p.r#type.as_ref())
+                .and_then(|t| t.as_str())
+                .map(|t| t.to_string()),
+            path_hash: Some(format!("{:016x}", resolution.path_hash())),
+            relative_path: resolution
+                .relative_full_path()
+                .map(|path| path.to_string_lossy().to_string()),
+        },
+        Err(err) => DetailedResolveResult {
+            path: None,
+            error: Some(err.into()),
+            module_type: None,
+            path_hash: None,
+            relative_path: None,
         },
-        Err(err) => ResolveResult { path: None, module_type: None, error: Some(err.to_string()) },
     }
 }
 
+/// Node.js builtin modules, the same list as `require('module').builtinModules`.
+#[napi]
+pub fn nodejs_builtins() -> Vec<String> {
+    oxc_resolver::NODEJS_BUILTINS.iter().map(ToString::to_string).collect()
+}
+
+/// Returns whether `specifier` is a Node.js builtin module, with or without the `node:` prefix.
+///
+/// This is the same as Node.js `module.isBuiltin`.
+#[allow(clippy::needless_pass_by_value)]
+#[napi]
+pub fn is_builtin(specifier: String) -> bool {
+    oxc_resolver::is_nodejs_builtin(&specifier)
+}
+
 #[allow(clippy::needless_pass_by_value)]
 #[napi]
 pub fn sync(path: String, request: String) -> ResolveResult {
@@ -51,43 +220,103 @@ pub fn sync(path: String, request: String) -> ResolveResult {
 }
 
 pub struct ResolveTask {
-    resolver: Arc<Resolver>,
+    resolver: Arc<FactoryResolver>,
     directory: PathBuf,
     request: String,
 }
 
 #[napi]
 impl Task for ResolveTask {
-    type Output = ResolveResult;
-    type JsValue = ResolveResult;
+    type Output = Option<ResolveResult>;
+    type JsValue = Either<ResolveResult, JsObject>;
 
     fn compute(&mut self) -> napi::Result<Self::Output> {
-        Ok(resolve(&self.resolver, &self.directory, &self.request))
+        Ok(compute(&self.resolver, || resolve(&self.resolver, &self.directory, &self.request)))
+    }
+
+    fn resolve(&mut self, env: Env, result: Self::Output) -> napi::Result<Self::JsValue> {
+        let resolver = Arc::clone(&self.resolver);
+        let (directory, request) = (mem::take(&mut self.directory), mem::take(&mut self.request));
+        settle(env, result, move || resolve(&resolver, &directory, &request))
+    }
+}
+
+pub struct DetailedResolveTask {
+    resolver: Arc<FactoryResolver>,
+    directory: PathBuf,
+    request: String,
+}
+
+#[napi]
+impl Task for DetailedResolveTask {
+    type Output = Option<DetailedResolveResult>;
+    type JsValue = Either<DetailedResolveResult, JsObject>;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        Ok(compute(&self.resolver, || {
+            resolve_detailed(&self.resolver, &self.directory, &self.request)
+        }))
+    }
+
+    fn resolve(&mut self, env: Env, result: Self::Output) -> napi::Result<Self::JsValue> {
+        let resolver = Arc::clone(&self.resolver);
+        let (directory, request) = (mem::take(&mut self.directory), mem::take(&mut self.request));
+        settle(env, result, move || resolve_detailed(&resolver, &directory, &request))
     }
+}
 
-    fn resolve(&mut self, _: napi::Env, result: Self::Output) -> napi::Result<Self::JsValue> {
-        Ok(result)
+/// Resolves on the libuv thread of an async method, `None` when a large manifest defers the resolution,
+/// see [file_system::LARGE_MANIFEST_SIZE].
+///
+/// Workspace packages are indexed as a whole and cached even when one of their manifests cannot be read,
+/// so resolvers with `workspaceRoot` parse large manifests on the libuv thread.
+fn compute<T, F: FnOnce() -> T>(resolver: &FactoryResolver, resolve: F) -> Option<T> {
+    if resolver.options().workspace_root.is_some() {
+        return Some(resolve());
     }
+    on_libuv_thread(resolve)
+}
+
+/// Returns the result of [compute], or a promise of resolving again on the rayon thread pool when it was deferred.
+fn settle<T, F>(env: Env, result: Option<T>, resolve: F) -> napi::Result<Either<T, JsObject>>
+where
+    T: napi::bindgen_prelude::ToNapiValue + Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    if let Some(result) = result {
+        return Ok(Either::A(result));
+    }
+    let (deferred, promise) = env.create_deferred()?;
+    rayon::spawn(move || {
+        let result = resolve();
+        deferred.resolve(move |_| Ok(result));
+    });
+    Ok(Either::B(promise))
 }
 
 #[napi]
 pub struct ResolverFactory {
-    resolver: Arc<Resolver>,
+    resolver: Arc<FactoryResolver>,
 }
 
 #[napi]
 impl ResolverFactory {
+    /// `files` maps absolute paths to file contents, layered over the real file system.
     #[napi(constructor)]
-    pub fn new(options: Option<NapiResolveOptions>) -> Self {
+    pub fn new(
+        options: Option<NapiResolveOptions>,
+        files: Option<HashMap<String, String>>,
+    ) -> Self {
         init_tracing();
         let options = options.map_or_else(|| ResolveOptions::default(), Self::normalize_options);
-        Self { resolver: Arc::new(Resolver::new(options)) }
+        let file_system =
+            OverlayFileSystem::new(DeferringFileSystem::default(), files.unwrap_or_default());
+        Self { resolver: Arc::new(FactoryResolver::new_with_file_system(file_system, options)) }
     }
 
     #[napi]
     pub fn default() -> Self {
-        let default_options = ResolveOptions::default();
-        Self { resolver: Arc::new(Resolver::new(default_options)) }
+        Self::new(None, None)
     }
 
     /// Clone the resolver using the same underlying cache.
@@ -104,6 +333,48 @@ impl ResolverFactory {
         self.resolver.clear_cache();
     }
 
+    /// Drop the cached entries of `path` and everything under it, keeping the rest of the cache.
+    ///
+    /// Use this when files under `path` changed, e.g. a symlink was re-linked to another target.
+    #[allow(clippy::needless_pass_by_value)]
+    #[napi]
+    pub fn invalidate(&self, path: String) {
+        self.resolver.invalidate(Path::new(&path));
+    }
+
+    /// Returns the cached paths dropped by `invalidate` since the last call, sorted.
+    ///
+    /// Only the modules whose file or missing dependencies are among them need to be resolved again.
+    #[napi]
+    pub fn take_invalidated(&self) -> Vec<String> {
+        self.resolver
+            .take_invalidated()
+            .into_iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect()
+    }
+
+    /// Drop the underlying cache and Yarn PnP caches now instead of when the resolver is garbage collected.
+    ///
+    /// The resolver can still be used afterwards, starting with empty caches.
+    /// A cache shared with resolvers created by `cloneWithOptions` is dropped once all of them are disposed,
+    /// resolutions in progress keep it alive until they finish.
+    #[napi]
+    pub fn dispose(&mut self) {
+        let options = self.resolver.options().clone();
+        let file_system = self.resolver.file_system().with_inner(DeferringFileSystem::default());
+        self.resolver = Arc::new(FactoryResolver::new_with_file_system(file_system, options));
+    }
+
+    /// Returns whether `specifier` resolves to a Node.js builtin module with the `builtinModules` option of this resolver.
+    ///
+    /// Always returns `false` when `builtinModules` is disabled.
+    #[allow(clippy::needless_pass_by_value)]
+    #[napi]
+    pub fn is_builtin(&self, specifier: String) -> bool {
+        self.resolver.is_builtin(&specifier)
+    }
+
     /// Synchronously resolve `specifier` at an absolute path to a `directory`.
     #[allow(clippy::needless_pass_by_value)]
     #[napi]
@@ -113,19 +384,60 @@ impl ResolverFactory {
     }
 
     /// Asynchronously resolve `specifier` at an absolute path to a `directory`.
+    ///
+    /// A package.json or tsconfig larger than 256 KiB is parsed on a separate thread pool instead of the libuv thread pool.
     #[allow(clippy::needless_pass_by_value)]
-    #[napi(js_name = "async")]
+    #[napi(js_name = "async", ts_return_type = "Promise<ResolveResult>")]
     pub fn resolve_async(&self, directory: String, request: String) -> AsyncTask<ResolveTask> {
         let path = PathBuf::from(directory);
         let resolver = self.resolver.clone();
         AsyncTask::new(ResolveTask { resolver, directory: path, request })
     }
 
+    /// Synchronously resolve `specifier` at an absolute path to a `directory`,
+    /// returning the error as an object with a stable `code`.
+    #[allow(clippy::needless_pass_by_value)]
+    #[napi]
+    pub fn sync_detailed(&self, directory: String, request: String) -> DetailedResolveResult {
+        let path = PathBuf::from(directory);
+        resolve_detailed(&self.resolver, &path, &request)
+    }
+
+    /// Asynchronously resolve `specifier` at an absolute path to a `directory`,
+    /// returning the error as an object with a stable `code`.
+    ///
+    /// A package.json or tsconfig larger than 256 KiB is parsed on a separate thread pool instead of the libuv thread pool.
+    #[allow(clippy::needless_pass_by_value)]
+    #[napi(ts_return_type = "Promise<DetailedResolveResult>")]
+    pub fn async_detailed(
+        &self,
+        directory: String,
+        request: String,
+    ) -> AsyncTask<DetailedResolveTask> {
+        let path = PathBuf::from(directory);
+        let resolver = self.resolver.clone();
+        AsyncTask::new(DetailedResolveTask { resolver, directory: path, request })
+    }
+
     fn normalize_options(op: NapiResolveOptions) -> ResolveOptions {
         let default = ResolveOptions::default();
         // merging options
         ResolveOptions {
             tsconfig: op.tsconfig.map(|tsconfig| tsconfig.into()),
+            tsconfig_scopes: op
+                .tsconfig_scopes
+                .map(|scopes| {
+                    scopes
+                        .into_iter()
+                        .map(|scope| (PathBuf::from(scope.directory), scope.tsconfig.into()))
+                        .collect()
+                })
+                .unwrap_or(default.tsconfig_scopes),
+            tsconfig_discovery: op.tsconfig_discovery.unwrap_or(default.tsconfig_discovery),
+            tsconfig_paths_precedence: op
+                .tsconfig_paths_precedence
+                .map(|precedence| precedence.into())
+                .unwrap_or(default.tsconfig_paths_precedence),
             alias: op
                 .alias
                 .map(|alias| {
@@ -144,12 +456,78 @@ impl ResolverFactory {
                         .collect::<Vec<_>>()
                 })
                 .unwrap_or(default.alias),
+            regex_alias: op
+                .regex_alias
+                .map(|alias| {
+                    alias
+                        .into_iter()
+                        .map(|(k, v)| {
+                            let regex = oxc_resolver::Regex::new(&k).unwrap_or_else(|err| {
+                                panic!("Invalid regexAlias key `{k}`: {err}")
+                            });
+                            let v = v
+                                .into_iter()
+                                .map(|item| match item {
+                                    Some(path) => oxc_resolver::AliasValue::from(path),
+                                    None => oxc_resolver::AliasValue::Ignore,
+                                })
+                                .collect();
+                            (regex, v)
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or(default.regex_alias),
+            alias_skip_node_modules: op
+                .alias_skip_node_modules
+                .unwrap_or(default.alias_skip_node_modules),
             alias_fields: op
                 .alias_fields
                 .map(|o| o.into_iter().map(|x| StrOrStrList(x).into()).collect::<Vec<_>>())
                 .unwrap_or(default.alias_fields),
+            alias_field_overrides: op
+                .alias_field_overrides
+                .map(|overrides| {
+                    overrides
+                        .into_iter()
+                        .map(|(name, entries)| {
+                            let value = entries.map_or(
+                                oxc_resolver::AliasFieldOverride::Ignore,
+                                |entries| {
+                                    oxc_resolver::AliasFieldOverride::Replace(
+                                        entries
+                                            .into_iter()
+                                            .map(|(key, value)| {
+                                                let value = value.map_or(
+                                                    oxc_resolver::AliasValue::Ignore,
+                                                    oxc_resolver::AliasValue::Path,
+                                                );
+                                                (key, value)
+                                            })
+                                            .collect(),
+                                    )
+                                },
+                            );
+                            (name, value)
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or(default.alias_field_overrides),
             condition_names: op.condition_names.unwrap_or(default.condition_names),
             description_files: op.description_files.unwrap_or(default.description_files),
+            jsonc_description_files: op
+                .jsonc_description_files
+                .unwrap_or(default.jsonc_description_files),
+            description_files_boundary: op.description_files_boundary.map(|boundary| {
+                if boundary == "node_modules" {
+                    oxc_resolver::DescriptionFilesBoundary::NodeModules
+                } else {
+                    oxc_resolver::DescriptionFilesBoundary::Path(PathBuf::from(boundary))
+                }
+            }),
+            enable_pnp: default.enable_pnp,
+            pnp_loose: default.pnp_loose,
+            #[cfg(feature = "package_json_raw_json_api")]
+            package_json_removed_fields: default.package_json_removed_fields,
             enforce_extension: op
                 .enforce_extension
                 .map(|enforce_extension| enforce_extension.into())
@@ -167,6 +545,15 @@ impl ResolverFactory {
                 .map(|extension_alias| extension_alias.into_iter().collect::<Vec<_>>())
                 .unwrap_or(default.extension_alias),
             extensions: op.extensions.unwrap_or(default.extensions),
+            adaptive_extensions: op.adaptive_extensions.unwrap_or(default.adaptive_extensions),
+            directory_listing_threshold: op
+                .directory_listing_threshold
+                .map(|threshold| threshold as usize)
+                .or(default.directory_listing_threshold),
+            extension_infixes: op.extension_infixes.unwrap_or(default.extension_infixes),
+            native_addon_suffixes: op
+                .native_addon_suffixes
+                .unwrap_or(default.native_addon_suffixes),
             fallback: op
                 .fallback
                 .map(|fallback| {
@@ -185,14 +572,40 @@ impl ResolverFactory {
                         .collect::<Vec<_>>()
                 })
                 .unwrap_or(default.fallback),
+            fallback_bare_only: op.fallback_bare_only.unwrap_or(default.fallback_bare_only),
+            fallback_options: op
+                .fallback_options
+                .map(|options| options.into_iter().map(Self::normalize_options).collect::<Vec<_>>())
+                .unwrap_or(default.fallback_options),
             fully_specified: op.fully_specified.unwrap_or(default.fully_specified),
+            fragment_as_path: op.fragment_as_path.unwrap_or(default.fragment_as_path),
+            forward_slashes: op.forward_slashes.unwrap_or(default.forward_slashes),
+            relative_path_base: op.relative_path_base.map(PathBuf::from),
             main_fields: op
                 .main_fields
-                .map(|o| StrOrStrList(o).into())
+                .map(|o| match o {
+                    Either::A(field) => vec![field],
+                    Either::B(fields) => fields
+                        .into_iter()
+                        .map(|x| Into::<Vec<String>>::into(StrOrStrList(x)).join("."))
+                        .collect::<Vec<_>>(),
+                })
                 .unwrap_or(default.main_fields),
             main_files: op.main_files.unwrap_or(default.main_files),
             modules: op.modules.map(|o| StrOrStrList(o).into()).unwrap_or(default.modules),
+            modules_provider: default.modules_provider,
+            package_json_provider: default.package_json_provider,
+            tsconfig_provider: default.tsconfig_provider,
+            modules_boundary: op.modules_boundary.map(|boundary| match boundary.as_str() {
+                "git" => oxc_resolver::ModulesBoundary::Git,
+                "workspace" => oxc_resolver::ModulesBoundary::Workspace,
+                _ => oxc_resolver::ModulesBoundary::Path(PathBuf::from(boundary)),
+            }),
             resolve_to_context: op.resolve_to_context.unwrap_or(default.resolve_to_context),
+            trailing_slash: op
+                .trailing_slash
+                .map(|trailing_slash| trailing_slash.into())
+                .unwrap_or(default.trailing_slash),
             prefer_relative: op.prefer_relative.unwrap_or(default.prefer_relative),
             prefer_absolute: op.prefer_absolute.unwrap_or(default.prefer_absolute),
             restrictions: op
@@ -204,12 +617,33 @@ impl ResolverFactory {
                         .collect::<Vec<_>>()
                 })
                 .unwrap_or(default.restrictions),
+            allowed_roots: op
+                .allowed_roots
+                .map(|roots| roots.into_iter().map(PathBuf::from).collect::<Vec<_>>())
+                .unwrap_or(default.allowed_roots),
             roots: op
                 .roots
                 .map(|roots| roots.into_iter().map(PathBuf::from).collect::<Vec<_>>())
                 .unwrap_or(default.roots),
+            root_prefixes: op.root_prefixes.map(Into::into),
+            workspace_root: op.workspace_root.map(PathBuf::from),
+            alias_root: op.alias_root.map(PathBuf::from),
+            dedupe: op.dedupe.unwrap_or(default.dedupe),
+            dedupe_root: op.dedupe_root.map(PathBuf::from),
+            strict_dependencies: op.strict_dependencies.map(Into::into),
             symlinks: op.symlinks.unwrap_or(default.symlinks),
+            symlink_escape: op.symlink_escape.map(Into::into),
+            externals: default.externals,
+            specifier_rewrites: default.specifier_rewrites,
             builtin_modules: op.builtin_modules.unwrap_or(default.builtin_modules),
+            builtin_modules_node_version: op
+                .builtin_modules_node_version
+                .map(|version| version.parse().unwrap_or_else(|err: String| panic!("{err}"))),
+            fs_operation_budget: op
+                .fs_operation_budget
+                .map(|budget| budget as usize)
+                .or(default.fs_operation_budget),
+            disable_cache: op.disable_cache.unwrap_or(default.disable_cache),
         }
     }
 }
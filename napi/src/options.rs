@@ -17,6 +17,30 @@ pub struct NapiResolveOptions {
     /// Default `None`
     pub tsconfig: Option<TsconfigOptions>,
 
+    /// TypeScript configuration files scoped by directory.
+    ///
+    /// Requests from under a directory use the tsconfig of the longest matching directory,
+    /// requests from elsewhere fall back to `tsconfig` and `tsconfigDiscovery`.
+    ///
+    /// Default `[]`
+    pub tsconfig_scopes: Option<Vec<TsconfigScope>>,
+
+    /// Find the closest `tsconfig.json` or `jsconfig.json` above the importing directory
+    /// and apply its `paths`.
+    ///
+    /// Only used when `tsconfig` is not set and no `tsconfigScopes` matches.
+    ///
+    /// Default `false`
+    pub tsconfig_discovery: Option<bool>,
+
+    /// When tsconfig `paths` are tried.
+    ///
+    /// * `First`: before everything else, including aliases and package exports.
+    /// * `Fallback`: only when the request cannot be resolved otherwise, before `fallback`.
+    ///
+    /// Default `First`
+    pub tsconfig_paths_precedence: Option<TsconfigPathsPrecedence>,
+
     /// Alias for [ResolveOptions::alias] and [ResolveOptions::fallback].
     ///
     /// For the second value of the tuple, `None -> AliasValue::Ignore`, Some(String) ->
@@ -25,6 +49,18 @@ pub struct NapiResolveOptions {
     /// A trailing $ can also be added to the given object's keys to signify an exact match.
     pub alias: Option<HashMap<String, Vec<Option<String>>>>,
 
+    /// Aliases whose keys are regular expressions matched against the whole specifier, tried after `alias`.
+    /// Values may reference capture groups with `$1` or `${name}`.
+    ///
+    /// Panics on invalid patterns.
+    pub regex_alias: Option<HashMap<String, Vec<Option<String>>>>,
+
+    /// Skip `alias` and `regexAlias` when the importing directory is inside a node_modules directory,
+    /// so application aliases do not rewrite imports of third-party packages.
+    ///
+    /// Default `false`
+    pub alias_skip_node_modules: Option<bool>,
+
     /// A list of alias fields in description files.
     /// Specify a field, such as `browser`, to be parsed according to [this specification](https://github.com/defunctzombie/package-browser-field-spec).
     /// Can be a path to json object such as `["path", "to", "exports"]`.
@@ -33,8 +69,20 @@ pub struct NapiResolveOptions {
     #[napi(ts_type = "(string | string[])[]")]
     pub alias_fields: Option<Vec<StrOrStrListType>>,
 
+    /// Overrides the alias fields of packages by their package.json "name".
+    ///
+    /// `null` ignores the alias fields of the package,
+    /// an object keyed like the "browser" field replaces them, where `null` values ignore the key.
+    ///
+    /// Default `{}`
+    #[napi(
+        ts_type = "Record<string, Record<string, string | undefined | null> | undefined | null>"
+    )]
+    pub alias_field_overrides: Option<HashMap<String, Option<AliasFieldOverrideEntries>>>,
+
     /// Condition names for exports field which defines entry points of a package.
     /// The key order in the exports field is significant. During condition matching, earlier entries have higher priority and take precedence over later entries.
+    /// The `"..."` item is expanded to the default values.
     ///
     /// Default `[]`
     pub condition_names: Option<Vec<String>>,
@@ -44,6 +92,21 @@ pub struct NapiResolveOptions {
     /// Default `["package.json"]`
     pub description_files: Option<Vec<String>>,
 
+    /// Description files parsed as JSONC, allowing comments and trailing commas.
+    /// Files not listed, including `package.json` by default, are parsed as strict JSON.
+    ///
+    /// Default `[]`
+    pub jsonc_description_files: Option<Vec<String>>,
+
+    /// Stop looking up description files in parent directories at this boundary,
+    /// instead of walking up to the file system root.
+    ///
+    /// Either an absolute path, or `'node_modules'` to stop below the nearest `node_modules` directory.
+    /// Importers outside of the path are not limited.
+    ///
+    /// Default `None`
+    pub description_files_boundary: Option<String>,
+
     /// If true, it will not allow extension-less files.
     /// So by default `require('./foo')` works if `./foo` has a `.js` extension,
     /// but with this enabled only `require('./foo.js')` will work.
@@ -79,15 +142,57 @@ pub struct NapiResolveOptions {
     /// Attempt to resolve these extensions in order.
     /// If multiple files share the same name but have different extensions,
     /// will resolve the one with the extension listed first in the array and skip the rest.
+    /// The `"..."` item is expanded to the default values.
     ///
     /// Default `[".js", ".json", ".node"]`
     pub extensions: Option<Vec<String>>,
 
+    /// Try `extensions` in the order of how often each of them resolved a file, most frequent first.
+    /// The result is not deterministic when a specifier matches files with different extensions.
+    ///
+    /// Default `false`
+    pub adaptive_extensions: Option<bool>,
+
+    /// Read a directory once instead of looking up each candidate in it
+    /// when `extensions` and `extensionInfixes` produce at least this many candidates.
+    /// File names are compared case-insensitively, names with non-ASCII characters are always looked up.
+    ///
+    /// Default `None`
+    pub directory_listing_threshold: Option<u32>,
+
+    /// Infixes tried in order between the file name and each extension,
+    /// e.g. `["ios", "native"]` tries `Button.ios.js` and `Button.native.js` before `Button.js`.
+    ///
+    /// Default `[]`
+    pub extension_infixes: Option<Vec<String>>,
+
+    /// Platform suffixes of native addons, e.g. `["linux-x64-gnu", "linux-x64"]`.
+    ///
+    /// When `foo.node` is not found, `foo.{suffix}.node` and `prebuilds/{suffix}/foo.node`
+    /// are tried for each suffix in order.
+    ///
+    /// Default `[]`
+    pub native_addon_suffixes: Option<Vec<String>>,
+
     /// Redirect module requests when normal resolving fails.
     ///
     /// Default `[]`
     pub fallback: Option<HashMap<String, Vec<Option<String>>>>,
 
+    /// Only consult `fallback` for bare specifiers such as `path` or `@scope/pkg`,
+    /// so a mistyped relative or absolute request is not redirected to a polyfill.
+    ///
+    /// Default `false`
+    pub fallback_bare_only: Option<bool>,
+
+    /// Option sets tried in order, sharing the cache, until one of them resolves.
+    ///
+    /// For example, resolve with ESM conditions first, then with CommonJS conditions.
+    /// Unlike the other options, unset fields of a fallback use the defaults instead of the outer option set.
+    ///
+    /// Default `[]`
+    pub fallback_options: Option<Vec<NapiResolveOptions>>,
+
     /// Request passed to resolve is already fully specified and extensions or main files are not resolved for it (they are still resolved for internal requests).
     ///
     /// See also webpack configuration [resolve.fullySpecified](https://webpack.js.org/configuration/module/#resolvefullyspecified)
@@ -95,11 +200,36 @@ pub struct NapiResolveOptions {
     /// Default `false`
     pub fully_specified: Option<bool>,
 
+    /// Try a relative or absolute specifier containing `#` as a literal path first,
+    /// before treating the `#` as the start of a fragment.
+    ///
+    /// Default `true`
+    pub fragment_as_path: Option<bool>,
+
+    /// Return paths with `/` separators on Windows, e.g. `C:/project/index.js` and `//server/share/index.js` for a UNC path,
+    /// for comparing with POSIX-style paths in JavaScript.
+    ///
+    /// Has no effect on other platforms.
+    ///
+    /// Default `false`
+    pub forward_slashes: Option<bool>,
+
+    /// Also return `relativePath`, the resolved path relative to this absolute directory,
+    /// with `..` for paths outside of it.
+    ///
+    /// Useful for machine-independent paths in persistent caches, combined with `forwardSlashes` on Windows.
+    /// No relative path is returned for a path on another drive or UNC share.
+    ///
+    /// Default `None`
+    pub relative_path_base: Option<String>,
+
     /// A list of main fields in description files
+    /// Can be a path to json object such as `["spm", "main"]` or `"spm.main"`.
+    /// The `"..."` item is expanded to the default values.
     ///
     /// Default `["main"]`.
-    #[napi(ts_type = "string | string[]")]
-    pub main_fields: Option<StrOrStrListType>,
+    #[napi(ts_type = "string | (string | string[])[]")]
+    pub main_fields: Option<Either<String, Vec<StrOrStrListType>>>,
 
     /// The filename to be used while resolving directories.
     ///
@@ -112,11 +242,25 @@ pub struct NapiResolveOptions {
     #[napi(ts_type = "string | string[]")]
     pub modules: Option<StrOrStrListType>,
 
+    /// Stop looking up `modules` in parent directories after this directory,
+    /// instead of walking up to the file system root.
+    ///
+    /// Either an absolute path, `'git'` for the nearest directory containing `.git`, or `'workspace'` for `workspaceRoot`.
+    /// Importers outside of the boundary are not limited.
+    ///
+    /// Default `None`
+    pub modules_boundary: Option<String>,
+
     /// Resolve to a context instead of a file.
     ///
     /// Default `false`
     pub resolve_to_context: Option<bool>,
 
+    /// How requests ending in `/`, e.g. `./dir/`, are resolved.
+    ///
+    /// Default `Index`
+    pub trailing_slash: Option<TrailingSlash>,
+
     /// Prefer to resolve module requests as relative requests instead of using modules from node_modules directories.
     ///
     /// Default `false`
@@ -132,12 +276,68 @@ pub struct NapiResolveOptions {
     /// Default `[]`
     pub restrictions: Option<Vec<Restriction>>,
 
+    /// A list of allowed root directories.
+    ///
+    /// When not empty, resolving to a path whose real path is outside all of these directories fails,
+    /// even when the path escapes through a symlink.
+    ///
+    /// Default `[]`
+    pub allowed_roots: Option<Vec<String>>,
+
     /// A list of directories where requests of server-relative URLs (starting with '/') are resolved.
     /// On non-Windows systems these requests are resolved as an absolute path first.
     ///
     /// Default `[]`
     pub roots: Option<Vec<String>>,
 
+    /// Resolve `~/foo` and `/foo` against a project or package root, as in Parcel, Vite and Nuxt.
+    ///
+    /// The root is the configured `root`, or else the directory of the nearest package.json of the importer,
+    /// so these specifiers keep working inside nested packages.
+    ///
+    /// Default `None`
+    pub root_prefixes: Option<RootPrefixOptions>,
+
+    /// Root directory of a monorepo.
+    ///
+    /// When set, the "packages" globs of its pnpm-workspace.yaml, or the "workspaces" globs of its package.json,
+    /// are expanded to find workspace packages,
+    /// and bare specifiers naming a workspace package resolve to its directory before `node_modules` are looked up.
+    ///
+    /// Default `None`
+    pub workspace_root: Option<String>,
+
+    /// Directory of the application's root package.json whose Parcel style "alias" field is applied.
+    ///
+    /// The field maps specifiers to other specifiers, to paths relative to the package.json, or to `false` to ignore the module,
+    /// e.g. `{ "alias": { "react": "preact/compat", "fs": false } }`.
+    /// It is applied before `alias`, so the manifest takes the highest priority.
+    ///
+    /// <https://parceljs.org/features/dependency-resolution/#aliases>
+    ///
+    /// Default `None`
+    pub alias_root: Option<String>,
+
+    /// Package names which always resolve from the `node_modules` of `dedupeRoot`,
+    /// regardless of the copy nearest to the importer, as in Vite's `resolve.dedupe`.
+    ///
+    /// Avoids duplicated instances of packages such as React in linked package setups.
+    ///
+    /// Default `[]`
+    pub dedupe: Option<Vec<String>>,
+
+    /// The directory the `dedupe` packages resolve from, `dedupe` has no effect when it is not set.
+    ///
+    /// Default `None`
+    pub dedupe_root: Option<String>,
+
+    /// Only resolve bare specifiers declared in the dependency fields of the importer's nearest package.json.
+    ///
+    /// Self-references and Node.js builtin modules are always allowed.
+    ///
+    /// Default `None`
+    pub strict_dependencies: Option<StrictDependencies>,
+
     /// Whether to resolve symlinks to their symlinked location.
     /// When enabled, symlinked resources are resolved to their real path, not their symlinked location.
     /// Note that this may cause module resolution to fail when using tools that symlink packages (like npm link).
@@ -145,11 +345,37 @@ pub struct NapiResolveOptions {
     /// Default `true`
     pub symlinks: Option<bool>,
 
+    /// What to do when a real path leaves the given roots, e.g. for globally linked packages with `npm link`.
+    ///
+    /// Only used when `symlinks` is enabled.
+    ///
+    /// Default `None`, the real path is always used.
+    pub symlink_escape: Option<SymlinkEscapeOptions>,
+
     /// Whether to parse [module.builtinModules](https://nodejs.org/api/module.html#modulebuiltinmodules) or not.
     /// For example, "zlib" will throw [crate::ResolveError::Builtin] when set to true.
     ///
     /// Default `false`
     pub builtin_modules: Option<bool>,
+
+    /// Recognize the builtin modules of this Node.js version, e.g. `"22.5.0"` for `node:sqlite`.
+    /// When set, `node:` specifiers which are not builtin modules of the version are resolved like other specifiers.
+    ///
+    /// Default `None`
+    pub builtin_modules_node_version: Option<String>,
+
+    /// Maximum number of paths a single resolution may probe on the file system,
+    /// the resolution fails with a list of directories where most lookups were spent when exceeded.
+    ///
+    /// Default `None`
+    pub fs_operation_budget: Option<u32>,
+
+    /// Bypass all caches, reading everything from the file system again for every request.
+    ///
+    /// For debugging suspected stale cache results, this is much slower.
+    ///
+    /// Default `false`
+    pub disable_cache: Option<bool>,
 }
 
 #[napi]
@@ -174,6 +400,10 @@ impl EnforceExtension {
     }
 }
 
+/// Entries replacing the alias fields of a package for [NapiResolveOptions::alias_field_overrides],
+/// `None` ignores the key.
+pub type AliasFieldOverrideEntries = HashMap<String, Option<String>>;
+
 /// Alias Value for [ResolveOptions::alias] and [ResolveOptions::fallback].
 /// Use struct because napi don't support structured union now
 #[napi(object)]
@@ -183,6 +413,60 @@ pub struct Restriction {
     pub regex: Option<String>,
 }
 
+/// Root Prefix Options
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct RootPrefixOptions {
+    /// Resolve `~/foo` against the root.
+    pub tilde: Option<bool>,
+
+    /// Resolve `/foo` against the root, before `roots` and the file system root are tried.
+    pub slash: Option<bool>,
+
+    /// The root directory, the directory of the nearest package.json of the importer when unset.
+    pub root: Option<String>,
+}
+
+/// Strict Dependencies Options
+///
+/// Packages in "dependencies" are always allowed.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct StrictDependencies {
+    /// Allow packages in "devDependencies", default `true`.
+    pub dev_dependencies: Option<bool>,
+
+    /// Allow packages in "peerDependencies", default `true`.
+    pub peer_dependencies: Option<bool>,
+
+    /// Allow packages in "optionalDependencies", default `true`.
+    pub optional_dependencies: Option<bool>,
+}
+
+/// Symlink Escape Options
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct SymlinkEscapeOptions {
+    /// Project or workspace roots, a real path outside all of them is an escape.
+    pub roots: Vec<String>,
+
+    /// * `'canonical'`: keep the real path, the default.
+    /// * `'original'`: keep the original symlinked path.
+    /// * `'error'`: fail the resolution.
+    #[napi(ts_type = "'canonical' | 'original' | 'error'")]
+    pub policy: Option<String>,
+}
+
+/// A tsconfig for requests from under `directory`, see `tsconfigScopes`.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct TsconfigScope {
+    /// Absolute path to the directory.
+    pub directory: String,
+
+    pub tsconfig: TsconfigOptions,
+}
+
 /// Tsconfig Options
 ///
 /// Derived from [tsconfig-paths-webpack-plugin](https://github.com/dividab/tsconfig-paths-webpack-plugin#options)
@@ -201,6 +485,10 @@ pub struct TsconfigOptions {
     /// * `string[]`: manually provided relative or absolute path.
     #[napi(ts_type = "'auto' | string[]")]
     pub references: Option<Either<String, Vec<String>>>,
+
+    /// The tsconfig content used in place of reading `config_file`,
+    /// which then only gives its virtual location: `extends` and relative paths resolve against its directory.
+    pub content: Option<String>,
 }
 
 impl Into<oxc_resolver::Restriction> for Restriction {
@@ -218,6 +506,40 @@ impl Into<oxc_resolver::Restriction> for Restriction {
     }
 }
 
+#[napi]
+#[derive(Debug, PartialEq, Eq)]
+pub enum TsconfigPathsPrecedence {
+    First,
+    Fallback,
+}
+
+impl From<TsconfigPathsPrecedence> for oxc_resolver::TsconfigPathsPrecedence {
+    fn from(precedence: TsconfigPathsPrecedence) -> Self {
+        match precedence {
+            TsconfigPathsPrecedence::First => oxc_resolver::TsconfigPathsPrecedence::First,
+            TsconfigPathsPrecedence::Fallback => oxc_resolver::TsconfigPathsPrecedence::Fallback,
+        }
+    }
+}
+
+#[napi]
+#[derive(Debug, PartialEq, Eq)]
+pub enum TrailingSlash {
+    Index,
+    Context,
+    Strict,
+}
+
+impl From<TrailingSlash> for oxc_resolver::TrailingSlash {
+    fn from(trailing_slash: TrailingSlash) -> Self {
+        match trailing_slash {
+            TrailingSlash::Index => oxc_resolver::TrailingSlash::Index,
+            TrailingSlash::Context => oxc_resolver::TrailingSlash::Context,
+            TrailingSlash::Strict => oxc_resolver::TrailingSlash::Strict,
+        }
+    }
+}
+
 impl Into<oxc_resolver::EnforceExtension> for EnforceExtension {
     fn into(self) -> oxc_resolver::EnforceExtension {
         match self {
@@ -244,6 +566,41 @@ impl Into<oxc_resolver::TsconfigOptions> for TsconfigOptions {
                 ),
                 None => oxc_resolver::TsconfigReferences::Disabled,
             },
+            content: self.content,
+        }
+    }
+}
+
+impl From<StrictDependencies> for oxc_resolver::StrictDependencies {
+    fn from(strict_dependencies: StrictDependencies) -> Self {
+        Self {
+            dev_dependencies: strict_dependencies.dev_dependencies.unwrap_or(true),
+            peer_dependencies: strict_dependencies.peer_dependencies.unwrap_or(true),
+            optional_dependencies: strict_dependencies.optional_dependencies.unwrap_or(true),
+        }
+    }
+}
+
+impl From<RootPrefixOptions> for oxc_resolver::RootPrefixOptions {
+    fn from(root_prefixes: RootPrefixOptions) -> Self {
+        Self {
+            tilde: root_prefixes.tilde.unwrap_or(false),
+            slash: root_prefixes.slash.unwrap_or(false),
+            root: root_prefixes.root.map(PathBuf::from),
+        }
+    }
+}
+
+impl From<SymlinkEscapeOptions> for oxc_resolver::SymlinkEscapeOptions {
+    fn from(symlink_escape: SymlinkEscapeOptions) -> Self {
+        Self {
+            roots: symlink_escape.roots.into_iter().map(PathBuf::from).collect::<Vec<_>>(),
+            policy: match symlink_escape.policy.as_deref() {
+                None | Some("canonical") => oxc_resolver::SymlinkEscapePolicy::Canonical,
+                Some("original") => oxc_resolver::SymlinkEscapePolicy::Original,
+                Some("error") => oxc_resolver::SymlinkEscapePolicy::Error,
+                Some(opt) => panic!("`{}` is not a valid option for symlink escape policy", opt),
+            },
         }
     }
 }
@@ -1,17 +1,73 @@
-use std::sync::OnceLock;
+use std::{
+    fs::File,
+    io::{self, Write},
+    sync::{Mutex, OnceLock},
+};
 
+use napi::{
+    bindgen_prelude::{Either, Function, Unknown},
+    threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode},
+};
+use napi_derive::napi;
 use tracing_subscriber::filter::Targets;
+use tracing_subscriber::fmt::{writer::BoxMakeWriter, MakeWriter};
 use tracing_subscriber::prelude::__tracing_subscriber_SubscriberExt;
-use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::util::{SubscriberInitExt, TryInitError};
+
+static TRACING: OnceLock<()> = OnceLock::new();
 
 /// To debug `oxc_resolver`:
 /// `OXC_LOG=DEBUG your program`
+///
+/// Logs are written to stderr unless [init_tracing_with_output] was called first.
 pub fn init_tracing() {
-    static TRACING: OnceLock<()> = OnceLock::new();
+    // A subscriber installed by the host is kept, the resolver's events go to it.
+    let _ = install(BoxMakeWriter::new(io::stderr), true);
+}
+
+/// Route tracing output to a file path or a JS callback instead of stderr.
+///
+/// The callback receives one formatted log line per call.
+/// `OXC_LOG` still controls which events are emitted.
+///
+/// Must be called before the first `ResolverFactory` is constructed,
+/// returns `false` if tracing has already been initialized.
+/// Throws if another global tracing subscriber is already installed in the process.
+#[napi(js_name = "initTracing")]
+pub fn init_tracing_with_output(
+    #[napi(ts_arg_type = "string | ((message: string) => void)")] output: Either<
+        String,
+        Function<String>,
+    >,
+) -> napi::Result<bool> {
+    // Avoids truncating the file when tracing is already initialized,
+    // `install` decides which of concurrent calls wins.
+    if TRACING.get().is_some() {
+        return Ok(false);
+    }
+    let writer = match output {
+        Either::A(path) => BoxMakeWriter::new(Mutex::new(File::create(path)?)),
+        Either::B(callback) => {
+            let callback = callback.build_threadsafe_function().weak::<true>().build()?;
+            BoxMakeWriter::new(CallbackWriter { callback })
+        }
+    };
+    match install(writer, false) {
+        Some(Ok(())) => Ok(true),
+        Some(Err(err)) => Err(napi::Error::from_reason(err.to_string())),
+        None => Ok(false),
+    }
+}
+
+/// Installs the global subscriber once, ANSI colors are only used for terminals.
+///
+/// Returns `None` when tracing was already initialized by an earlier or concurrent call.
+fn install(writer: BoxMakeWriter, ansi: bool) -> Option<Result<(), TryInitError>> {
+    let mut result = None;
     TRACING.get_or_init(|| {
         // Usage without the `regex` feature.
         // <https://github.com/tokio-rs/tracing/issues/1436#issuecomment-918528013>
-        tracing_subscriber::registry()
+        let installed = tracing_subscriber::registry()
             .with(std::env::var("OXC_LOG").map_or_else(
                 |_| Targets::new(),
                 |env_var| {
@@ -19,7 +75,51 @@ pub fn init_tracing() {
                     Targets::from_str(&env_var).unwrap()
                 },
             ))
-            .with(tracing_subscriber::fmt::layer())
-            .init();
+            .with(tracing_subscriber::fmt::layer().with_writer(writer).with_ansi(ansi))
+            .try_init();
+        result = Some(installed);
     });
+    result
+}
+
+type LogCallback = ThreadsafeFunction<String, Unknown, String, false, true>;
+
+/// Sends each formatted event to a JS callback.
+struct CallbackWriter {
+    callback: LogCallback,
+}
+
+impl<'a> MakeWriter<'a> for CallbackWriter {
+    type Writer = CallbackLine<'a>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        CallbackLine { callback: &self.callback, buf: Vec::new() }
+    }
+}
+
+/// Buffers a single event, the fmt layer creates one writer per event.
+struct CallbackLine<'a> {
+    callback: &'a LogCallback,
+    buf: Vec<u8>,
+}
+
+impl Write for CallbackLine<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buf.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for CallbackLine<'_> {
+    fn drop(&mut self) {
+        let line = String::from_utf8_lossy(&self.buf);
+        let line = line.trim_end_matches('\n');
+        if !line.is_empty() {
+            self.callback.call(line.to_string(), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+    }
 }
@@ -1,3 +1,7 @@
+use std::str::FromStr;
+
+use serde::{de, Deserialize, Deserializer};
+
 /// Node.js built-in modules
 ///
 /// `node -p "[...require('module').builtinModules].map(b => JSON.stringify(b)).join(',\n')"`
@@ -69,3 +73,73 @@ pub const NODEJS_BUILTINS: &[&str] = &[
     "worker_threads",
     "zlib",
 ];
+
+/// Returns whether `specifier` is one of [NODEJS_BUILTINS], with or without the `node:` prefix.
+///
+/// This is the same as Node.js [module.isBuiltin](https://nodejs.org/api/module.html#moduleisbuiltinmodulename).
+pub fn is_nodejs_builtin(specifier: &str) -> bool {
+    let name = specifier.strip_prefix("node:").unwrap_or(specifier);
+    NODEJS_BUILTINS.binary_search(&name).is_ok()
+}
+
+/// Node.js built-in modules added after [NODEJS_BUILTINS], with the version which added them,
+/// for [crate::ResolveOptions::builtin_modules_node_version].
+///
+/// Modules marked `true` are only available with the `node:` prefix, e.g. `node:test`.
+pub const NODEJS_VERSIONED_BUILTINS: &[(&str, NodeVersion, bool)] = &[
+    ("inspector/promises", NodeVersion::new(19, 0, 0), false),
+    ("readline/promises", NodeVersion::new(17, 0, 0), false),
+    ("sea", NodeVersion::new(20, 12, 0), true),
+    ("sqlite", NodeVersion::new(22, 5, 0), true),
+    ("test", NodeVersion::new(18, 0, 0), true),
+    ("test/reporters", NodeVersion::new(19, 9, 0), true),
+    ("wasi", NodeVersion::new(13, 3, 0), false),
+];
+
+/// A Node.js version, parsed from `"22"`, `"22.5"` or `"v22.5.0"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl NodeVersion {
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self { major, minor, patch }
+    }
+}
+
+impl FromStr for NodeVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.strip_prefix('v').unwrap_or(s).split('.').map(str::parse::<u32>);
+        let invalid = || format!("Invalid Node.js version '{s}'");
+        let major = parts.next().and_then(Result::ok).ok_or_else(invalid)?;
+        let minor = parts.next().map_or(Ok(0), |part| part.map_err(|_| invalid()))?;
+        let patch = parts.next().map_or(Ok(0), |part| part.map_err(|_| invalid()))?;
+        if parts.next().is_some() {
+            return Err(invalid());
+        }
+        Ok(Self { major, minor, patch })
+    }
+}
+
+impl<'de> Deserialize<'de> for NodeVersion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(de::Error::custom)
+    }
+}
+
+/// Returns whether `specifier` is a builtin module of the Node.js `version`, with or without the `node:` prefix.
+///
+/// Unlike [is_nodejs_builtin], this includes [NODEJS_VERSIONED_BUILTINS] added up to `version`.
+pub fn is_nodejs_builtin_in(specifier: &str, version: NodeVersion) -> bool {
+    let (name, prefixed) =
+        specifier.strip_prefix("node:").map_or((specifier, false), |name| (name, true));
+    NODEJS_BUILTINS.binary_search(&name).is_ok()
+        || NODEJS_VERSIONED_BUILTINS.iter().any(|(builtin, since, prefix_only)| {
+            *builtin == name && *since <= version && (prefixed || !prefix_only)
+        })
+}
@@ -1,49 +1,348 @@
 use once_cell::sync::OnceCell as OnceLock;
 use std::{
     borrow::{Borrow, Cow},
+    cmp,
     convert::AsRef,
+    ffi::{OsStr, OsString},
+    fmt,
     hash::{BuildHasherDefault, Hash, Hasher},
     io,
     ops::Deref,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, RwLock,
+    },
 };
 
 use dashmap::{DashMap, DashSet};
-use rustc_hash::FxHasher;
+use rustc_hash::{FxHashSet, FxHasher};
 
 use crate::{
-    context::ResolveContext as Ctx, package_json::PackageJson, path::PathUtil, FileMetadata,
-    FileSystem, ResolveError, ResolveOptions, TsConfig,
+    context::{ResolveAttemptReason, ResolveContext as Ctx},
+    package_json::PackageJson,
+    path::PathUtil,
+    workspaces::Workspaces,
+    DescriptionFilesBoundary, FileMetadata, FileSystem, ResolveError, ResolveOptions, TsConfig,
+    TsconfigProvider,
 };
 
+/// Number of entries held by the resolver cache, returned from [crate::ResolverGeneric::cache_stats].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Cached paths, including directories and paths that do not exist.
+    pub paths: usize,
+    /// Parsed package.json files.
+    pub package_jsons: usize,
+    /// Parsed tsconfig.json files.
+    pub tsconfigs: usize,
+    /// Number of files resolved with each extension, most frequent first and alphabetically on ties,
+    /// counted when [ResolveOptions::adaptive_extensions] is enabled.
+    ///
+    /// This is the order extensions are tried in, which is refreshed after a number of new hits.
+    pub extension_hits: Vec<(String, u64)>,
+}
+
+/// Approximate number of bytes held by the resolver cache, returned from [crate::ResolverGeneric::approximate_memory_usage].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// Cached paths with their metadata, real paths and directory entries.
+    pub paths: usize,
+    /// Parsed package.json files, including their JSON documents.
+    pub package_jsons: usize,
+    /// Parsed tsconfig.json files.
+    pub tsconfigs: usize,
+    /// Yarn PnP manifests, estimated by the size of their `.pnp.cjs` files.
+    pub pnp_manifests: usize,
+}
+
+impl MemoryUsage {
+    /// Sum of all categories.
+    pub fn total(&self) -> usize {
+        self.paths + self.package_jsons + self.tsconfigs + self.pnp_manifests
+    }
+}
+
+/// Number of extension hits after which [Cache::order_extensions] sorts the extensions again.
+pub const EXTENSION_ORDER_INTERVAL: u64 = 16;
+
+/// Configured extensions and their order sorted by [Cache::order_extensions].
+type ExtensionOrder = (Box<[String]>, Arc<[String]>);
+
+/// Orders extensions by their number of hits, most frequent first and alphabetically on ties.
+/// Extensions without hits compare equal, so a stable sort keeps their configured order.
+fn compare_extension_hits(a: (&str, u64), b: (&str, u64)) -> cmp::Ordering {
+    b.1.cmp(&a.1).then_with(|| if a.1 == 0 { cmp::Ordering::Equal } else { a.0.cmp(b.0) })
+}
+
+/// File names of [Cache::find_tsconfig], in order of precedence.
+const TSCONFIG_NAMES: [&str; 2] = ["tsconfig.json", "jsconfig.json"];
+
 #[derive(Default)]
 pub struct Cache<Fs> {
     pub(crate) fs: Fs,
     paths: DashSet<CachedPath, BuildHasherDefault<IdentityHasher>>,
     tsconfigs: DashMap<PathBuf, Arc<TsConfig>, BuildHasherDefault<FxHasher>>,
+    workspaces: DashMap<PathBuf, Arc<Workspaces>, BuildHasherDefault<FxHasher>>,
+    /// The closest tsconfig of a directory, see [Cache::find_tsconfig].
+    tsconfig_discovery: DashMap<PathBuf, Option<PathBuf>, BuildHasherDefault<FxHasher>>,
+    extension_hits: DashMap<String, AtomicU64, BuildHasherDefault<FxHasher>>,
+    /// The configured extensions and their order from the last sort, see [Cache::order_extensions].
+    extension_order: RwLock<Option<ExtensionOrder>>,
+    /// Extension hits since [Cache::extension_order] was sorted.
+    extension_order_hits: AtomicU64,
+    /// Paths dropped by [Cache::invalidate], see [crate::ResolverGeneric::take_invalidated].
+    invalidated: DashSet<PathBuf, BuildHasherDefault<FxHasher>>,
+    /// [crate::ResolveOptions::disable_cache]
+    disabled: bool,
 }
 
 impl<Fs: FileSystem> Cache<Fs> {
-    pub fn new(fs: Fs) -> Self {
-        Self { fs, paths: DashSet::default(), tsconfigs: DashMap::default() }
+    pub fn new(fs: Fs, disabled: bool) -> Self {
+        Self {
+            fs,
+            paths: DashSet::default(),
+            tsconfigs: DashMap::default(),
+            workspaces: DashMap::default(),
+            tsconfig_discovery: DashMap::default(),
+            extension_hits: DashMap::default(),
+            extension_order: RwLock::default(),
+            extension_order_hits: AtomicU64::default(),
+            invalidated: DashSet::default(),
+            disabled,
+        }
     }
 
     pub fn clear(&self) {
         self.paths.clear();
         self.tsconfigs.clear();
+        self.workspaces.clear();
+        self.tsconfig_discovery.clear();
+        self.extension_hits.clear();
+        *self.extension_order.write().unwrap() = None;
+        self.extension_order_hits.store(0, Ordering::Relaxed);
+        self.invalidated.clear();
     }
 
-    pub fn value(&self, path: &Path) -> CachedPath {
-        let hash = {
-            let mut hasher = FxHasher::default();
-            path.hash(&mut hasher);
-            hasher.finish()
+    /// Drops the cached paths of `path` and everything under it,
+    /// along with their metadata, real paths and package.json files, and the tsconfig files under `path`.
+    ///
+    /// When `path` is not a cached directory, e.g. a changed package.json or a created file,
+    /// its directory is dropped along with everything under it,
+    /// because the directory holds its package.json, entries and real path, and cached paths keep their parent.
+    ///
+    /// The tsconfig discovered for directories is dropped for the directories under `path`,
+    /// and for the directories under a `tsconfig.json` or `jsconfig.json` given as `path`.
+    pub fn invalidate(&self, path: &Path) {
+        let scope = match path.parent() {
+            Some(parent) if !self.is_cached_dir(path) => parent,
+            _ => path,
         };
+        self.paths.retain(|cached_path| {
+            let invalidated = cached_path.path.starts_with(scope);
+            if invalidated {
+                cached_path.invalidated.store(true, Ordering::Relaxed);
+                self.invalidated.insert(cached_path.to_path_buf());
+            }
+            !invalidated
+        });
+        self.tsconfigs.retain(|tsconfig_path, _| {
+            let invalidated = tsconfig_path.starts_with(path);
+            if invalidated {
+                self.invalidated.insert(tsconfig_path.clone());
+            }
+            !invalidated
+        });
+        self.workspaces.retain(|root, _| !root.starts_with(scope));
+        // A created or removed tsconfig changes the discovery of every directory under its own.
+        let tsconfig_dir = path.parent().filter(|_| {
+            path.file_name()
+                .is_some_and(|name| TSCONFIG_NAMES.iter().any(|tsconfig| name == *tsconfig))
+        });
+        self.tsconfig_discovery.retain(|dir, tsconfig| {
+            !dir.starts_with(path)
+                && !tsconfig_dir.is_some_and(|tsconfig_dir| dir.starts_with(tsconfig_dir))
+                && !tsconfig.as_ref().is_some_and(|tsconfig| tsconfig.starts_with(path))
+        });
+    }
+
+    fn is_cached_dir(&self, path: &Path) -> bool {
+        self.paths.get((hash_path(path), path).borrow() as &dyn CacheKey).is_some_and(
+            |cached_path| cached_path.meta.get().copied().flatten().is_some_and(|meta| meta.is_dir),
+        )
+    }
+
+    /// Returns the paths dropped by [Cache::invalidate] since the last call, sorted.
+    pub fn take_invalidated(&self) -> Vec<PathBuf> {
+        let mut paths = vec![];
+        self.invalidated.retain(|path| {
+            paths.push(path.clone());
+            false
+        });
+        paths.sort_unstable();
+        paths
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let package_jsons = self
+            .paths
+            .iter()
+            .filter(|path| path.package_json.get().is_some_and(Option::is_some))
+            .count();
+        let mut extension_hits = self
+            .extension_hits
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().load(Ordering::Relaxed)))
+            .collect::<Vec<_>>();
+        extension_hits.sort_by(|a, b| compare_extension_hits((&a.0, a.1), (&b.0, b.1)));
+        CacheStats {
+            paths: self.paths.len(),
+            package_jsons,
+            tsconfigs: self.tsconfigs.len(),
+            extension_hits,
+        }
+    }
+
+    /// Approximate memory usage of the cached paths, package.json and tsconfig files.
+    ///
+    /// A package.json shared by several cached paths is counted once.
+    pub fn approximate_memory_usage(&self) -> MemoryUsage {
+        let mut package_jsons = FxHashSet::default();
+        let mut usage = MemoryUsage::default();
+        for cached_path in self.paths.iter() {
+            usage.paths += cached_path.approximate_memory_usage();
+            if let Some(Some(package_json)) = cached_path.package_json.get() {
+                if package_jsons.insert(Arc::as_ptr(package_json)) {
+                    usage.package_jsons += package_json.approximate_memory_usage();
+                }
+            }
+        }
+        usage.paths += self
+            .tsconfig_discovery
+            .iter()
+            .map(|entry| {
+                entry.key().capacity() + entry.value().as_ref().map_or(0, PathBuf::capacity)
+            })
+            .sum::<usize>();
+        usage.tsconfigs = self
+            .tsconfigs
+            .iter()
+            .map(|tsconfig| tsconfig.key().capacity() + tsconfig.value().approximate_memory_usage())
+            .sum();
+        usage
+    }
+
+    /// Find the closest `tsconfig.json` or `jsconfig.json` in the directory of `cached_path` or its ancestors.
+    ///
+    /// The result is cached per directory until [Cache::invalidate] of a tsconfig above it.
+    /// The found file and the missing candidates closer to `cached_path` are added to the dependencies.
+    pub fn find_tsconfig(&self, cached_path: &CachedPath, ctx: &mut Ctx) -> Option<PathBuf> {
+        let tsconfig = self.discover_tsconfig(cached_path);
+        if ctx.file_dependencies.is_some() || ctx.missing_dependencies.is_some() {
+            for dir in cached_path.path().ancestors() {
+                for name in TSCONFIG_NAMES {
+                    let path = dir.join(name);
+                    if tsconfig.as_ref() == Some(&path) {
+                        ctx.add_file_dependency(&path);
+                        return tsconfig;
+                    }
+                    ctx.add_missing_dependency(&path);
+                }
+            }
+        }
+        tsconfig
+    }
+
+    fn discover_tsconfig(&self, cached_path: &CachedPath) -> Option<PathBuf> {
+        if !self.disabled {
+            if let Some(tsconfig) = self.tsconfig_discovery.get(cached_path.path()) {
+                return tsconfig.clone();
+            }
+        }
+        let tsconfig = TSCONFIG_NAMES
+            .into_iter()
+            .map(|name| cached_path.path().join(name))
+            .find(|path| self.fs.metadata(path).is_ok_and(|meta| meta.is_file))
+            .or_else(|| cached_path.parent().and_then(|parent| self.discover_tsconfig(parent)));
+        if !self.disabled {
+            self.tsconfig_discovery.insert(cached_path.to_path_buf(), tsconfig.clone());
+        }
+        tsconfig
+    }
+
+    /// Counts a file resolved with `extension`, see [ResolveOptions::adaptive_extensions].
+    pub fn add_extension_hit(&self, extension: &str) {
+        self.extension_order_hits.fetch_add(1, Ordering::Relaxed);
+        if let Some(hits) = self.extension_hits.get(extension) {
+            hits.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        self.extension_hits
+            .entry(extension.to_string())
+            .or_default()
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns `extensions` sorted by the number of files resolved with each of them, in the order of [CacheStats::extension_hits].
+    /// Extensions without hits keep their configured order after them.
+    ///
+    /// The order is stored and only sorted again after [EXTENSION_ORDER_INTERVAL] hits,
+    /// or when a resolver sharing the cache has different extensions.
+    /// Returns `None` before the first sort.
+    pub fn order_extensions(&self, extensions: &[String]) -> Option<Arc<[String]>> {
+        let stored = self.extension_order.read().unwrap().clone();
+        let stale = self.extension_order_hits.load(Ordering::Relaxed) >= EXTENSION_ORDER_INTERVAL;
+        match stored {
+            Some((configured, ordered)) if !stale && *configured == *extensions => {
+                return Some(ordered);
+            }
+            None if !stale => return None,
+            _ => {}
+        }
+        self.extension_order_hits.store(0, Ordering::Relaxed);
+        let mut ordered = extensions
+            .iter()
+            .map(|extension| {
+                let hits = self
+                    .extension_hits
+                    .get(extension)
+                    .map_or(0, |hits| hits.load(Ordering::Relaxed));
+                (extension, hits)
+            })
+            .collect::<Vec<_>>();
+        ordered.sort_by(|a, b| compare_extension_hits((a.0, a.1), (b.0, b.1)));
+        let ordered =
+            ordered.into_iter().map(|(extension, _)| extension.clone()).collect::<Arc<[_]>>();
+        *self.extension_order.write().unwrap() = Some((extensions.into(), Arc::clone(&ordered)));
+        Some(ordered)
+    }
+
+    /// Returns the cached path of `path`.
+    ///
+    /// With [ResolveOptions::disable_cache], cached paths are only shared within the resolution of `ctx`.
+    pub fn value(&self, path: &Path, ctx: &mut Ctx) -> CachedPath {
+        let hash = hash_path(path);
+        if self.disabled {
+            if let Some(cached_path) = ctx.scratch_paths.get(path) {
+                return cached_path.clone();
+            }
+            let parent = path.parent().map(|p| self.value(p, ctx));
+            let cached_path = CachedPath(Arc::new(CachedPathImpl::new(
+                hash,
+                path.to_path_buf().into_boxed_path(),
+                parent,
+            )));
+            ctx.scratch_paths.insert(path.to_path_buf(), cached_path.clone());
+            return cached_path;
+        }
         if let Some(cache_entry) = self.paths.get((hash, path).borrow() as &dyn CacheKey) {
+            #[cfg(feature = "metrics")]
+            metrics::counter!("rspack_resolver.path_cache.hits").increment(1);
             return cache_entry.clone();
         }
-        let parent = path.parent().map(|p| self.value(p));
+        #[cfg(feature = "metrics")]
+        metrics::counter!("rspack_resolver.path_cache.misses").increment(1);
+        let parent = path.parent().map(|p| self.value(p, ctx));
         let data = CachedPath(Arc::new(CachedPathImpl::new(
             hash,
             path.to_path_buf().into_boxed_path(),
@@ -53,17 +352,37 @@ impl<Fs: FileSystem> Cache<Fs> {
         data
     }
 
+    pub fn workspaces<F: FnOnce() -> Result<Workspaces, ResolveError>>(
+        &self,
+        root: &Path,
+        init: F,
+    ) -> Result<Arc<Workspaces>, ResolveError> {
+        if self.disabled {
+            return init().map(Arc::new);
+        }
+        if let Some(workspaces) = self.workspaces.get(root) {
+            return Ok(Arc::clone(workspaces.value()));
+        }
+        let workspaces = Arc::new(init()?);
+        self.workspaces.insert(root.to_path_buf(), Arc::clone(&workspaces));
+        Ok(workspaces)
+    }
+
     pub fn tsconfig<F: FnOnce(&mut TsConfig) -> Result<(), ResolveError>>(
         &self,
         root: bool,
         path: &Path,
+        content: Option<&str>,
+        provider: Option<&dyn TsconfigProvider>,
         callback: F, // callback for modifying tsconfig with `extends`
     ) -> Result<Arc<TsConfig>, ResolveError> {
-        if let Some(tsconfig_ref) = self.tsconfigs.get(path) {
+        if let Some(tsconfig_ref) = self.tsconfigs.get(path).filter(|_| !self.disabled) {
+            trace_cache("tsconfig", path, true);
             return Ok(Arc::clone(tsconfig_ref.value()));
         }
-        let meta = self.fs.metadata(path).ok();
-        let tsconfig_path = if meta.is_some_and(|m| m.is_file) {
+        trace_cache("tsconfig", path, false);
+        let meta = if content.is_some() { None } else { self.fs.metadata(path).ok() };
+        let tsconfig_path = if content.is_some() || meta.is_some_and(|m| m.is_file) {
             Cow::Borrowed(path)
         } else if meta.is_some_and(|m| m.is_dir) {
             Cow::Owned(path.join("tsconfig.json"))
@@ -72,10 +391,16 @@ impl<Fs: FileSystem> Cache<Fs> {
             os_string.push(".json");
             Cow::Owned(PathBuf::from(os_string))
         };
-        let mut tsconfig_string = self
-            .fs
-            .read_to_string(&tsconfig_path)
-            .map_err(|_| ResolveError::TsconfigNotFound(path.to_path_buf()))?;
+        let tsconfig_string = content
+            .map(ToString::to_string)
+            .or_else(|| provider.and_then(|p| p.tsconfig(&tsconfig_path)));
+        let mut tsconfig_string = match tsconfig_string {
+            Some(tsconfig_string) => tsconfig_string,
+            None => self
+                .fs
+                .read_to_string(&tsconfig_path)
+                .map_err(|_| ResolveError::TsconfigNotFound(path.to_path_buf()))?,
+        };
         let mut tsconfig =
             TsConfig::parse(root, &tsconfig_path, &mut tsconfig_string).map_err(|error| {
                 ResolveError::from_serde_json_error(
@@ -86,7 +411,9 @@ impl<Fs: FileSystem> Cache<Fs> {
             })?;
         callback(&mut tsconfig)?;
         let tsconfig = Arc::new(tsconfig.build());
-        self.tsconfigs.insert(path.to_path_buf(), Arc::clone(&tsconfig));
+        if !self.disabled {
+            self.tsconfigs.insert(path.to_path_buf(), Arc::clone(&tsconfig));
+        }
         Ok(tsconfig)
     }
 }
@@ -94,6 +421,12 @@ impl<Fs: FileSystem> Cache<Fs> {
 #[derive(Clone)]
 pub struct CachedPath(Arc<CachedPathImpl>);
 
+impl fmt::Debug for CachedPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CachedPath").field(&self.path).finish()
+    }
+}
+
 impl Hash for CachedPath {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.0.hash.hash(state);
@@ -133,26 +466,59 @@ impl CacheKey for CachedPath {
     }
 }
 
+/// Traces whether the cached `cache` entry of `path` is reused (`hit`) or loaded from the file system.
+pub fn trace_cache(cache: &'static str, path: &Path, hit: bool) {
+    tracing::trace!(cache, path = ?path, hit, "cache");
+}
+
+/// Hash of `path`, used as the cache key and as [crate::Resolution::path_hash].
+pub fn hash_path(path: &Path) -> u64 {
+    let mut hasher = FxHasher::default();
+    path.hash(&mut hasher);
+    hasher.finish()
+}
+
 pub struct CachedPathImpl {
     hash: u64,
     path: Box<Path>,
     parent: Option<CachedPath>,
     meta: OnceLock<Option<FileMetadata>>,
+    /// The error which made the metadata unavailable when it was not a not found error.
+    meta_error: OnceLock<ResolveError>,
+    /// File names of the directory entries in ASCII lowercase, see [ResolveOptions::directory_listing_threshold].
+    entries: OnceLock<Option<FxHashSet<OsString>>>,
     canonicalized: OnceLock<Option<PathBuf>>,
     node_modules: OnceLock<Option<CachedPath>>,
     package_json: OnceLock<Option<Arc<PackageJson>>>,
+    /// Removed from the cache by [Cache::invalidate].
+    invalidated: AtomicBool,
 }
 
 impl CachedPathImpl {
+    fn approximate_memory_usage(&self) -> usize {
+        let path_size = |path: &Option<PathBuf>| path.as_ref().map_or(0, PathBuf::capacity);
+        let entries = self.entries.get().and_then(Option::as_ref).map_or(0, |entries| {
+            entries.capacity() * std::mem::size_of::<OsString>()
+                + entries.iter().map(OsString::capacity).sum::<usize>()
+        });
+        std::mem::size_of::<Self>()
+            + self.path.as_os_str().len()
+            + entries
+            + self.canonicalized.get().map_or(0, path_size)
+    }
+
     fn new(hash: u64, path: Box<Path>, parent: Option<CachedPath>) -> Self {
         Self {
             hash,
             path,
             parent,
             meta: OnceLock::new(),
+            meta_error: OnceLock::new(),
+            entries: OnceLock::new(),
             canonicalized: OnceLock::new(),
             node_modules: OnceLock::new(),
             package_json: OnceLock::new(),
+            invalidated: AtomicBool::new(false),
         }
     }
 
@@ -164,34 +530,92 @@ impl CachedPathImpl {
         self.path.to_path_buf()
     }
 
+    pub fn path_hash(&self) -> u64 {
+        self.hash
+    }
+
     pub fn parent(&self) -> Option<&CachedPath> {
         self.parent.as_ref()
     }
 
     fn meta<Fs: FileSystem>(&self, fs: &Fs) -> Option<FileMetadata> {
-        *self.meta.get_or_init(|| fs.metadata(&self.path).ok())
+        trace_cache("metadata", &self.path, self.meta.get().is_some());
+        *self.meta.get_or_init(|| {
+            #[cfg(feature = "metrics")]
+            metrics::counter!("rspack_resolver.fs.metadata").increment(1);
+            fs.metadata(&self.path)
+                .map_err(|error| {
+                    if !is_not_found(&error) {
+                        _ = self.meta_error.set(ResolveError::from_io_error(&self.path, &error));
+                    }
+                })
+                .ok()
+        })
+    }
+
+    /// File names of the entries of this directory in ASCII lowercase, `None` when it cannot be read.
+    pub fn entries<Fs: FileSystem>(&self, fs: &Fs) -> Option<&FxHashSet<OsString>> {
+        trace_cache("read_dir", &self.path, self.entries.get().is_some());
+        self.entries
+            .get_or_init(|| {
+                #[cfg(feature = "metrics")]
+                metrics::counter!("rspack_resolver.fs.read_dir").increment(1);
+                fs.read_dir(&self.path).ok().map(|entries| {
+                    entries
+                        .into_iter()
+                        .filter_map(|entry| entry.file_name().map(OsStr::to_ascii_lowercase))
+                        .collect()
+                })
+            })
+            .as_ref()
+    }
+
+    /// Records that the path does not exist, e.g. when it is not listed by its parent directory.
+    pub fn mark_missing(&self) {
+        _ = self.meta.set(None);
+    }
+
+    /// Whether the path exists, without recording a dependency.
+    pub fn exists<Fs: FileSystem>(&self, fs: &Fs) -> bool {
+        self.meta(fs).is_some()
     }
 
     pub fn is_file<Fs: FileSystem>(&self, fs: &Fs, ctx: &mut Ctx) -> bool {
+        ctx.add_fs_operation(self.path());
+        if ctx.is_excluded_candidate(self.path()) {
+            return false;
+        }
         if let Some(meta) = self.meta(fs) {
             ctx.add_file_dependency(self.path());
+            if !meta.is_file {
+                ctx.add_attempt(self.path(), ResolveAttemptReason::NotAFile);
+            }
             meta.is_file
         } else {
-            ctx.add_missing_dependency(self.path());
+            self.add_missing(ctx);
             false
         }
     }
 
     pub fn is_dir<Fs: FileSystem>(&self, fs: &Fs, ctx: &mut Ctx) -> bool {
+        ctx.add_fs_operation(self.path());
         self.meta(fs).map_or_else(
             || {
-                ctx.add_missing_dependency(self.path());
+                self.add_missing(ctx);
                 false
             },
             |meta| meta.is_dir,
         )
     }
 
+    fn add_missing(&self, ctx: &mut Ctx) {
+        ctx.add_missing_dependency(self.path());
+        ctx.add_attempt(self.path(), ResolveAttemptReason::NotFound);
+        if let Some(error) = self.meta_error.get() {
+            ctx.add_io_error(self.path(), error);
+        }
+    }
+
     pub fn realpath<Fs: FileSystem>(&self, fs: &Fs) -> io::Result<PathBuf> {
         self.canonicalized
             .get_or_try_init(|| {
@@ -216,7 +640,7 @@ impl CachedPathImpl {
         cache: &Cache<Fs>,
         ctx: &mut Ctx,
     ) -> Option<CachedPath> {
-        let cached_path = cache.value(&self.path.join(module_name));
+        let cached_path = cache.value(&self.path.join(module_name), ctx);
         cached_path.is_dir(&cache.fs, ctx).then_some(cached_path)
     }
 
@@ -225,10 +649,17 @@ impl CachedPathImpl {
         cache: &Cache<Fs>,
         ctx: &mut Ctx,
     ) -> Option<CachedPath> {
-        self.node_modules.get_or_init(|| self.module_directory("node_modules", cache, ctx)).clone()
+        match self.node_modules.get_or_init(|| self.module_directory("node_modules", cache, ctx)) {
+            // The cached directory is stale after invalidation, look it up again.
+            Some(node_modules) if node_modules.invalidated.load(Ordering::Relaxed) => {
+                self.module_directory("node_modules", cache, ctx)
+            }
+            node_modules => node_modules.clone(),
+        }
     }
 
-    /// Find package.json of a path by traversing parent directories.
+    /// Find package.json of a path by traversing parent directories,
+    /// up to [ResolveOptions::description_files_boundary].
     ///
     /// # Errors
     ///
@@ -248,11 +679,26 @@ impl CachedPathImpl {
                 break;
             }
         }
+        let (boundary, node_modules_boundary) = match &options.description_files_boundary {
+            Some(DescriptionFilesBoundary::Path(path)) => {
+                (cache_value.path.starts_with(path).then_some(path.as_path()), false)
+            }
+            Some(DescriptionFilesBoundary::NodeModules) => (None, true),
+            None => (None, false),
+        };
         let mut cache_value = Some(cache_value);
         while let Some(cv) = cache_value {
+            if node_modules_boundary
+                && cv.path.file_name().is_some_and(|name| name == "node_modules")
+            {
+                break;
+            }
             if let Some(package_json) = cv.package_json(fs, options, ctx)? {
                 return Ok(Some(Arc::clone(&package_json)));
             }
+            if boundary.is_some_and(|boundary| cv.path() == boundary) {
+                break;
+            }
             cache_value = cv.parent.as_deref();
         }
         Ok(None)
@@ -263,58 +709,106 @@ impl CachedPathImpl {
     /// # Errors
     ///
     /// * [ResolveError::JSON]
+    /// * [ResolveError::IOError] when a description file cannot be read for a permission error
     pub fn package_json<Fs: FileSystem>(
         &self,
         fs: &Fs,
         options: &ResolveOptions,
         ctx: &mut Ctx,
     ) -> Result<Option<Arc<PackageJson>>, ResolveError> {
+        trace_cache("package_json", &self.path, self.package_json.get().is_some());
         // Change to `std::sync::OnceLock::get_or_try_init` when it is stable.
         let result = self
             .package_json
             .get_or_try_init(|| {
-                let package_json_path = self.path.join("package.json");
-                let Ok(package_json_string) = fs.read_to_string(&package_json_path) else {
+                if let Some(package_json) = options
+                    .package_json_provider
+                    .as_ref()
+                    .and_then(|provider| provider.package_json(&self.path))
+                {
+                    return Ok(Some(package_json));
+                }
+                // The first description file found in the directory is used.
+                let mut found = None;
+                for name in description_files(options) {
+                    let path = self.path.join(name);
+                    match fs.read_to_string(&path) {
+                        Ok(string) => {
+                            found = Some((name, path, string));
+                            break;
+                        }
+                        // A description file which cannot be read is not treated as missing.
+                        Err(error) if !is_not_found(&error) => {
+                            return Err(ResolveError::from_io_error(&path, &error));
+                        }
+                        Err(_) => {}
+                    }
+                }
+                let Some((name, package_json_path, mut package_json_string)) = found else {
                     return Ok(None);
                 };
+                if options.jsonc_description_files.iter().any(|jsonc| jsonc == name) {
+                    // Comments and trailing commas are replaced with whitespace, error locations are kept.
+                    _ = json_strip_comments::strip(&mut package_json_string);
+                }
                 let real_path = if options.symlinks {
-                    self.realpath(fs)?.join("package.json")
+                    self.realpath(fs)?.join(name)
                 } else {
                     package_json_path.clone()
                 };
-                PackageJson::parse(package_json_path.clone(), real_path, &package_json_string)
-                    .map(Arc::new)
-                    .map(Some)
-                    .map_err(|error| {
-                        ResolveError::from_serde_json_error(
-                            package_json_path,
-                            &error,
-                            Some(package_json_string),
-                        )
-                    })
+                PackageJson::parse(
+                    package_json_path.clone(),
+                    real_path,
+                    &package_json_string,
+                    options,
+                )
+                .map(Arc::new)
+                .map(Some)
+                .map_err(|error| {
+                    ResolveError::from_serde_json_error(
+                        package_json_path,
+                        &error,
+                        Some(package_json_string),
+                    )
+                })
             })
             .cloned();
         // https://github.com/webpack/enhanced-resolve/blob/58464fc7cb56673c9aa849e68e6300239601e615/lib/DescriptionFileUtils.js#L68-L82
         match &result {
             Ok(Some(package_json)) => {
-                ctx.add_file_dependency(&package_json.path);
+                ctx.add_description_dependency(&package_json.path);
             }
             Ok(None) => {
                 // Avoid an allocation by making this lazy
                 if let Some(deps) = &mut ctx.missing_dependencies {
-                    deps.push(self.path.join("package.json"));
+                    deps.extend(description_files(options).map(|name| self.path.join(name)));
                 }
             }
-            Err(_) => {
-                if let Some(deps) = &mut ctx.file_dependencies {
-                    deps.push(self.path.join("package.json"));
-                }
+            Err(ResolveError::JSON(error)) => {
+                ctx.add_description_dependency(&error.path);
             }
+            Err(_) => {}
         }
         result
     }
 }
 
+/// File names of [ResolveOptions::description_files], `package.json` when none are configured.
+fn description_files(options: &ResolveOptions) -> impl Iterator<Item = &str> {
+    let default = options.description_files.is_empty().then_some("package.json");
+    options.description_files.iter().map(String::as_str).chain(default)
+}
+
+/// Whether `error` means the path is missing rather than inaccessible,
+/// including `ENOTDIR` and `EISDIR` for a file or directory where the other is looked up.
+fn is_not_found(error: &io::Error) -> bool {
+    // `io::ErrorKind::NotADirectory` and `io::ErrorKind::IsADirectory` are not stable on the MSRV.
+    const ENOTDIR: i32 = 20;
+    const EISDIR: i32 = 21;
+    error.kind() == io::ErrorKind::NotFound
+        || (cfg!(unix) && matches!(error.raw_os_error(), Some(ENOTDIR | EISDIR)))
+}
+
 /// Memoized cache key, code adapted from <https://stackoverflow.com/a/50478038>.
 trait CacheKey {
     fn tuple(&self) -> (u64, &Path);
@@ -1,13 +1,99 @@
 use std::{
     ops::{Deref, DerefMut},
     path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
 };
 
-use crate::error::ResolveError;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+    cache::CachedPath,
+    error::ResolveError,
+    graph::{GraphEdgeKind, GraphNode, ResolveStep},
+};
 
 #[derive(Debug, Default, Clone)]
 pub struct ResolveContext(ResolveContextImpl);
 
+/// A candidate path the resolver tried and rejected,
+/// collected into [crate::ResolveContext::attempts].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolveAttempt {
+    /// The candidate path.
+    pub path: PathBuf,
+
+    /// Why the candidate was rejected.
+    pub reason: ResolveAttemptReason,
+}
+
+/// Why a [ResolveAttempt] was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolveAttemptReason {
+    /// The path does not exist.
+    NotFound,
+
+    /// A file was expected but the path is not a file, e.g. a directory.
+    NotAFile,
+
+    /// The path is ignored by an alias or alias field set to `false`, see [crate::ResolveOutcome::Ignored].
+    Ignored,
+
+    /// The resolved path is outside of [crate::ResolveOptions::restrictions].
+    Restricted,
+
+    /// The file exists, but [crate::ResolveOptions::extension_alias] replaces its extension with others which do not.
+    ExtensionAliased,
+}
+
+/// Abandons resolutions which take too long, e.g. on an unresponsive network mount,
+/// see [crate::ResolveContext::cancellation].
+///
+/// Checked between file system lookups, cancelled resolutions fail with [ResolveError::Cancelled].
+/// Clones share the cancelled state.
+#[derive(Debug, Default, Clone)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A token which is cancelled once `deadline` has passed.
+    pub fn with_deadline(deadline: Instant) -> Self {
+        Self { cancelled: Arc::default(), deadline: Some(deadline) }
+    }
+
+    /// A token which is cancelled after `timeout` from now.
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self::with_deadline(Instant::now() + timeout)
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+            || self.deadline.is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+/// File system operations spent against [crate::ResolveOptions::fs_operation_budget].
+#[derive(Debug, Default, Clone)]
+pub struct FsOperations {
+    budget: usize,
+    spent: usize,
+    /// Operations per directory, for reporting where the budget was spent.
+    directories: FxHashMap<PathBuf, usize>,
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct ResolveContextImpl {
     pub fully_specified: bool,
@@ -17,10 +103,50 @@ pub struct ResolveContextImpl {
     pub fragment: Option<String>,
 
     /// Files that was found on file system
-    pub file_dependencies: Option<Vec<PathBuf>>,
+    pub file_dependencies: Option<FxHashSet<PathBuf>>,
 
     /// Files that was found on file system
-    pub missing_dependencies: Option<Vec<PathBuf>>,
+    pub missing_dependencies: Option<FxHashSet<PathBuf>>,
+
+    /// package.json and tsconfig files that were consulted, also in `file_dependencies`
+    pub description_dependencies: Option<FxHashSet<PathBuf>>,
+
+    /// Candidate paths that were rejected, in the order they were tried
+    pub attempts: Option<Vec<ResolveAttempt>>,
+
+    /// Module directories searched for bare specifiers, collected along with `attempts`
+    /// to explain a [ResolveError::NotFound]
+    pub searched_directories: Vec<PathBuf>,
+
+    /// Resolutions which succeeded in a degraded way
+    pub warnings: Option<Vec<String>>,
+
+    /// Paths that looked missing because they could not be accessed, with their errors, in lookup order.
+    /// The first one the specifier points to is reported as [ResolveError::IOError] instead of [ResolveError::NotFound]
+    pub io_errors: Vec<(PathBuf, ResolveError)>,
+
+    /// Rewrites taken by the resolution, innermost first, for [crate::ResolutionGraph]
+    pub steps: Option<Vec<ResolveStep>>,
+
+    pub cancellation: Option<CancellationToken>,
+
+    pub fs_operations: Option<FsOperations>,
+
+    /// Paths looked up by this resolution when [crate::ResolveOptions::disable_cache] is set,
+    /// so each of them is read at most once per resolution.
+    pub scratch_paths: FxHashMap<PathBuf, CachedPath>,
+
+    /// Files treated as missing, for [crate::ResolverGeneric::resolve_all_candidates].
+    pub excluded_candidates: Option<FxHashSet<PathBuf>>,
+
+    /// Replaces [crate::ResolveOptions::condition_names], for [crate::ResolverGeneric::resolve_condition_sets].
+    pub condition_names: Option<Arc<[String]>>,
+
+    /// Conditions of "exports" or "imports" were matched, so the result depends on the condition names.
+    pub conditions_evaluated: bool,
+
+    /// The importing directory is inside node_modules, for [crate::ResolveOptions::alias_skip_node_modules].
+    pub issuer_in_node_modules: bool,
 
     /// The current resolving alias for bailing recursion alias.
     pub resolving_alias: Option<String>,
@@ -57,20 +183,80 @@ impl ResolveContext {
         }
     }
 
-    pub fn init_file_dependencies(&mut self) {
-        self.file_dependencies.replace(vec![]);
-        self.missing_dependencies.replace(vec![]);
+    /// Collects dependencies into the given sets, which are handed back by [Self::take_file_dependencies].
+    pub fn init_file_dependencies(
+        &mut self,
+        file_dependencies: FxHashSet<PathBuf>,
+        missing_dependencies: FxHashSet<PathBuf>,
+    ) {
+        self.file_dependencies.replace(file_dependencies);
+        self.missing_dependencies.replace(missing_dependencies);
+    }
+
+    pub fn take_file_dependencies(&mut self) -> (FxHashSet<PathBuf>, FxHashSet<PathBuf>) {
+        (
+            self.file_dependencies.take().unwrap_or_default(),
+            self.missing_dependencies.take().unwrap_or_default(),
+        )
     }
 
     pub fn add_file_dependency(&mut self, dep: &Path) {
         if let Some(deps) = &mut self.file_dependencies {
-            deps.push(dep.to_path_buf());
+            // Avoid allocating for dependencies that are already collected.
+            if !deps.contains(dep) {
+                deps.insert(dep.to_path_buf());
+            }
+        }
+    }
+
+    /// Adds a package.json or tsconfig file to both the description and the file dependencies.
+    pub fn add_description_dependency(&mut self, dep: &Path) {
+        if let Some(deps) = &mut self.description_dependencies {
+            if !deps.contains(dep) {
+                deps.insert(dep.to_path_buf());
+            }
         }
+        self.add_file_dependency(dep);
     }
 
     pub fn add_missing_dependency(&mut self, dep: &Path) {
         if let Some(deps) = &mut self.missing_dependencies {
-            deps.push(dep.to_path_buf());
+            if !deps.contains(dep) {
+                deps.insert(dep.to_path_buf());
+            }
+        }
+    }
+
+    pub fn is_excluded_candidate(&self, path: &Path) -> bool {
+        self.excluded_candidates.as_ref().is_some_and(|excluded| excluded.contains(path))
+    }
+
+    pub fn add_attempt(&mut self, path: &Path, reason: ResolveAttemptReason) {
+        if let Some(attempts) = &mut self.attempts {
+            attempts.push(ResolveAttempt { path: path.to_path_buf(), reason });
+        }
+    }
+
+    pub fn add_searched_directory(&mut self, path: &Path) {
+        if self.attempts.is_some() && !self.searched_directories.iter().any(|p| p == path) {
+            self.searched_directories.push(path.to_path_buf());
+        }
+    }
+
+    pub fn add_step(&mut self, kind: GraphEdgeKind, to: GraphNode) {
+        if let Some(steps) = &mut self.steps {
+            steps.push(ResolveStep { kind, to });
+        }
+    }
+
+    pub fn add_io_error(&mut self, path: &Path, error: &ResolveError) {
+        self.io_errors.push((path.to_path_buf(), error.clone()));
+    }
+
+    /// Adds the warning built by `warning`, which is only called when warnings are collected.
+    pub fn add_warning<F: FnOnce() -> String>(&mut self, warning: F) {
+        if let Some(warnings) = &mut self.warnings {
+            warnings.push(warning());
         }
     }
 
@@ -78,6 +264,49 @@ impl ResolveContext {
         self.resolving_alias = Some(alias);
     }
 
+    pub fn init_fs_operation_budget(&mut self, budget: Option<usize>) {
+        // Fallback resolvers keep spending the budget of the main resolver.
+        if self.fs_operations.is_none() {
+            self.fs_operations =
+                budget.map(|budget| FsOperations { budget, ..FsOperations::default() });
+        }
+    }
+
+    pub fn add_fs_operation(&mut self, path: &Path) {
+        if let Some(fs_operations) = &mut self.fs_operations {
+            fs_operations.spent += 1;
+            let directory = path.parent().unwrap_or(path);
+            if let Some(count) = fs_operations.directories.get_mut(directory) {
+                *count += 1;
+            } else {
+                fs_operations.directories.insert(directory.to_path_buf(), 1);
+            }
+        }
+    }
+
+    /// Fails when the resolution is cancelled or has spent its file system operation budget.
+    pub fn test_for_limits(&self) -> Result<(), ResolveError> {
+        if self.cancellation.as_ref().is_some_and(CancellationToken::is_cancelled) {
+            return Err(ResolveError::Cancelled);
+        }
+        if let Some(fs_operations) = &self.fs_operations {
+            if fs_operations.spent > fs_operations.budget {
+                let mut directories = fs_operations
+                    .directories
+                    .iter()
+                    .map(|(directory, count)| (directory.clone(), *count))
+                    .collect::<Vec<_>>();
+                directories.sort_unstable_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+                directories.truncate(5);
+                return Err(ResolveError::FsOperationBudgetExceeded(
+                    fs_operations.budget,
+                    directories,
+                ));
+            }
+        }
+        Ok(())
+    }
+
     pub fn test_for_infinite_recursion(&mut self) -> Result<(), ResolveError> {
         self.depth += 1;
         // 64 should be more than enough for detecting infinite recursion.
@@ -1,4 +1,9 @@
-use std::{io, path::PathBuf, sync::Arc};
+use std::{
+    fmt::Write,
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use thiserror::Error;
 
 /// All resolution errors
@@ -18,10 +23,15 @@ pub enum ResolveError {
     /// }
     /// ```
     /// See <https://github.com/defunctzombie/package-browser-field-spec#ignore-a-module>
+    ///
+    /// Use [crate::ResolverGeneric::resolve_outcome] to receive this as [crate::ResolveOutcome::Ignored] instead.
     #[error("Path is ignored {0}")]
     Ignored(PathBuf),
 
     /// Module not found
+    ///
+    /// The node_modules, [crate::ResolveOptions::modules] and [crate::ResolveOptions::roots] directories
+    /// that were searched are listed in [crate::ResolveContext::searched_directories].
     #[error("Cannot find module '{0}'")]
     NotFound(/* specifier */ String),
 
@@ -29,6 +39,15 @@ pub enum ResolveError {
     #[error("Cannot find module '{0}' for matched aliased key '{1}'")]
     MatchedAliasNotFound(/* specifier */ String, /* alias key */ String),
 
+    /// A request ending in `/` points to a file, see [crate::TrailingSlash::Strict]
+    #[error(r#"Path "{0}" is not a directory"#)]
+    NotADirectory(PathBuf),
+
+    /// A bare specifier is not declared in the dependency fields of the importer's package.json,
+    /// see [crate::ResolveOptions::strict_dependencies]
+    #[error("Package '{0}' is not declared as a dependency in {1}")]
+    UndeclaredDependency(/* package name */ String, /* path to package.json */ PathBuf),
+
     /// Tsconfig not found
     #[error("Tsconfig not found {0}")]
     TsconfigNotFound(PathBuf),
@@ -65,9 +84,17 @@ pub enum ResolveError {
     #[error("{0:?}")]
     JSON(JSONError),
 
-    /// Restricted by `ResolveOptions::restrictions`
-    #[error(r#"Path "{0}" restricted by {0}"#)]
-    Restriction(PathBuf, PathBuf),
+    /// The resolved path is rejected by an entry of `ResolveOptions::restrictions`
+    #[error(r#"Path "{0}" is restricted by "{1}""#)]
+    Restriction(/* resolved path */ PathBuf, /* restricted path */ PathBuf),
+
+    /// The real path of the resolved path is outside of `ResolveOptions::allowed_roots`
+    #[error(r#"Path "{0}" is outside of the allowed roots"#)]
+    OutsideAllowedRoots(PathBuf),
+
+    /// The real path of a symlink leaves `SymlinkEscapeOptions::roots` with `SymlinkEscapePolicy::Error`
+    #[error(r#"Symlink "{0}" escapes to "{1}""#)]
+    SymlinkEscape(/* symlinked path */ PathBuf, /* real path */ PathBuf),
 
     #[error(r#"Invalid module "{0}" specifier is not a valid subpath for the "exports" resolution of {1}"#)]
     InvalidModuleSpecifier(String, PathBuf),
@@ -78,6 +105,12 @@ pub enum ResolveError {
     #[error(r#"Package subpath '{0}' is not defined by "exports" in {1}"#)]
     PackagePathNotExported(String, PathBuf),
 
+    /// The package author blocked the subpath by mapping it to `null` in "exports".
+    #[error(
+        r#"Package subpath '{0}' is blocked by the "exports" key '{1}' mapped to null in {2}"#
+    )]
+    PackagePathBlocked(/* subpath */ String, /* exports key */ String, PathBuf),
+
     #[error(r#"Invalid package config "{0}", "exports" cannot contain some keys starting with '.' and some not. The exports object must either be an object of package subpath keys or an object of main entry condition name keys only."#)]
     InvalidPackageConfig(PathBuf),
 
@@ -90,12 +123,27 @@ pub enum ResolveError {
     #[error(r#"Package import specifier "{0}" is not defined in package {1}"#)]
     PackageImportNotDefined(String, PathBuf),
 
+    /// The "bin" field of the package does not define the executable
+    #[error("Cannot find bin '{0}' in {1}")]
+    BinNotFound(/* bin name */ String, /* path to package.json */ PathBuf),
+
     #[error("{0} is unimplemented")]
     Unimplemented(&'static str),
 
     /// Occurs when alias paths reference each other.
     #[error("Recursion in resolving")]
     Recursion,
+
+    /// Cancelled by `ResolveContext::cancellation`
+    #[error("Resolving is cancelled")]
+    Cancelled,
+
+    /// More paths were probed than `ResolveOptions::fs_operation_budget` allows
+    #[error("Exceeded the budget of {0} file system operations, most were spent in {}", display_directories(.1))]
+    FsOperationBudgetExceeded(
+        /* budget */ usize,
+        /* directories with the most operations */ Vec<(PathBuf, usize)>,
+    ),
 }
 
 impl ResolveError {
@@ -103,6 +151,47 @@ impl ResolveError {
         matches!(self, Self::Ignored(_))
     }
 
+    /// A stable code for each kind of error, for branching without matching on messages.
+    ///
+    /// Errors defined by the Node.js ESM resolution algorithm use the Node.js codes such as `ERR_PACKAGE_PATH_NOT_EXPORTED`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Ignored(_) => "IGNORED",
+            Self::NotFound(_) => "MODULE_NOT_FOUND",
+            Self::MatchedAliasNotFound(..) => "MATCHED_ALIAS_NOT_FOUND",
+            Self::NotADirectory(_) => "NOT_A_DIRECTORY",
+            Self::UndeclaredDependency(..) => "UNDECLARED_DEPENDENCY",
+            Self::TsconfigNotFound(_) => "TSCONFIG_NOT_FOUND",
+            Self::TsconfigSelfReference(_) => "TSCONFIG_SELF_REFERENCE",
+            Self::IOError(_) => "IO_ERROR",
+            Self::Builtin(_) => "BUILTIN",
+            Self::ExtensionAlias(..) => "EXTENSION_ALIAS_NOT_FOUND",
+            Self::Specifier(_) => "INVALID_SPECIFIER",
+            Self::JSON(_) => "JSON_PARSE_ERROR",
+            Self::Restriction(..) => "RESTRICTED",
+            Self::OutsideAllowedRoots(_) => "OUTSIDE_ALLOWED_ROOTS",
+            Self::SymlinkEscape(..) => "SYMLINK_ESCAPE",
+            Self::InvalidModuleSpecifier(..) => "ERR_INVALID_MODULE_SPECIFIER",
+            Self::InvalidPackageTarget(..) => "ERR_INVALID_PACKAGE_TARGET",
+            Self::PackagePathNotExported(..) => "ERR_PACKAGE_PATH_NOT_EXPORTED",
+            Self::PackagePathBlocked(..) => "ERR_PACKAGE_PATH_BLOCKED",
+            Self::InvalidPackageConfig(_)
+            | Self::InvalidPackageConfigDefault(_)
+            | Self::InvalidPackageConfigDirectory(_) => "ERR_INVALID_PACKAGE_CONFIG",
+            Self::PackageImportNotDefined(..) => "ERR_PACKAGE_IMPORT_NOT_DEFINED",
+            Self::BinNotFound(..) => "BIN_NOT_FOUND",
+            Self::Unimplemented(_) => "UNIMPLEMENTED",
+            Self::Recursion => "RECURSION",
+            Self::Cancelled => "CANCELLED",
+            Self::FsOperationBudgetExceeded(..) => "FS_OPERATION_BUDGET_EXCEEDED",
+        }
+    }
+
+    /// An IO error for `path` other than not found, with the path in the message.
+    pub(crate) fn from_io_error(path: &Path, error: &io::Error) -> Self {
+        io::Error::new(error.kind(), format!("{}: {error}", path.display())).into()
+    }
+
     pub(crate) fn from_serde_json_error(
         path: PathBuf,
         error: &serde_json::Error,
@@ -118,6 +207,17 @@ impl ResolveError {
     }
 }
 
+fn display_directories(directories: &[(PathBuf, usize)]) -> String {
+    let mut s = String::new();
+    for (i, (directory, count)) in directories.iter().enumerate() {
+        if i > 0 {
+            s.push_str(", ");
+        }
+        let _ = write!(s, "'{}' ({count})", directory.display());
+    }
+    s
+}
+
 /// Error for [ResolveError::Specifier]
 #[derive(Debug, Clone, Eq, PartialEq, Error)]
 pub enum SpecifierError {
@@ -125,6 +225,28 @@ pub enum SpecifierError {
     Empty(String),
 }
 
+/// Problematic option values found by [crate::ResolveOptions::validate]
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+#[non_exhaustive]
+pub enum OptionsWarning {
+    /// [crate::ResolveOptions::condition_names] contains both "import" and "require",
+    /// so a "require" condition listed after "import" in conditional exports never matches.
+    #[error(r#"condition_names contains both "import" and "require", "require" never matches when listed after "import""#)]
+    ConflictingConditions,
+
+    /// A field path of the named option is an empty array.
+    #[error("{0} contains an empty field path")]
+    EmptyFieldPath(/* option */ &'static str),
+
+    /// An extension of the named option does not start with a leading dot.
+    #[error("Extension '{0}' in {1} does not start with a leading dot")]
+    ExtensionWithoutLeadingDot(/* extension */ String, /* option */ &'static str),
+
+    /// An absolute path in [crate::ResolveOptions::alias] or [crate::ResolveOptions::fallback] does not exist.
+    #[error("Alias '{0}' points to '{1}' which does not exist")]
+    AliasTargetNotFound(/* alias key */ String, PathBuf),
+}
+
 /// JSON error from [serde_json::Error]
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct JSONError {
@@ -192,4 +314,20 @@ fn test_coverage() {
     let error = ResolveError::Specifier(SpecifierError::Empty("x".into()));
     assert_eq!(format!("{error:?}"), r#"Specifier(Empty("x"))"#);
     assert_eq!(error.clone(), error);
+    assert_eq!(error.code(), "INVALID_SPECIFIER");
+    assert_eq!(ResolveError::NotFound("x".into()).code(), "MODULE_NOT_FOUND");
+    assert_eq!(
+        ResolveError::PackagePathNotExported("x".into(), PathBuf::new()).code(),
+        "ERR_PACKAGE_PATH_NOT_EXPORTED"
+    );
+    let error = ResolveError::PackagePathBlocked(
+        "./internal/a.js".into(),
+        "./internal/*".into(),
+        PathBuf::from("package.json"),
+    );
+    assert_eq!(error.code(), "ERR_PACKAGE_PATH_BLOCKED");
+    assert_eq!(
+        error.to_string(),
+        r#"Package subpath './internal/a.js' is blocked by the "exports" key './internal/*' mapped to null in package.json"#
+    );
 }
@@ -1,7 +1,13 @@
 use cfg_if::cfg_if;
+use dashmap::DashMap;
+use rustc_hash::{FxHashMap, FxHashSet, FxHasher};
 use std::{
-    fs, io,
+    fmt, fs,
+    hash::BuildHasherDefault,
+    io,
     path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
 };
 
 #[cfg(feature = "yarn_pnp")]
@@ -61,6 +67,32 @@ pub trait FileSystem {
     /// you want to store multiple `dyn FileSystem` in a `Vec` or use a `ResolverGeneric<Fs>` in
     /// napi env.
     fn canonicalize(&self, path: &Path) -> io::Result<PathBuf>;
+
+    /// Returns the target of a symbolic link, see [std::fs::read_link]
+    ///
+    /// The target is returned as written in the link, relative targets are relative to the parent of `path`.
+    /// The default implementation returns [io::ErrorKind::Unsupported].
+    ///
+    /// # Errors
+    ///
+    /// See [std::fs::read_link]
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+        _ = path;
+        Err(io::Error::from(io::ErrorKind::Unsupported))
+    }
+
+    /// Returns the paths of the entries of a directory, see [std::fs::read_dir]
+    ///
+    /// Only used for discovering workspace packages ([crate::ResolveOptions::workspace_root]),
+    /// the default implementation returns [io::ErrorKind::Unsupported].
+    ///
+    /// # Errors
+    ///
+    /// See [std::fs::read_dir]
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        _ = path;
+        Err(io::Error::from(io::ErrorKind::Unsupported))
+    }
 }
 
 /// Metadata information about a file
@@ -69,11 +101,23 @@ pub struct FileMetadata {
     pub is_file: bool,
     pub is_dir: bool,
     pub is_symlink: bool,
+    /// Last modification time, `None` when the file system does not provide it.
+    pub mtime: Option<SystemTime>,
+    /// Size in bytes, `None` when the file system does not provide it.
+    pub size: Option<u64>,
 }
 
 impl FileMetadata {
     pub fn new(is_file: bool, is_dir: bool, is_symlink: bool) -> Self {
-        Self { is_file, is_dir, is_symlink }
+        Self { is_file, is_dir, is_symlink, mtime: None, size: None }
+    }
+
+    /// Sets the last modification time and the size.
+    #[must_use]
+    pub fn with_mtime_and_size(mut self, mtime: Option<SystemTime>, size: Option<u64>) -> Self {
+        self.mtime = mtime;
+        self.size = size;
+        self
     }
 }
 
@@ -87,12 +131,24 @@ impl From<pnp::fs::FileType> for FileMetadata {
 impl From<fs::Metadata> for FileMetadata {
     fn from(metadata: fs::Metadata) -> Self {
         Self::new(metadata.is_file(), metadata.is_dir(), metadata.is_symlink())
+            .with_mtime_and_size(metadata.modified().ok(), Some(metadata.len()))
     }
 }
 
+/// Options for [FileSystemOs].
+#[derive(Debug, Clone)]
 pub struct FileSystemOptions {
+    /// Read files inside Yarn PnP zip archives and `__virtual__` directories.
+    ///
+    /// Default `true`
     #[cfg(feature = "yarn_pnp")]
     pub enable_pnp: bool,
+
+    /// Number of opened zip archives kept in memory when `enable_pnp` is on.
+    ///
+    /// Default `50`
+    #[cfg(feature = "yarn_pnp")]
+    pub zip_cache_size: u64,
 }
 
 impl Default for FileSystemOptions {
@@ -100,6 +156,8 @@ impl Default for FileSystemOptions {
         Self {
             #[cfg(feature = "yarn_pnp")]
             enable_pnp: true,
+            #[cfg(feature = "yarn_pnp")]
+            zip_cache_size: 50,
         }
     }
 }
@@ -113,10 +171,16 @@ pub struct FileSystemOs {
 
 impl Default for FileSystemOs {
     fn default() -> Self {
+        Self::new(FileSystemOptions::default())
+    }
+}
+
+impl FileSystemOs {
+    pub fn new(options: FileSystemOptions) -> Self {
         Self {
-            options: FileSystemOptions::default(),
             #[cfg(feature = "yarn_pnp")]
-            pnp_lru: LruZipCache::new(50, pnp::fs::open_zip_via_read_p),
+            pnp_lru: LruZipCache::new(options.zip_cache_size, pnp::fs::open_zip_via_read_p),
+            options,
         }
     }
 }
@@ -159,10 +223,16 @@ impl FileSystem for FileSystemOs {
             if #[cfg(feature = "yarn_pnp")] {
                 if self.options.enable_pnp {
                     return match VPath::from(path)? {
-                        VPath::Zip(info) => self
-                            .pnp_lru
-                            .file_type(info.physical_base_path(), info.zip_path)
-                            .map(FileMetadata::from),
+                        VPath::Zip(info) => {
+                            let zip_path = info.physical_base_path();
+                            self.pnp_lru
+                                .file_type(&zip_path, &info.zip_path)
+                                // Directories of the archive are listed with a trailing slash.
+                                .or_else(|_| {
+                                    self.pnp_lru.file_type(&zip_path, format!("{}/", info.zip_path))
+                                })
+                                .map(FileMetadata::from)
+                        }
                         VPath::Virtual(info) => {
                             fs::metadata(info.physical_base_path()).map(FileMetadata::from)
                         }
@@ -201,7 +271,7 @@ impl FileSystem for FileSystemOs {
                 use std::path::Component;
                 let mut path_buf = path.to_path_buf();
                 loop {
-                    let link = fs::read_link(&path_buf)?;
+                    let link = self.read_link(&path_buf)?;
                     path_buf.pop();
                     for component in link.components() {
                         match component {
@@ -209,15 +279,7 @@ impl FileSystem for FileSystemOs {
                                 path_buf.pop();
                             }
                             Component::Normal(seg) => {
-                                #[cfg(target_family = "wasm")]
-                                // Need to trim the extra \0 introduces by https://github.com/nodejs/uvwasi/issues/262
-                                {
-                                    path_buf.push(seg.to_string_lossy().trim_end_matches('\0'));
-                                }
-                                #[cfg(not(target_family = "wasm"))]
-                                {
-                                    path_buf.push(seg);
-                                }
+                                path_buf.push(seg);
                             }
                             Component::RootDir => {
                                 path_buf = PathBuf::from("/");
@@ -233,14 +295,389 @@ impl FileSystem for FileSystemOs {
             }
         }
     }
+
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+        cfg_if! {
+            if #[cfg(feature = "yarn_pnp")] {
+                if self.options.enable_pnp {
+                    return match VPath::from(path)? {
+                        // Zip archives do not contain symlinks.
+                        VPath::Zip(_) => {
+                            Err(io::Error::new(io::ErrorKind::InvalidInput, "not a symlink"))
+                        }
+                        VPath::Virtual(info) => fs::read_link(info.physical_base_path()),
+                        VPath::Native(path) => fs::read_link(path),
+                    }
+                }
+            }
+        }
+
+        cfg_if! {
+            if #[cfg(target_family = "wasm")] {
+                // Need to trim the extra \0 introduces by https://github.com/nodejs/uvwasi/issues/262
+                let link = fs::read_link(path)?;
+                Ok(link
+                    .components()
+                    .map(|component| match component {
+                        std::path::Component::Normal(seg) => {
+                            PathBuf::from(seg.to_string_lossy().trim_end_matches('\0'))
+                        }
+                        component => PathBuf::from(component.as_os_str()),
+                    })
+                    .collect())
+            } else {
+                fs::read_link(path)
+            }
+        }
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        fs::read_dir(path)?.map(|entry| entry.map(|entry| entry.path())).collect()
+    }
+}
+
+/// In-memory files layered over another [FileSystem], the in-memory files take precedence.
+///
+/// Directories containing the in-memory files exist as well.
+pub struct OverlayFileSystem<Fs> {
+    fs: Fs,
+    files: Arc<FxHashMap<PathBuf, Vec<u8>>>,
+    directories: Arc<FxHashSet<PathBuf>>,
+}
+
+impl<Fs> OverlayFileSystem<Fs> {
+    /// Layers `files`, absolute paths mapped to their contents, over `fs`.
+    pub fn new<P, C, I>(fs: Fs, files: I) -> Self
+    where
+        P: Into<PathBuf>,
+        C: Into<Vec<u8>>,
+        I: IntoIterator<Item = (P, C)>,
+    {
+        let files = files
+            .into_iter()
+            .map(|(path, content)| (path.into(), content.into()))
+            .collect::<FxHashMap<_, _>>();
+        let directories =
+            files.keys().flat_map(|path| path.ancestors().skip(1)).map(Path::to_path_buf);
+        let directories = directories.collect();
+        Self { fs, files: Arc::new(files), directories: Arc::new(directories) }
+    }
+
+    /// Layers the same in-memory files over another file system, the files are shared.
+    pub fn with_inner<F>(&self, fs: F) -> OverlayFileSystem<F> {
+        OverlayFileSystem {
+            fs,
+            files: Arc::clone(&self.files),
+            directories: Arc::clone(&self.directories),
+        }
+    }
+
+    fn overlay_metadata(&self, path: &Path) -> Option<FileMetadata> {
+        if let Some(content) = self.files.get(path) {
+            let size = u64::try_from(content.len()).ok();
+            return Some(FileMetadata::new(true, false, false).with_mtime_and_size(None, size));
+        }
+        self.directories.contains(path).then(|| FileMetadata::new(false, true, false))
+    }
+}
+
+impl<Fs: FileSystem> FileSystem for OverlayFileSystem<Fs> {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        match self.files.get(path) {
+            Some(content) => Ok(content.clone()),
+            None if self.directories.contains(path) => {
+                Err(io::Error::new(io::ErrorKind::InvalidInput, "is a directory"))
+            }
+            None => self.fs.read(path),
+        }
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        if self.overlay_metadata(path).is_some() {
+            return buffer_to_string(self.read(path)?);
+        }
+        self.fs.read_to_string(path)
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.overlay_metadata(path).map_or_else(|| self.fs.metadata(path), Ok)
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.overlay_metadata(path).map_or_else(|| self.fs.symlink_metadata(path), Ok)
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        if self.overlay_metadata(path).is_some() {
+            return Ok(path.to_path_buf());
+        }
+        self.fs.canonicalize(path)
+    }
+
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+        if self.overlay_metadata(path).is_some() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "not a symlink"));
+        }
+        self.fs.read_link(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        if !self.directories.contains(path) {
+            return self.fs.read_dir(path);
+        }
+        let mut entries = self.fs.read_dir(path).unwrap_or_default();
+        for entry in self.files.keys().chain(self.directories.iter()) {
+            if entry.parent() == Some(path) && !entries.contains(entry) {
+                entries.push(entry.clone());
+            }
+        }
+        Ok(entries)
+    }
+}
+
+/// A file system operation recorded by [TraceFileSystem].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FsOperation {
+    Read,
+    ReadToString,
+    Metadata,
+    SymlinkMetadata,
+    Canonicalize,
+    ReadLink,
+    ReadDir,
+}
+
+/// Accumulated calls of [TraceFileSystem].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FsTraceStats {
+    /// Number of calls.
+    pub calls: u64,
+
+    /// Number of calls which returned an error.
+    pub errors: u64,
+
+    /// Total time spent in the calls.
+    pub duration: Duration,
+}
+
+impl FsTraceStats {
+    fn add(&mut self, other: &Self) {
+        self.calls += other.calls;
+        self.errors += other.errors;
+        self.duration += other.duration;
+    }
+}
+
+/// Summary of the calls of a [TraceFileSystem], see [TraceFileSystem::summary].
+///
+/// Entries are sorted by duration, slowest first.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FsTraceSummary {
+    /// All calls.
+    pub total: FsTraceStats,
+
+    /// Calls per operation.
+    pub by_operation: Vec<(FsOperation, FsTraceStats)>,
+
+    /// Calls per path, all operations combined.
+    pub by_path: Vec<(PathBuf, FsTraceStats)>,
+}
+
+/// Displays the totals, the operations and the slowest 20 paths.
+impl fmt::Display for FsTraceSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let FsTraceStats { calls, errors, duration } = self.total;
+        writeln!(f, "{calls} calls, {errors} errors, {duration:?}")?;
+        for (operation, FsTraceStats { calls, errors, duration }) in &self.by_operation {
+            writeln!(f, "  {operation:?}: {calls} calls, {errors} errors, {duration:?}")?;
+        }
+        for (path, FsTraceStats { calls, errors, duration }) in self.by_path.iter().take(20) {
+            writeln!(f, "  {}: {calls} calls, {errors} errors, {duration:?}", path.display())?;
+        }
+        Ok(())
+    }
+}
+
+/// Counts and times every call to another [FileSystem], for finding out where resolution spends its IO.
+///
+/// Each call is also logged as a `tracing` debug event when enabled by [TraceFileSystem::with_log].
+pub struct TraceFileSystem<Fs> {
+    fs: Fs,
+    log: bool,
+    stats: DashMap<(FsOperation, PathBuf), FsTraceStats, BuildHasherDefault<FxHasher>>,
+}
+
+impl<Fs> TraceFileSystem<Fs> {
+    /// Traces the calls to `fs`, without logging.
+    pub fn new(fs: Fs) -> Self {
+        Self { fs, log: false, stats: DashMap::default() }
+    }
+
+    /// Log each call with its operation, path, duration and result.
+    #[must_use]
+    pub fn with_log(mut self, log: bool) -> Self {
+        self.log = log;
+        self
+    }
+
+    /// The traced file system.
+    pub fn inner(&self) -> &Fs {
+        &self.fs
+    }
+
+    /// Aggregates the calls recorded so far.
+    pub fn summary(&self) -> FsTraceSummary {
+        let mut total = FsTraceStats::default();
+        let mut by_operation = FxHashMap::<FsOperation, FsTraceStats>::default();
+        let mut by_path = FxHashMap::<PathBuf, FsTraceStats>::default();
+        for entry in &self.stats {
+            let ((operation, path), stats) = entry.pair();
+            total.add(stats);
+            by_operation.entry(*operation).or_default().add(stats);
+            by_path.entry(path.clone()).or_default().add(stats);
+        }
+        let mut by_operation = by_operation.into_iter().collect::<Vec<_>>();
+        by_operation.sort_unstable_by(|(a, a_stats), (b, b_stats)| {
+            b_stats.duration.cmp(&a_stats.duration).then(a.cmp(b))
+        });
+        let mut by_path = by_path.into_iter().collect::<Vec<_>>();
+        by_path.sort_unstable_by(|(a, a_stats), (b, b_stats)| {
+            b_stats.duration.cmp(&a_stats.duration).then_with(|| a.cmp(b))
+        });
+        FsTraceSummary { total, by_operation, by_path }
+    }
+
+    /// Forgets the calls recorded so far.
+    pub fn reset(&self) {
+        self.stats.clear();
+    }
+
+    fn trace<T>(
+        &self,
+        operation: FsOperation,
+        path: &Path,
+        f: impl FnOnce() -> io::Result<T>,
+    ) -> io::Result<T> {
+        let start = Instant::now();
+        let result = f();
+        let duration = start.elapsed();
+        if self.log {
+            tracing::debug!(operation = ?operation, path = ?path, duration = ?duration, ok = result.is_ok(), "fs");
+        }
+        let mut stats = self.stats.entry((operation, path.to_path_buf())).or_default();
+        stats.add(&FsTraceStats { calls: 1, errors: u64::from(result.is_err()), duration });
+        result
+    }
+}
+
+impl<Fs: FileSystem> FileSystem for TraceFileSystem<Fs> {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.trace(FsOperation::Read, path, || self.fs.read(path))
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        self.trace(FsOperation::ReadToString, path, || self.fs.read_to_string(path))
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.trace(FsOperation::Metadata, path, || self.fs.metadata(path))
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.trace(FsOperation::SymlinkMetadata, path, || self.fs.symlink_metadata(path))
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        self.trace(FsOperation::Canonicalize, path, || self.fs.canonicalize(path))
+    }
+
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+        self.trace(FsOperation::ReadLink, path, || self.fs.read_link(path))
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        self.trace(FsOperation::ReadDir, path, || self.fs.read_dir(path))
+    }
 }
 
 #[test]
 fn metadata() {
-    let meta = FileMetadata { is_file: true, is_dir: true, is_symlink: true };
+    let meta = FileMetadata::new(true, true, true);
     assert_eq!(
         format!("{meta:?}"),
-        "FileMetadata { is_file: true, is_dir: true, is_symlink: true }"
+        "FileMetadata { is_file: true, is_dir: true, is_symlink: true, mtime: None, size: None }"
     );
     let _ = meta;
 }
+
+#[test]
+fn metadata_mtime_and_size() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+    let meta = FileSystemOs::default().metadata(&path).unwrap();
+    let std_meta = fs::metadata(&path).unwrap();
+    assert_eq!(meta.size, Some(std_meta.len()));
+    assert_eq!(meta.mtime, std_meta.modified().ok());
+}
+
+#[test]
+fn overlay() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let fs = OverlayFileSystem::new(
+        FileSystemOs::default(),
+        [(root.join("overlay/src/index.js"), "export {}"), (root.join("Cargo.toml"), "")],
+    );
+    let meta = fs.metadata(&root.join("overlay/src/index.js")).unwrap();
+    assert!(meta.is_file);
+    assert_eq!(meta.size, Some(9));
+    assert!(fs.metadata(&root.join("overlay")).unwrap().is_dir);
+    assert!(fs.metadata(&root.join("src/lib.rs")).unwrap().is_file);
+    assert!(fs.metadata(&root.join("overlay/missing.js")).is_err());
+    assert_eq!(fs.read_to_string(&root.join("Cargo.toml")).unwrap(), "");
+    assert!(fs.read_to_string(&root.join("overlay")).is_err());
+
+    let entries = fs.read_dir(root).unwrap();
+    assert!(entries.contains(&root.join("overlay")));
+    assert!(entries.contains(&root.join("src")));
+    assert_eq!(entries.iter().filter(|entry| entry.ends_with("Cargo.toml")).count(), 1);
+    assert_eq!(fs.read_dir(&root.join("overlay")).unwrap(), vec![root.join("overlay/src")]);
+
+    let resolver = crate::ResolverGeneric::new_with_file_system(
+        fs.with_inner(FileSystemOs::default()),
+        crate::ResolveOptions::default(),
+    );
+    let resolution = resolver.resolve(root, "./overlay/src").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(root.join("overlay/src/index.js")));
+}
+
+#[test]
+fn trace() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let fs = TraceFileSystem::new(FileSystemOs::default()).with_log(true);
+    assert!(fs.metadata(&root.join("Cargo.toml")).is_ok());
+    assert!(fs.metadata(&root.join("Cargo.toml")).is_ok());
+    assert!(fs.metadata(&root.join("missing")).is_err());
+    assert!(fs.read_to_string(&root.join("Cargo.toml")).is_ok());
+
+    let summary = fs.summary();
+    assert_eq!((summary.total.calls, summary.total.errors), (4, 1));
+    let mut operations = summary
+        .by_operation
+        .iter()
+        .map(|(operation, stats)| (*operation, stats.calls))
+        .collect::<Vec<_>>();
+    operations.sort_unstable();
+    assert_eq!(operations, vec![(FsOperation::ReadToString, 1), (FsOperation::Metadata, 3)]);
+    let cargo_toml = summary.by_path.iter().find(|(path, _)| path == &root.join("Cargo.toml"));
+    assert_eq!(cargo_toml.map(|(_, stats)| stats.calls), Some(3));
+    assert!(summary.to_string().starts_with("4 calls, 1 errors"));
+
+    let resolver = crate::ResolverGeneric::new_with_file_system(
+        TraceFileSystem::new(FileSystemOs::default()),
+        crate::ResolveOptions::default(),
+    );
+    assert!(resolver.resolve(root, "./src/lib.rs").is_ok());
+    assert!(resolver.file_system().summary().total.calls > 0);
+
+    fs.reset();
+    assert_eq!(fs.summary(), FsTraceSummary::default());
+}
@@ -0,0 +1,250 @@
+//! In-memory file system built from a declarative JSON tree, for tests and bug reproductions.
+//!
+//! Only JSON is supported, YAML trees need to be converted to JSON first.
+use std::{
+    io,
+    path::{Component, Path, PathBuf},
+};
+
+use rustc_hash::FxHashMap;
+use serde_json::{Map, Value};
+
+use crate::{path::PathUtil, FileMetadata, FileSystem};
+
+/// The key of a symlink object in a [FixtureFileSystem] tree.
+const SYMLINK_KEY: &str = "$symlink";
+
+/// Symlinks followed before giving up, the same limit as Linux.
+const MAX_SYMLINKS: usize = 40;
+
+#[derive(Debug, Clone)]
+enum Entry {
+    File(Vec<u8>),
+    Directory,
+    /// The link target as written, relative to the directory of the link or absolute.
+    Symlink(PathBuf),
+}
+
+/// In-memory files, directories and symlinks described by a JSON tree:
+///
+/// ```json
+/// {
+///   "package.json": { "name": "app", "main": "lib/index.js" },
+///   "lib": { "index.js": "module.exports = 1" },
+///   "node_modules": { "app": { "$symlink": ".." } },
+///   "empty": {}
+/// }
+/// ```
+///
+/// * A string is a file with that content.
+/// * An object or array named `*.json` is a JSON file.
+/// * An object with the single key `"$symlink"` is a symlink to the target path.
+/// * Any other object is a directory, its keys may contain `/` for nested entries.
+///
+/// A directory may be described more than once, e.g. by `"lib/a.js"` and `"lib": {}`,
+/// but a file or symlink path must not be used by another entry.
+#[derive(Debug, Clone, Default)]
+pub struct FixtureFileSystem {
+    entries: FxHashMap<PathBuf, Entry>,
+}
+
+impl FixtureFileSystem {
+    /// Builds the file system from the JSON `tree` placed at the absolute `root` directory.
+    ///
+    /// # Errors
+    ///
+    /// * The JSON is invalid or is not an object.
+    /// * An entry is neither a string, an object nor a symlink.
+    /// * A path is described both as a file or symlink and as another entry.
+    pub fn from_json<P: AsRef<Path>>(root: P, tree: &str) -> io::Result<Self> {
+        let tree = serde_json::from_str::<Value>(tree)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        Self::from_value(root, &tree)
+    }
+
+    /// Builds the file system from a parsed JSON `tree`, see [FixtureFileSystem::from_json].
+    ///
+    /// # Errors
+    ///
+    /// * The tree is not an object.
+    /// * An entry is neither a string, an object nor a symlink.
+    /// * A path is described both as a file or symlink and as another entry.
+    pub fn from_value<P: AsRef<Path>>(root: P, tree: &Value) -> io::Result<Self> {
+        let root = root.as_ref().normalize();
+        let Value::Object(tree) = tree else {
+            return Err(invalid_entry(&root));
+        };
+        let mut fs = Self::default();
+        fs.add_directory(&root)?;
+        fs.add_tree(&root, tree)?;
+        Ok(fs)
+    }
+
+    fn add_tree(&mut self, dir: &Path, tree: &Map<String, Value>) -> io::Result<()> {
+        for (name, value) in tree {
+            let path = dir.normalize_with(name);
+            self.add_directory(path.parent().unwrap_or(dir))?;
+            match value {
+                Value::String(content) => {
+                    self.add_entry(path, Entry::File(content.clone().into_bytes()))?;
+                }
+                Value::Object(map) if map.len() == 1 && map.contains_key(SYMLINK_KEY) => {
+                    let Value::String(target) = &map[SYMLINK_KEY] else {
+                        return Err(invalid_entry(&path));
+                    };
+                    self.add_entry(path, Entry::Symlink(PathBuf::from(target)))?;
+                }
+                Value::Object(_) | Value::Array(_) if is_json(name) => {
+                    let content = serde_json::to_vec_pretty(value)?;
+                    self.add_entry(path, Entry::File(content))?;
+                }
+                Value::Object(map) => {
+                    self.add_directory(&path)?;
+                    self.add_tree(&path, map)?;
+                }
+                _ => return Err(invalid_entry(&path)),
+            }
+        }
+        Ok(())
+    }
+
+    fn add_entry(&mut self, path: PathBuf, entry: Entry) -> io::Result<()> {
+        if self.entries.contains_key(&path) {
+            return Err(conflicting_entry(&path));
+        }
+        self.entries.insert(path, entry);
+        Ok(())
+    }
+
+    fn add_directory(&mut self, dir: &Path) -> io::Result<()> {
+        for dir in dir.ancestors() {
+            match self.entries.get(dir) {
+                Some(Entry::Directory) => break,
+                Some(_) => return Err(conflicting_entry(dir)),
+                None => {
+                    self.entries.insert(dir.to_path_buf(), Entry::Directory);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Resolves the symlinks of `path`, the final component is kept when `follow` is false.
+    fn real_path(&self, path: &Path, follow: bool) -> io::Result<PathBuf> {
+        let mut resolved = PathBuf::new();
+        let mut symlinks = 0;
+        let mut components = path.components().collect::<Vec<_>>();
+        components.reverse();
+        while let Some(component) = components.pop() {
+            match component {
+                Component::ParentDir => {
+                    resolved.pop();
+                    continue;
+                }
+                Component::CurDir => continue,
+                _ => resolved.push(component),
+            }
+            let is_last = components.is_empty();
+            match self.entries.get(&resolved) {
+                Some(Entry::Symlink(target)) if follow || !is_last => {
+                    symlinks += 1;
+                    if symlinks > MAX_SYMLINKS {
+                        return Err(io::Error::new(io::ErrorKind::Other, "too many symlinks"));
+                    }
+                    resolved.pop();
+                    // Continue with the components of the target, an absolute target replaces `resolved`.
+                    components.extend(target.components().rev());
+                }
+                Some(Entry::File(_)) if !is_last => {
+                    return Err(io::Error::new(io::ErrorKind::NotFound, "not a directory"));
+                }
+                Some(_) => {}
+                None => return Err(io::Error::new(io::ErrorKind::NotFound, "no such file")),
+            }
+        }
+        Ok(resolved)
+    }
+
+    fn entry(&self, path: &Path, follow: bool) -> io::Result<(PathBuf, &Entry)> {
+        let path = self.real_path(path, follow)?;
+        let entry = self
+            .entries
+            .get(&path)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no such file"))?;
+        Ok((path, entry))
+    }
+}
+
+fn is_json(name: &str) -> bool {
+    Path::new(name).extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+}
+
+fn conflicting_entry(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: described by more than one entry", path.display()),
+    )
+}
+
+fn invalid_entry(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: expected a string, an object or a symlink", path.display()),
+    )
+}
+
+impl FileSystem for FixtureFileSystem {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        match self.entry(path, true)? {
+            (_, Entry::File(content)) => Ok(content.clone()),
+            _ => Err(io::Error::new(io::ErrorKind::InvalidInput, "is a directory")),
+        }
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        String::from_utf8(self.read(path)?)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        Ok(metadata(self.entry(path, true)?.1))
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        Ok(metadata(self.entry(path, false)?.1))
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        self.real_path(path, true)
+    }
+
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+        match self.entry(path, false)? {
+            (_, Entry::Symlink(target)) => Ok(target.clone()),
+            _ => Err(io::Error::new(io::ErrorKind::InvalidInput, "not a symlink")),
+        }
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let (dir, Entry::Directory) = self.entry(path, true)? else {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "not a directory"));
+        };
+        let mut entries = self
+            .entries
+            .keys()
+            .filter(|entry| entry.parent() == Some(dir.as_path()))
+            .filter_map(|entry| entry.file_name().map(|name| path.join(name)))
+            .collect::<Vec<_>>();
+        entries.sort_unstable();
+        Ok(entries)
+    }
+}
+
+fn metadata(entry: &Entry) -> FileMetadata {
+    match entry {
+        Entry::File(content) => FileMetadata::new(true, false, false)
+            .with_mtime_and_size(None, u64::try_from(content.len()).ok()),
+        Entry::Directory => FileMetadata::new(false, true, false),
+        Entry::Symlink(_) => FileMetadata::new(false, false, true),
+    }
+}
@@ -0,0 +1,199 @@
+//! Graph of how specifiers were resolved, see [crate::ResolverGeneric::resolution_graph].
+//!
+//! Each entry `(directory, specifier)` is a [GraphNode::Request] node,
+//! connected through the specifiers and paths it was rewritten to by aliases, "exports", "imports" and tsconfig paths,
+//! to the resolved file or the error.
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+use rustc_hash::FxHashMap;
+
+use crate::{Resolution, ResolveError};
+
+/// A node of [ResolutionGraph].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GraphNode {
+    /// An entry passed to [crate::ResolverGeneric::resolution_graph].
+    Request { directory: PathBuf, specifier: String },
+
+    /// A specifier an alias rewrote the request to.
+    Specifier(String),
+
+    /// A path matched by "exports", "imports" or tsconfig paths, or the resolved file.
+    File(PathBuf),
+
+    /// The error of a failed resolution.
+    Error(String),
+}
+
+/// How an edge of [ResolutionGraph] was taken.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GraphEdgeKind {
+    /// [crate::ResolveOptions::alias], [crate::ResolveOptions::fallback] or a package.json alias with the matched key.
+    Alias(String),
+
+    /// The "exports" field of a package.json.
+    Exports,
+
+    /// The "imports" field of a package.json.
+    Imports,
+
+    /// The "paths" of a tsconfig.
+    TsconfigPaths,
+
+    /// Loaded as a file or directory.
+    Resolved,
+
+    /// The resolution failed.
+    Failed,
+}
+
+impl GraphEdgeKind {
+    fn label(&self) -> String {
+        match self {
+            Self::Alias(key) => format!("alias {key}"),
+            Self::Exports => "exports".to_string(),
+            Self::Imports => "imports".to_string(),
+            Self::TsconfigPaths => "tsconfig paths".to_string(),
+            Self::Resolved => "resolved".to_string(),
+            Self::Failed => "failed".to_string(),
+        }
+    }
+}
+
+/// An edge between two [ResolutionGraph::nodes] indices.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GraphEdge {
+    pub from: usize,
+    pub to: usize,
+    pub kind: GraphEdgeKind,
+}
+
+/// A rewrite taken by a successful resolution, collected when resolving for [ResolutionGraph].
+#[derive(Debug, Clone)]
+pub struct ResolveStep {
+    pub kind: GraphEdgeKind,
+    pub to: GraphNode,
+}
+
+/// Who resolved what to where, exportable as JSON or DOT.
+///
+/// Nodes and edges are deduplicated, so entries resolving through the same alias share its nodes.
+#[derive(Debug, Default, Clone)]
+pub struct ResolutionGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+    indices: FxHashMap<GraphNode, usize>,
+}
+
+impl ResolutionGraph {
+    /// Adds the resolution of an entry, `steps` are in the order they were completed, innermost first.
+    pub(crate) fn add(
+        &mut self,
+        directory: &Path,
+        specifier: &str,
+        steps: Vec<ResolveStep>,
+        result: &Result<Resolution, ResolveError>,
+    ) {
+        let mut from = self.node(GraphNode::Request {
+            directory: directory.to_path_buf(),
+            specifier: specifier.to_string(),
+        });
+        match result {
+            Ok(resolution) => {
+                for step in steps.into_iter().rev() {
+                    let to = self.node(step.to);
+                    self.edge(from, to, step.kind);
+                    from = to;
+                }
+                let to = self.node(GraphNode::File(resolution.full_path()));
+                if to != from {
+                    self.edge(from, to, GraphEdgeKind::Resolved);
+                }
+            }
+            Err(error) => {
+                let to = self.node(GraphNode::Error(error.to_string()));
+                self.edge(from, to, GraphEdgeKind::Failed);
+            }
+        }
+    }
+
+    fn node(&mut self, node: GraphNode) -> usize {
+        if let Some(index) = self.indices.get(&node) {
+            return *index;
+        }
+        let index = self.nodes.len();
+        self.indices.insert(node.clone(), index);
+        self.nodes.push(node);
+        index
+    }
+
+    fn edge(&mut self, from: usize, to: usize, kind: GraphEdgeKind) {
+        let edge = GraphEdge { from, to, kind };
+        if !self.edges.contains(&edge) {
+            self.edges.push(edge);
+        }
+    }
+
+    fn label(node: &GraphNode) -> String {
+        match node {
+            GraphNode::Request { directory, specifier } => {
+                format!("{specifier} from {}", directory.display())
+            }
+            GraphNode::Specifier(specifier) => specifier.clone(),
+            GraphNode::File(path) => path.to_string_lossy().to_string(),
+            GraphNode::Error(message) => message.clone(),
+        }
+    }
+
+    /// Exports the graph as JSON, `{ "nodes": [{ "kind", "label" }], "edges": [{ "from", "to", "kind" }] }`.
+    pub fn to_json(&self) -> String {
+        let nodes = self
+            .nodes
+            .iter()
+            .map(|node| {
+                let kind = match node {
+                    GraphNode::Request { .. } => "request",
+                    GraphNode::Specifier(_) => "specifier",
+                    GraphNode::File(_) => "file",
+                    GraphNode::Error(_) => "error",
+                };
+                serde_json::json!({ "kind": kind, "label": Self::label(node) })
+            })
+            .collect::<Vec<_>>();
+        let edges = self
+            .edges
+            .iter()
+            .map(|edge| {
+                serde_json::json!({ "from": edge.from, "to": edge.to, "kind": edge.kind.label() })
+            })
+            .collect::<Vec<_>>();
+        serde_json::json!({ "nodes": nodes, "edges": edges }).to_string()
+    }
+
+    /// Exports the graph in the Graphviz DOT language.
+    pub fn to_dot(&self) -> String {
+        fn escape(label: &str) -> String {
+            label.replace('\\', "\\\\").replace('"', "\\\"")
+        }
+        let mut dot = String::from("digraph resolutions {\n");
+        for (index, node) in self.nodes.iter().enumerate() {
+            let shape = match node {
+                GraphNode::Request { .. } => "box",
+                GraphNode::Specifier(_) => "ellipse",
+                GraphNode::File(_) => "note",
+                GraphNode::Error(_) => "octagon",
+            };
+            let label = escape(&Self::label(node));
+            let _ = writeln!(dot, "  {index} [label=\"{label}\", shape={shape}];");
+        }
+        for edge in &self.edges {
+            let label = escape(&edge.kind.label());
+            let _ = writeln!(dot, "  {} -> {} [label=\"{label}\"];", edge.from, edge.to);
+        }
+        dot.push('}');
+        dot
+    }
+}
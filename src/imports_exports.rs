@@ -0,0 +1,259 @@
+//! Precompiled "exports" and "imports" fields.
+//!
+//! Both fields are compiled once when the package.json is parsed,
+//! so resolving a subpath does not re-walk the raw JSON on every request.
+use std::cmp::Reverse;
+
+use rustc_hash::FxHashMap;
+use serde_json::Value as JSONValue;
+
+use crate::package_json::JSONMap;
+
+/// A target value in the "exports" or "imports" field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportsExportsTarget {
+    /// `"./dist/index.js"`
+    String(String),
+
+    /// `{ "import": "./index.mjs", "default": "./index.js" }`, in object insertion order.
+    Conditions(Vec<(String, ImportsExportsTarget)>),
+
+    /// `["./a.js", "./b.js"]`
+    Array(Vec<ImportsExportsTarget>),
+
+    /// `null`, and all other values which never resolve.
+    Null,
+}
+
+impl From<&JSONValue> for ImportsExportsTarget {
+    fn from(value: &JSONValue) -> Self {
+        match value {
+            JSONValue::String(target) => Self::String(target.clone()),
+            JSONValue::Object(map) => Self::Conditions(
+                map.iter().map(|(key, value)| (key.clone(), value.into())).collect(),
+            ),
+            JSONValue::Array(targets) => Self::Array(targets.iter().map(Into::into).collect()),
+            _ => Self::Null,
+        }
+    }
+}
+
+/// A subpath map, i.e. an "exports" object with keys starting with "." or the "imports" object.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportsExportsMap {
+    exact: FxHashMap<String, ImportsExportsTarget>,
+
+    /// Keys containing a single "*" and folder mappings ending with "/",
+    /// sorted by PATTERN_KEY_COMPARE in descending order of specificity.
+    patterns: Vec<ImportsExportsPattern>,
+
+    /// Whether `"./*": "./*"` is the only pattern, so every subpath that is not an exact key resolves to itself.
+    identity_wildcard: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ImportsExportsPattern {
+    key: String,
+    /// The index of "*" in `key`, `None` for a folder mapping ending with "/".
+    star: Option<usize>,
+    target: ImportsExportsTarget,
+}
+
+impl ImportsExportsPattern {
+    /// Returns the pattern match for `match_key`.
+    fn matches<'a>(&self, match_key: &'a str) -> Option<&'a str> {
+        let Some(star) = self.star else {
+            return match_key.strip_prefix(self.key.as_str());
+        };
+        // 1. Let patternBase be the substring of expansionKey up to but excluding the first "*" character.
+        let (pattern_base, pattern_trailer) = (&self.key[..star], &self.key[star + 1..]);
+        // 2. If matchKey starts with but is not equal to patternBase, then
+        // 1. Let patternTrailer be the substring of expansionKey from the index after the first "*" character.
+        // 2. If patternTrailer has zero length, or if matchKey ends with patternTrailer and the length of matchKey is greater than or equal to the length of expansionKey, then
+        (match_key.starts_with(pattern_base)
+            && (pattern_trailer.is_empty()
+                || (match_key.len() >= self.key.len() && match_key.ends_with(pattern_trailer))))
+        // 2. Let patternMatch be the substring of matchKey starting at the index of the length of patternBase up to the length of matchKey minus the length of patternTrailer.
+        .then(|| &match_key[pattern_base.len()..match_key.len() - pattern_trailer.len()])
+    }
+}
+
+impl ImportsExportsMap {
+    pub fn new(map: &JSONMap) -> Self {
+        let mut exact = FxHashMap::default();
+        let mut patterns = vec![];
+        for (key, value) in map {
+            let star = if key.starts_with("./") || key.starts_with('#') {
+                match key.split_once('*') {
+                    // Keys with more than one "*" are never matched as a pattern.
+                    Some((_, pattern_trailer)) if pattern_trailer.contains('*') => continue,
+                    Some((pattern_base, _)) => Some(Some(pattern_base.len())),
+                    None if key.ends_with('/') => Some(None),
+                    None => None,
+                }
+            } else {
+                None
+            };
+            match star {
+                // Keys containing "*" or ending with "/" are never matched exactly.
+                Some(star) => {
+                    patterns.push(ImportsExportsPattern {
+                        key: key.clone(),
+                        star,
+                        target: value.into(),
+                    });
+                }
+                None => {
+                    exact.insert(key.clone(), value.into());
+                }
+            }
+        }
+        // PATTERN_KEY_COMPARE(keyA, keyB) as a sort key:
+        // longer base length first, then keys containing "*", then longer keys.
+        // The sort is stable, keys with the same specificity keep their insertion order.
+        patterns.sort_by_key(|pattern| {
+            let base_length =
+                pattern.key.chars().position(|c| c == '*').map_or(pattern.key.len(), |p| p + 1);
+            (Reverse(base_length), pattern.star.is_none(), Reverse(pattern.key.len()))
+        });
+        let identity_wildcard = matches!(
+            patterns.as_slice(),
+            [ImportsExportsPattern { key, target: ImportsExportsTarget::String(target), .. }]
+                if key == "./*" && target == "./*"
+        );
+        Self { exact, patterns, identity_wildcard }
+    }
+
+    /// Whether the map is `{ "./*": "./*" }` plus any number of exact subpaths.
+    pub fn is_identity_wildcard(&self) -> bool {
+        self.identity_wildcard
+    }
+
+    /// Returns the target of `key` when it is matched exactly, e.g. the main export `"."`.
+    pub fn get(&self, key: &str) -> Option<&ImportsExportsTarget> {
+        self.exact.get(key)
+    }
+
+    /// Returns `(key, target, patternMatch)` of the best match for `match_key`.
+    pub fn find<'a>(
+        &'a self,
+        match_key: &'a str,
+    ) -> Option<(&'a str, &'a ImportsExportsTarget, Option<&'a str>)> {
+        // 1. If matchKey is a key of matchObj and does not contain "*", then
+        if !match_key.contains('*') {
+            if let Some((key, target)) = self.exact.get_key_value(match_key) {
+                return Some((key, target, None));
+            }
+        }
+        // Fast path for the `"./*": "./*"` sugar, the pattern table does not need to be matched.
+        if self.identity_wildcard {
+            return match_key.strip_prefix("./").map(|pattern_match| {
+                let pattern = &self.patterns[0];
+                (pattern.key.as_str(), &pattern.target, Some(pattern_match))
+            });
+        }
+        // 2. Let expansionKeys be the list of keys of matchObj containing only a single "*", sorted by the sorting function PATTERN_KEY_COMPARE which orders in descending order of specificity.
+        // 3. For each key expansionKey in expansionKeys, do
+        self.patterns.iter().find_map(|pattern| {
+            pattern
+                .matches(match_key)
+                .map(|pattern_match| (pattern.key.as_str(), &pattern.target, Some(pattern_match)))
+        })
+    }
+}
+
+/// A typed "exports" field, classified when the package.json is parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExportsField {
+    /// `"exports": "./index.js"`
+    String(String),
+
+    /// `"exports": ["./a.js", "./b.js"]`
+    Array(Vec<ImportsExportsTarget>),
+
+    /// An object with all keys starting with ".", e.g. `{ ".": "./index.js", "./sub": "./sub.js" }`.
+    Map(ImportsExportsMap),
+
+    /// An object with no keys starting with ".", e.g. `{ "import": "./index.mjs" }`, in object insertion order.
+    Conditions(Vec<(String, ImportsExportsTarget)>),
+
+    /// `null`, and all other values which never resolve.
+    Null,
+
+    /// An object with both a key starting with "." and a key not starting with ".".
+    Invalid,
+}
+
+impl ExportsField {
+    pub fn new(exports: &JSONValue) -> Self {
+        match exports {
+            JSONValue::String(target) => Self::String(target.clone()),
+            JSONValue::Array(targets) => Self::Array(targets.iter().map(Into::into).collect()),
+            JSONValue::Object(map) => {
+                let mut has_dot = false;
+                let mut without_dot = false;
+                for key in map.keys() {
+                    let starts_with_dot_or_hash = key.starts_with(['.', '#']);
+                    has_dot = has_dot || starts_with_dot_or_hash;
+                    without_dot = without_dot || !starts_with_dot_or_hash;
+                }
+                match (has_dot, without_dot) {
+                    (true, true) => Self::Invalid,
+                    (true, false) => Self::Map(ImportsExportsMap::new(map)),
+                    _ => Self::Conditions(
+                        map.iter().map(|(key, value)| (key.clone(), value.into())).collect(),
+                    ),
+                }
+            }
+            _ => Self::Null,
+        }
+    }
+}
+
+#[test]
+fn find() {
+    let json = serde_json::json!({
+        "./a/": "./folder/",
+        "./a/*": "./star/*",
+        "./a/b/*.js": "./long/*.js",
+        "./a/*.js": "./short/*.js",
+        "./a/exact": "./exact.js",
+        "./x/*/*": "./never",
+    });
+    let map = ImportsExportsMap::new(json.as_object().unwrap());
+    let target = |s: &str| ImportsExportsTarget::String(s.to_string());
+
+    assert_eq!(map.find("./a/exact"), Some(("./a/exact", &target("./exact.js"), None)));
+    assert_eq!(map.find("./a/b/c.js"), Some(("./a/b/*.js", &target("./long/*.js"), Some("c"))));
+    assert_eq!(map.find("./a/c.js"), Some(("./a/*.js", &target("./short/*.js"), Some("c"))));
+    assert_eq!(map.find("./a/c"), Some(("./a/*", &target("./star/*"), Some("c"))));
+    assert_eq!(map.find("./x/y/z"), None);
+    assert_eq!(map.find("./y"), None);
+}
+
+#[test]
+fn exports_field() {
+    use serde_json::json;
+    let target = |s: &str| ImportsExportsTarget::String(s.to_string());
+
+    assert_eq!(ExportsField::new(&json!("./a.js")), ExportsField::String("./a.js".into()));
+    assert_eq!(ExportsField::new(&json!(["./a.js"])), ExportsField::Array(vec![target("./a.js")]));
+    assert_eq!(
+        ExportsField::new(&json!({ "import": "./a.mjs" })),
+        ExportsField::Conditions(vec![("import".into(), target("./a.mjs"))])
+    );
+    assert_eq!(ExportsField::new(&json!({})), ExportsField::Conditions(vec![]));
+    assert_eq!(
+        ExportsField::new(&json!({ ".": "./a.js", "import": "./a.mjs" })),
+        ExportsField::Invalid
+    );
+    assert_eq!(ExportsField::new(&json!(null)), ExportsField::Null);
+    assert_eq!(ExportsField::new(&json!(true)), ExportsField::Null);
+
+    let ExportsField::Map(map) = ExportsField::new(&json!({ ".": "./a.js", "./b": "./b.js" }))
+    else {
+        unreachable!()
+    };
+    assert_eq!(map.get("."), Some(&target("./a.js")));
+    assert_eq!(map.get("./c"), None);
+}
@@ -52,49 +52,80 @@ mod cache;
 mod context;
 mod error;
 mod file_system;
+#[cfg(feature = "fixture_fs")]
+mod fixture_fs;
+mod graph;
+mod imports_exports;
 mod options;
 mod package_json;
 mod path;
+mod report;
 mod resolution;
 mod specifier;
 mod tsconfig;
+mod workspaces;
 
 #[cfg(test)]
 mod tests;
 
 use std::{
     borrow::Cow,
-    cmp::Ordering,
-    ffi::OsStr,
+    ffi::{OsStr, OsString},
     fmt,
     path::{Component, Path, PathBuf},
-    sync::Arc,
+    sync::{
+        atomic::{AtomicUsize, Ordering as AtomicOrdering},
+        Arc,
+    },
 };
 
-use dashmap::{mapref::one::Ref, DashMap};
+use dashmap::{
+    mapref::{entry::Entry, one::MappedRef},
+    DashMap,
+};
 use rustc_hash::FxHashSet;
-use serde_json::Value as JSONValue;
 
+#[cfg(feature = "fixture_fs")]
+pub use crate::fixture_fs::FixtureFileSystem;
 pub use crate::{
-    builtins::NODEJS_BUILTINS,
-    error::{JSONError, ResolveError, SpecifierError},
-    file_system::{FileMetadata, FileSystem, FileSystemOs},
+    builtins::{
+        is_nodejs_builtin, is_nodejs_builtin_in, NodeVersion, NODEJS_BUILTINS,
+        NODEJS_VERSIONED_BUILTINS,
+    },
+    cache::{CacheStats, MemoryUsage},
+    context::{CancellationToken, ResolveAttempt, ResolveAttemptReason},
+    error::{JSONError, OptionsWarning, ResolveError, SpecifierError},
+    file_system::{
+        FileMetadata, FileSystem, FileSystemOptions, FileSystemOs, FsOperation, FsTraceStats,
+        FsTraceSummary, OverlayFileSystem, TraceFileSystem,
+    },
+    graph::{GraphEdge, GraphEdgeKind, GraphNode, ResolutionGraph},
+    imports_exports::{ExportsField, ImportsExportsMap, ImportsExportsTarget},
     options::{
-        Alias, AliasValue, EnforceExtension, ResolveOptions, Restriction, TsconfigOptions,
+        Alias, AliasFieldOverride, AliasValue, DescriptionFilesBoundary, EnforceExtension,
+        EnhancedResolveAlias, EnhancedResolveAliasItem, EnhancedResolveFieldPath,
+        EnhancedResolveFieldPathList, EnhancedResolveOptions, EnhancedResolveStrOrStrList,
+        EnhancedResolveTsconfig, External, ModulesBoundary, ModulesProvider, ResolveOptions,
+        Restriction, RootPrefixOptions, SpecifierRewrite, StrictDependencies, SymlinkEscapeOptions,
+        SymlinkEscapePolicy, TrailingSlash, TsconfigOptions, TsconfigPathsPrecedence,
         TsconfigReferences,
     },
-    package_json::PackageJson,
-    resolution::Resolution,
+    package_json::{PackageJson, PackageJsonProvider},
+    path::{normalize_path, normalize_path_with},
+    report::NotFoundReport,
+    resolution::{Resolution, ResolveOutcome},
+    specifier::{escape_specifier, unescape_specifier, Specifier},
 };
 use crate::{
-    cache::{Cache, CachedPath},
+    cache::{hash_path, Cache, CachedPath},
     context::ResolveContext as Ctx,
-    package_json::JSONMap,
-    path::{PathUtil, SLASH_START},
-    specifier::Specifier,
+    path::{relative_path, to_forward_slashes, PathUtil, SLASH_START},
     tsconfig::ExtendsField,
-    tsconfig::{ProjectReference, TsConfig},
+    tsconfig::{ProjectReference, TsConfig, TsconfigProvider},
+    workspaces::Workspaces,
 };
+/// The regular expression of [ResolveOptions::regex_alias] keys.
+pub use regex::Regex;
 
 type ResolveResult = Result<Option<CachedPath>, ResolveError>;
 
@@ -106,6 +137,53 @@ pub struct ResolveContext {
 
     /// Dependencies that was not found on file system
     pub missing_dependencies: FxHashSet<PathBuf>,
+
+    /// package.json and tsconfig files that were consulted, also contained in `file_dependencies`,
+    /// so manifests can be watched or invalidated differently from source files.
+    pub description_dependencies: FxHashSet<PathBuf>,
+
+    /// Candidate paths that were tried and rejected, in order.
+    ///
+    /// Only collected when set to `Some` before calling [Resolver::resolve_with_context],
+    /// useful for "module not found, tried these locations" error messages.
+    pub attempts: Option<Vec<ResolveAttempt>>,
+
+    /// The node_modules, [ResolveOptions::modules] and [ResolveOptions::roots] directories
+    /// searched for the specifier, in order, to explain a [ResolveError::NotFound].
+    ///
+    /// Only collected along with `attempts`.
+    pub searched_directories: Vec<PathBuf>,
+
+    /// Warnings about resolutions which succeeded in a degraded way,
+    /// e.g. undeclared dependencies found in Yarn PnP loose mode ([ResolveOptions::pnp_loose]),
+    /// and deprecated folder mappings in "exports".
+    ///
+    /// Only collected when set to `Some` before calling [Resolver::resolve_with_context].
+    pub warnings: Option<Vec<String>>,
+
+    /// Fails the resolution with [ResolveError::Cancelled] once the token is cancelled or its deadline has passed.
+    ///
+    /// Checked while walking node_modules directories and probing extensions.
+    pub cancellation: Option<CancellationToken>,
+}
+
+impl ResolveContext {
+    /// Clears the collected dependencies, attempts and warnings while keeping the allocated capacity,
+    /// so the context can be reused across [Resolver::resolve_with_context] calls in batch resolution.
+    ///
+    /// Collecting attempts and warnings stays enabled if it was enabled, and the cancellation token is kept.
+    pub fn clear(&mut self) {
+        self.file_dependencies.clear();
+        self.missing_dependencies.clear();
+        self.description_dependencies.clear();
+        if let Some(attempts) = &mut self.attempts {
+            attempts.clear();
+        }
+        self.searched_directories.clear();
+        if let Some(warnings) = &mut self.warnings {
+            warnings.clear();
+        }
+    }
 }
 
 /// Resolver with the current operating system as the file system
@@ -114,9 +192,19 @@ pub type Resolver = ResolverGeneric<FileSystemOs>;
 /// Generic implementation of the resolver, can be configured by the [FileSystem] trait
 pub struct ResolverGeneric<Fs> {
     options: ResolveOptions,
+    /// Resolvers for [ResolveOptions::fallback_options].
+    fallback_resolvers: Vec<Self>,
     cache: Arc<Cache<Fs>>,
     #[cfg(feature = "yarn_pnp")]
-    pnp_cache: Arc<DashMap<CachedPath, Option<pnp::Manifest>>>,
+    pnp_cache: Arc<DashMap<CachedPath, PnpManifest>>,
+}
+
+/// The Yarn PnP manifest found from a directory, with the size of its `.pnp.cjs` when it was loaded.
+#[cfg(feature = "yarn_pnp")]
+struct PnpManifest {
+    manifest: Option<pnp::Manifest>,
+    /// Size of the `.pnp.cjs` file in bytes.
+    size: usize,
 }
 
 impl<Fs> fmt::Debug for ResolverGeneric<Fs> {
@@ -133,23 +221,20 @@ impl<Fs: FileSystem + Default> Default for ResolverGeneric<Fs> {
 
 impl<Fs: FileSystem + Default> ResolverGeneric<Fs> {
     pub fn new(options: ResolveOptions) -> Self {
-        Self {
-            options: options.sanitize(),
-            cache: Arc::new(Cache::new(Fs::default())),
-            #[cfg(feature = "yarn_pnp")]
-            pnp_cache: Arc::new(DashMap::default()),
-        }
+        Self::new_with_file_system(Fs::default(), options)
     }
 }
 
 impl<Fs: FileSystem> ResolverGeneric<Fs> {
     pub fn new_with_file_system(file_system: Fs, options: ResolveOptions) -> Self {
         Self {
+            cache: Arc::new(Cache::new(file_system, options.disable_cache)),
             options: options.sanitize(),
-            cache: Arc::new(Cache::new(file_system)),
+            fallback_resolvers: vec![],
             #[cfg(feature = "yarn_pnp")]
             pnp_cache: Arc::new(DashMap::default()),
         }
+        .with_fallback_resolvers()
     }
 
     /// Clone the resolver using the same underlying cache.
@@ -157,10 +242,23 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
     pub fn clone_with_options(&self, options: ResolveOptions) -> Self {
         Self {
             options: options.sanitize(),
+            fallback_resolvers: vec![],
             cache: Arc::clone(&self.cache),
             #[cfg(feature = "yarn_pnp")]
             pnp_cache: Arc::clone(&self.pnp_cache),
         }
+        .with_fallback_resolvers()
+    }
+
+    /// Creates the resolvers of [ResolveOptions::fallback_options] once, sharing the cache.
+    fn with_fallback_resolvers(mut self) -> Self {
+        self.fallback_resolvers = self
+            .options
+            .fallback_options
+            .iter()
+            .map(|options| self.clone_with_options(options.clone()))
+            .collect();
+        self
     }
 
     /// Returns the options.
@@ -168,9 +266,87 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
         &self.options
     }
 
+    /// Returns the file system.
+    pub fn file_system(&self) -> &Fs {
+        &self.cache.fs
+    }
+
     /// Clear the underlying cache.
     pub fn clear_cache(&self) {
         self.cache.clear();
+        #[cfg(feature = "yarn_pnp")]
+        self.pnp_cache.clear();
+    }
+
+    /// Drop the cached entries of `path` and everything under it, keeping the rest of the cache.
+    ///
+    /// Use this when files under `path` changed, e.g. a symlink was re-linked to another target,
+    /// so their metadata, real paths, package.json, tsconfig and `.pnp.cjs` files are read again.
+    ///
+    /// A file, e.g. a changed package.json or a created module, also drops the entries of its directory.
+    pub fn invalidate(&self, path: &Path) {
+        self.cache.invalidate(path);
+        #[cfg(feature = "yarn_pnp")]
+        {
+            // A created or removed `.pnp.cjs` changes the manifest found from every directory under its own.
+            let pnp_dir =
+                path.parent().filter(|_| path.file_name() == Some(OsStr::new(".pnp.cjs")));
+            self.pnp_cache.retain(|cached_path, entry| {
+                !cached_path.path().starts_with(path)
+                    && !pnp_dir.is_some_and(|dir| cached_path.path().starts_with(dir))
+                    && !entry
+                        .manifest
+                        .as_ref()
+                        .is_some_and(|manifest| manifest.manifest_path.starts_with(path))
+            });
+        }
+    }
+
+    /// Returns the cached paths dropped by [Self::invalidate] since the last call, sorted.
+    ///
+    /// These are the directories and files, including missing ones, that were looked up by earlier resolutions,
+    /// so only the modules whose file or missing dependencies are among them need to be resolved again.
+    pub fn take_invalidated(&self) -> Vec<PathBuf> {
+        self.cache.take_invalidated()
+    }
+
+    /// Returns the number of entries held by the underlying cache.
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache.stats()
+    }
+
+    /// Returns the approximate number of bytes held by the underlying cache and the Yarn PnP manifests,
+    /// for attributing the memory of a resolver.
+    ///
+    /// This walks the whole cache, call it for debugging rather than on every resolution.
+    pub fn approximate_memory_usage(&self) -> MemoryUsage {
+        #[cfg_attr(not(feature = "yarn_pnp"), allow(unused_mut))]
+        let mut usage = self.cache.approximate_memory_usage();
+        #[cfg(feature = "yarn_pnp")]
+        {
+            usage.pnp_manifests = self
+                .pnp_cache
+                .iter()
+                .filter(|entry| entry.manifest.is_some())
+                .map(|entry| entry.size)
+                .sum();
+        }
+        usage
+    }
+
+    /// Returns whether `specifier` resolves to a Node.js builtin module ([ResolveError::Builtin]).
+    ///
+    /// Always returns `false` when [ResolveOptions::builtin_modules] is disabled.
+    /// Specifiers with the `node:` prefix are always treated as builtin modules,
+    /// unless [ResolveOptions::builtin_modules_node_version] is set.
+    pub fn is_builtin(&self, specifier: &str) -> bool {
+        if !self.options.builtin_modules {
+            return false;
+        }
+        self.options.builtin_modules_node_version.map_or_else(
+            || specifier.starts_with("node:") || NODEJS_BUILTINS.binary_search(&specifier).is_ok(),
+            |version| is_nodejs_builtin_in(specifier, version),
+        )
     }
 
     /// Resolve `specifier` at an absolute path to a `directory`.
@@ -190,7 +366,105 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
         specifier: &str,
     ) -> Result<Resolution, ResolveError> {
         let mut ctx = Ctx::default();
-        self.resolve_tracing(directory.as_ref(), specifier, &mut ctx)
+        self.resolve_request(directory.as_ref(), specifier, &mut ctx)
+    }
+
+    /// Resolve `specifier` at an absolute path to a `directory`, returning ignored modules as
+    /// [ResolveOutcome::Ignored] instead of [ResolveError::Ignored].
+    ///
+    /// # Errors
+    ///
+    /// * See [ResolveError], except for [ResolveError::Ignored]
+    pub fn resolve_outcome<P: AsRef<Path>>(
+        &self,
+        directory: P,
+        specifier: &str,
+    ) -> Result<ResolveOutcome, ResolveError> {
+        ResolveOutcome::from_result(self.resolve(directory, specifier))
+    }
+
+    /// Resolve `specifier` at an absolute path to a `directory` once per condition set,
+    /// each replacing [ResolveOptions::condition_names], e.g. `["import"]` and `["require"]` for dual-format output.
+    ///
+    /// Returns a result per set. The sets share the cache, and a result that did not depend on
+    /// the conditions of "exports" or "imports" is used for the remaining sets without resolving again.
+    pub fn resolve_condition_sets<P: AsRef<Path>>(
+        &self,
+        directory: P,
+        specifier: &str,
+        condition_sets: &[Vec<String>],
+    ) -> Vec<Result<Resolution, ResolveError>> {
+        let mut results = Vec::with_capacity(condition_sets.len());
+        for conditions in condition_sets {
+            let mut ctx = Ctx::default();
+            ctx.condition_names = Some(Arc::from(conditions.as_slice()));
+            let result = self.resolve_request(directory.as_ref(), specifier, &mut ctx);
+            if !ctx.conditions_evaluated {
+                results.resize(condition_sets.len(), result);
+                break;
+            }
+            results.push(result);
+        }
+        results
+    }
+
+    /// Resolve `specifier` at an absolute path to a `directory`, returning every existing candidate in priority order
+    /// instead of stopping at the first one, e.g. both `foo.ts` and `foo.js` for extensions `[".ts", ".js"]`.
+    ///
+    /// The first candidate is the result of [ResolverGeneric::resolve].
+    /// Candidates are collected by resolving again with the previously found files excluded,
+    /// so alternatives are found wherever the algorithm moves on from a missing file:
+    /// extensions, main fields, main files and `node_modules` directories.
+    ///
+    /// # Errors
+    ///
+    /// * See [ResolveError], when there is no candidate at all
+    pub fn resolve_all_candidates<P: AsRef<Path>>(
+        &self,
+        directory: P,
+        specifier: &str,
+    ) -> Result<Vec<Resolution>, ResolveError> {
+        let mut ctx = Ctx::default();
+        ctx.excluded_candidates = Some(FxHashSet::default());
+        let mut candidates = vec![self.resolve_request(directory.as_ref(), specifier, &mut ctx)?];
+        loop {
+            let excluded_candidates = ctx.excluded_candidates.take();
+            ctx = Ctx::default();
+            ctx.excluded_candidates = excluded_candidates;
+            // Not counted by metrics, these are part of the same request.
+            let Ok(resolution) = self.resolve_tracing(directory.as_ref(), specifier, &mut ctx)
+            else {
+                break;
+            };
+            // Candidates which cannot be excluded, e.g. directories with `resolve_to_context`.
+            if candidates.contains(&resolution) {
+                break;
+            }
+            candidates.push(resolution);
+        }
+        Ok(candidates)
+    }
+
+    /// Resolve `specifier` at an absolute UTF-8 `directory`,
+    /// returning the full path with query and fragment.
+    ///
+    /// # Errors
+    ///
+    /// * See [ResolveError]
+    /// * [ResolveError::IOError] with [std::io::ErrorKind::InvalidData] when the resolved path is not valid UTF-8
+    #[cfg(feature = "camino")]
+    pub fn resolve_utf8<P: AsRef<camino::Utf8Path>>(
+        &self,
+        directory: P,
+        specifier: &str,
+    ) -> Result<camino::Utf8PathBuf, ResolveError> {
+        let resolution = self.resolve(directory.as_ref(), specifier)?;
+        resolution.utf8_full_path().ok_or_else(|| {
+            ResolveError::from(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Path {} is not valid UTF-8", resolution.path().display()),
+            ))
+        })
     }
 
     /// Resolve `specifier` at absolute `path` with [ResolveContext]
@@ -204,19 +478,341 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
         specifier: &str,
         resolve_context: &mut ResolveContext,
     ) -> Result<Resolution, ResolveError> {
+        // Collect straight into the caller's buffers, so a context reused across requests
+        // (see [ResolveContext::clear]) does not allocate new collections per request.
         let mut ctx = Ctx::default();
-        ctx.init_file_dependencies();
-        let result = self.resolve_tracing(directory.as_ref(), specifier, &mut ctx);
-        if let Some(deps) = &mut ctx.file_dependencies {
-            resolve_context.file_dependencies.extend(deps.drain(..));
+        ctx.init_file_dependencies(
+            std::mem::take(&mut resolve_context.file_dependencies),
+            std::mem::take(&mut resolve_context.missing_dependencies),
+        );
+        ctx.description_dependencies =
+            Some(std::mem::take(&mut resolve_context.description_dependencies));
+        ctx.attempts = resolve_context.attempts.take();
+        ctx.searched_directories = std::mem::take(&mut resolve_context.searched_directories);
+        ctx.warnings = resolve_context.warnings.take();
+        ctx.cancellation.clone_from(&resolve_context.cancellation);
+        let result = self.resolve_request(directory.as_ref(), specifier, &mut ctx);
+        (resolve_context.file_dependencies, resolve_context.missing_dependencies) =
+            ctx.take_file_dependencies();
+        resolve_context.description_dependencies =
+            ctx.description_dependencies.take().unwrap_or_default();
+        resolve_context.attempts = ctx.attempts.take();
+        resolve_context.searched_directories = std::mem::take(&mut ctx.searched_directories);
+        resolve_context.warnings = ctx.warnings.take();
+        result
+    }
+
+    /// [ResolverGeneric::resolve_outcome] with [ResolveContext]
+    ///
+    /// # Errors
+    ///
+    /// * See [ResolveError], except for [ResolveError::Ignored]
+    pub fn resolve_outcome_with_context<P: AsRef<Path>>(
+        &self,
+        directory: P,
+        specifier: &str,
+        resolve_context: &mut ResolveContext,
+    ) -> Result<ResolveOutcome, ResolveError> {
+        ResolveOutcome::from_result(self.resolve_with_context(
+            directory,
+            specifier,
+            resolve_context,
+        ))
+    }
+
+    /// Resolve `(directory, specifier)` entries and record who resolved what to where,
+    /// through which alias, "exports", "imports" and tsconfig paths rewrites, see [ResolutionGraph].
+    pub fn resolution_graph<P: AsRef<Path>, S: AsRef<str>>(
+        &self,
+        entries: &[(P, S)],
+    ) -> ResolutionGraph {
+        let mut graph = ResolutionGraph::default();
+        for (directory, specifier) in entries {
+            let (directory, specifier) = (directory.as_ref(), specifier.as_ref());
+            let mut ctx = Ctx::default();
+            ctx.steps = Some(vec![]);
+            let result = self.resolve_request(directory, specifier, &mut ctx);
+            graph.add(directory, specifier, ctx.steps.take().unwrap_or_default(), &result);
+        }
+        graph
+    }
+
+    /// Group the entries of a batch resolution not found, e.g. by [ResolverGeneric::resolve_all],
+    /// by missing package name and by importing directory, see [NotFoundReport].
+    ///
+    /// `results` are in the order of `entries`.
+    /// [ResolveError::NotFound] and [ResolveError::MatchedAliasNotFound] count as not found,
+    /// only bare specifiers are grouped by package name.
+    pub fn not_found_report<P: AsRef<Path>, S: AsRef<str>>(
+        &self,
+        entries: &[(P, S)],
+        results: &[Result<Resolution, ResolveError>],
+    ) -> NotFoundReport {
+        NotFoundReport::new(entries.iter().zip(results).filter_map(
+            |((directory, specifier), result)| {
+                if !matches!(
+                    result,
+                    Err(ResolveError::NotFound(_) | ResolveError::MatchedAliasNotFound(..))
+                ) {
+                    return None;
+                }
+                let specifier = specifier.as_ref();
+                let is_bare =
+                    !specifier.starts_with(['.', '/', '#']) && !Path::new(specifier).is_absolute();
+                let package_name = is_bare.then(|| Self::parse_package_specifier(specifier).0);
+                Some((directory.as_ref(), package_name))
+            },
+        ))
+    }
+
+    /// Resolve `(directory, specifier)` entries on up to `concurrency` threads sharing this resolver's cache.
+    ///
+    /// Returns one result per entry in the order of `entries`.
+    /// Dependencies of all entries are merged into `resolve_context`,
+    /// attempts (when enabled) are appended per thread and are not in entry order.
+    ///
+    /// A `concurrency` of `0` or `1` resolves on the current thread.
+    ///
+    /// # Panics
+    ///
+    /// * When a resolving thread panics.
+    pub fn resolve_all<P, S>(
+        &self,
+        entries: &[(P, S)],
+        concurrency: usize,
+        resolve_context: &mut ResolveContext,
+    ) -> Vec<Result<Resolution, ResolveError>>
+    where
+        Fs: Send + Sync,
+        P: AsRef<Path> + Sync,
+        S: AsRef<str> + Sync,
+    {
+        let concurrency = concurrency.clamp(1, entries.len().max(1));
+        if concurrency == 1 {
+            return entries
+                .iter()
+                .map(|(directory, specifier)| {
+                    self.resolve_with_context(directory, specifier.as_ref(), resolve_context)
+                })
+                .collect();
+        }
+
+        let next = AtomicUsize::new(0);
+        let collect_attempts = resolve_context.attempts.is_some();
+        let collect_warnings = resolve_context.warnings.is_some();
+        let cancellation = &resolve_context.cancellation;
+        let workers = std::thread::scope(|scope| {
+            // All threads must be spawned before joining any of them.
+            #[allow(clippy::needless_collect)]
+            let handles = (0..concurrency)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut ctx = ResolveContext {
+                            attempts: collect_attempts.then(Vec::new),
+                            warnings: collect_warnings.then(Vec::new),
+                            cancellation: cancellation.clone(),
+                            ..ResolveContext::default()
+                        };
+                        let mut results = vec![];
+                        loop {
+                            let index = next.fetch_add(1, AtomicOrdering::Relaxed);
+                            let Some((directory, specifier)) = entries.get(index) else {
+                                break;
+                            };
+                            let result =
+                                self.resolve_with_context(directory, specifier.as_ref(), &mut ctx);
+                            results.push((index, result));
+                        }
+                        (ctx, results)
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles.into_iter().map(|handle| handle.join().unwrap()).collect::<Vec<_>>()
+        });
+
+        let mut results = Vec::with_capacity(entries.len());
+        results.resize_with(entries.len(), || None);
+        for (ctx, worker_results) in workers {
+            resolve_context.file_dependencies.extend(ctx.file_dependencies);
+            resolve_context.missing_dependencies.extend(ctx.missing_dependencies);
+            resolve_context.description_dependencies.extend(ctx.description_dependencies);
+            if let (Some(attempts), Some(ctx_attempts)) =
+                (&mut resolve_context.attempts, ctx.attempts)
+            {
+                attempts.extend(ctx_attempts);
+            }
+            resolve_context.searched_directories.extend(ctx.searched_directories);
+            if let (Some(warnings), Some(ctx_warnings)) =
+                (&mut resolve_context.warnings, ctx.warnings)
+            {
+                warnings.extend(ctx_warnings);
+            }
+            for (index, result) in worker_results {
+                results[index] = Some(result);
+            }
         }
-        if let Some(deps) = &mut ctx.missing_dependencies {
-            resolve_context.missing_dependencies.extend(deps.drain(..));
+        results.into_iter().map(|result| result.unwrap()).collect()
+    }
+
+    /// Resolve the root directory of the package of a bare `specifier`, i.e. the directory containing its package.json,
+    /// looking up `node_modules` from an absolute path to a `directory`.
+    ///
+    /// Only the package name of `specifier` is used, the "main" and "exports" fields are not resolved.
+    ///
+    /// # Errors
+    ///
+    /// * [ResolveError::NotFound] when the package is not found or `specifier` is not a bare specifier
+    pub fn resolve_package_dir<P: AsRef<Path>>(
+        &self,
+        directory: P,
+        specifier: &str,
+    ) -> Result<PathBuf, ResolveError> {
+        let (package_name, _) = Self::parse_package_specifier(specifier);
+        if package_name.is_empty()
+            || package_name.starts_with(['.', '/', '#'])
+            || Path::new(package_name).is_absolute()
+        {
+            return Err(ResolveError::NotFound(specifier.to_string()));
         }
-        result
+        let mut ctx = Ctx::default();
+        let cached_path = self.cache.value(directory.as_ref(), &mut ctx);
+        self.find_package_directory(&cached_path, package_name, &mut ctx)?
+            .map(|(_, package_json)| package_json.directory().to_path_buf())
+            .ok_or_else(|| ResolveError::NotFound(specifier.to_string()))
+    }
+
+    /// Returns the "version" field of the package.json of `package_name`, looking up `node_modules` from an absolute path to a `directory`.
+    ///
+    /// The package.json is read from the cache, the "main" and "exports" fields are not resolved.
+    ///
+    /// # Errors
+    ///
+    /// * [ResolveError::NotFound] when the package is not found
+    pub fn package_version<P: AsRef<Path>>(
+        &self,
+        directory: P,
+        package_name: &str,
+    ) -> Result<Option<String>, ResolveError> {
+        let mut ctx = Ctx::default();
+        let cached_path = self.cache.value(directory.as_ref(), &mut ctx);
+        self.find_package_directory(&cached_path, package_name, &mut ctx)?
+            .map(|(_, package_json)| package_json.version.clone())
+            .ok_or_else(|| ResolveError::NotFound(package_name.to_string()))
+    }
+
+    /// Whether `specifier` requested from an absolute path to a `directory` matches one of [ResolveOptions::externals].
+    pub fn is_external<P: AsRef<Path>>(&self, directory: P, specifier: &str) -> bool {
+        let directory = directory.as_ref();
+        self.options.externals.iter().any(|external| external.matches(specifier, directory))
+    }
+
+    /// Returns the package.json of an external package, confirming it exists in `node_modules` from an absolute path to a `directory`.
+    ///
+    /// Returns `None` when `specifier` is not external, or does not name a package, e.g. `node:fs`.
+    ///
+    /// # Errors
+    ///
+    /// * [ResolveError::NotFound] when the external package is not found
+    pub fn external_package_json<P: AsRef<Path>>(
+        &self,
+        directory: P,
+        specifier: &str,
+    ) -> Result<Option<Arc<PackageJson>>, ResolveError> {
+        let directory = directory.as_ref();
+        if !self.is_external(directory, specifier) {
+            return Ok(None);
+        }
+        let (package_name, _) = Self::parse_package_specifier(specifier);
+        if package_name.is_empty()
+            || package_name.starts_with(['.', '/', '#'])
+            || package_name.contains(':')
+            || Path::new(package_name).is_absolute()
+        {
+            return Ok(None);
+        }
+        let mut ctx = Ctx::default();
+        let cached_path = self.cache.value(directory, &mut ctx);
+        self.find_package_directory(&cached_path, package_name, &mut ctx)?
+            .map(|(_, package_json)| Some(package_json))
+            .ok_or_else(|| ResolveError::NotFound(specifier.to_string()))
+    }
+
+    /// Returns the version of `package_name` in a pnpm catalog of the `pnpm-workspace.yaml` in [ResolveOptions::workspace_root].
+    ///
+    /// `catalog` is the name of a catalog in `catalogs`, `None` for the default `catalog`.
+    /// Returns `None` when [ResolveOptions::workspace_root] is not set or the catalog does not define the package.
+    ///
+    /// # Errors
+    ///
+    /// * See [ResolveError]
+    pub fn workspace_catalog(
+        &self,
+        catalog: Option<&str>,
+        package_name: &str,
+    ) -> Result<Option<String>, ResolveError> {
+        Ok(self.load_workspaces()?.and_then(|workspaces| {
+            workspaces.catalog(catalog, package_name).map(ToString::to_string)
+        }))
+    }
+
+    /// Resolve the executable of the "bin" field of `package_name`, looking up `node_modules` from an absolute path to a `directory`.
+    ///
+    /// `bin_name` selects an entry of the map form of the "bin" field,
+    /// and defaults to the package name without its scope, which is also the name of the string form.
+    ///
+    /// # Errors
+    ///
+    /// * [ResolveError::NotFound] when the package is not found
+    /// * [ResolveError::BinNotFound] when the package does not define the executable
+    pub fn resolve_bin<P: AsRef<Path>>(
+        &self,
+        directory: P,
+        package_name: &str,
+        bin_name: Option<&str>,
+    ) -> Result<PathBuf, ResolveError> {
+        let mut ctx = Ctx::default();
+        let cached_path = self.cache.value(directory.as_ref(), &mut ctx);
+        let Some((package_path, package_json)) =
+            self.find_package_directory(&cached_path, package_name, &mut ctx)?
+        else {
+            return Err(ResolveError::NotFound(package_name.to_string()));
+        };
+        let bin_name =
+            bin_name.unwrap_or_else(|| package_name.rsplit_once('/').map_or(package_name, |s| s.1));
+        let Some(bin) = package_json.bin(bin_name) else {
+            return Err(ResolveError::BinNotFound(bin_name.to_string(), package_json.path.clone()));
+        };
+        let path = package_path.path().normalize_with(bin);
+        if !self.cache.value(&path, &mut ctx).is_file(&self.cache.fs, &mut ctx) {
+            return Err(ResolveError::NotFound(path.to_string_lossy().to_string()));
+        }
+        Ok(path)
     }
 
     /// Wrap `resolve_impl` with `tracing` information
+    /// Resolves a request made through a public entry point.
+    ///
+    /// Metrics are recorded here rather than in [ResolverGeneric::resolve_tracing],
+    /// so a request is counted once however many [ResolveOptions::fallback_options] are tried.
+    fn resolve_request(
+        &self,
+        directory: &Path,
+        specifier: &str,
+        ctx: &mut Ctx,
+    ) -> Result<Resolution, ResolveError> {
+        #[cfg(feature = "metrics")]
+        let start = std::time::Instant::now();
+        let r = self.resolve_tracing(directory, specifier, ctx);
+        #[cfg(feature = "metrics")]
+        {
+            let result = if r.is_ok() { "ok" } else { "error" };
+            metrics::counter!("rspack_resolver.resolutions", "result" => result).increment(1);
+            metrics::histogram!("rspack_resolver.resolution_duration_seconds")
+                .record(start.elapsed().as_secs_f64());
+        }
+        r
+    }
+
     fn resolve_tracing(
         &self,
         directory: &Path,
@@ -226,6 +822,34 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
         let span = tracing::debug_span!("resolve", path = ?directory, specifier = specifier);
         let _enter = span.enter();
         let r = self.resolve_impl(directory, specifier, ctx);
+        let r = self.resolve_fallback_options(r, directory, specifier, ctx);
+        // The path the specifier points to could not be accessed, rather than being missing.
+        let r = r.map_err(|error| {
+            let io_error = ctx
+                .io_errors
+                .iter()
+                .position(|(path, _)| Self::is_specifier_target(directory, specifier, path));
+            match (error, io_error) {
+                (ResolveError::NotFound(_), Some(index)) => ctx.io_errors.swap_remove(index).1,
+                (error, _) => error,
+            }
+        });
+        let r = r.map(|mut resolution| {
+            if let Some(base) = &self.options.relative_path_base {
+                resolution.relative_path = relative_path(&base.normalize(), &resolution.path);
+            }
+            // `\` is a valid file name character outside of Windows.
+            if cfg!(windows) && self.options.forward_slashes {
+                if let Some(path) = resolution.path.to_str() {
+                    resolution.path = PathBuf::from(to_forward_slashes(path));
+                    resolution.path_hash = hash_path(&resolution.path);
+                }
+                if let Some(path) = resolution.relative_path.as_deref().and_then(Path::to_str) {
+                    resolution.relative_path = Some(PathBuf::from(to_forward_slashes(path)));
+                }
+            }
+            resolution
+        });
         match &r {
             Ok(r) => {
                 tracing::debug!(options = ?self.options, path = ?directory, specifier = specifier, ret = ?r.path);
@@ -237,6 +861,27 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
         r
     }
 
+    /// Tries the resolvers of [ResolveOptions::fallback_options] in order while `result` is an error.
+    fn resolve_fallback_options(
+        &self,
+        mut result: Result<Resolution, ResolveError>,
+        directory: &Path,
+        specifier: &str,
+        ctx: &mut Ctx,
+    ) -> Result<Resolution, ResolveError> {
+        for resolver in &self.fallback_resolvers {
+            if !result.as_ref().is_err_and(|err| !err.is_ignore()) {
+                break;
+            }
+            // Reset the state of the failed attempt, dependencies are kept.
+            ctx.query = None;
+            ctx.fragment = None;
+            ctx.resolving_alias = None;
+            result = resolver.resolve_tracing(directory, specifier, ctx);
+        }
+        result
+    }
+
     fn resolve_impl(
         &self,
         path: &Path,
@@ -244,24 +889,56 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
         ctx: &mut Ctx,
     ) -> Result<Resolution, ResolveError> {
         ctx.with_fully_specified(self.options.fully_specified);
-        let cached_path = self.cache.value(path);
-        let cached_path = self.require(&cached_path, specifier, ctx)?;
+        ctx.init_fs_operation_budget(self.options.fs_operation_budget);
+        let specifier = self.rewrite_specifier(specifier);
+        ctx.issuer_in_node_modules = self.options.alias_skip_node_modules
+            && path.components().any(|c| c.as_os_str() == "node_modules");
+        let cached_path = self.cache.value(&self.physical_path(path), ctx);
+        let cached_path = self.require(&cached_path, &specifier, ctx)?;
+        if let Some(excluded_candidates) = &mut ctx.excluded_candidates {
+            excluded_candidates.insert(cached_path.to_path_buf());
+        }
         let path = self.load_realpath(&cached_path)?;
         // enhanced-resolve: restrictions
-        self.check_restrictions(&path)?;
+        self.check_restrictions(&path, ctx)?;
+        self.check_allowed_roots(&cached_path, &path)?;
         let package_json = cached_path.find_package_json(&self.cache.fs, &self.options, ctx)?;
         if let Some(package_json) = &package_json {
-            // path must be inside the package.
-            debug_assert!(path.starts_with(package_json.directory()));
+            // path must be inside the package, unless the symlinked path is kept for an escaping symlink.
+            debug_assert!(
+                path.starts_with(package_json.directory())
+                    || self.options.symlink_escape.as_ref().is_some_and(|symlink_escape| {
+                        symlink_escape.policy == SymlinkEscapePolicy::Original
+                    })
+            );
         }
+        // Reuse the hash of the cache key when the path is not changed by symlinks.
+        let path_hash =
+            if path == cached_path.path() { cached_path.path_hash() } else { hash_path(&path) };
         Ok(Resolution {
             path,
+            path_hash,
             query: ctx.query.take(),
             fragment: ctx.fragment.take(),
             package_json,
+            relative_path: None,
         })
     }
 
+    /// Applies [ResolveOptions::specifier_rewrites] in order.
+    fn rewrite_specifier<'s>(&self, specifier: &'s str) -> Cow<'s, str> {
+        let mut rewritten = Cow::Borrowed(specifier);
+        for rewrite in &self.options.specifier_rewrites {
+            if let Some(specifier) = rewrite.rewrite(&rewritten) {
+                rewritten = Cow::Owned(specifier);
+            }
+        }
+        if let Cow::Owned(rewritten) = &rewritten {
+            tracing::debug!(specifier = specifier, rewritten = rewritten, "rewrite specifier");
+        }
+        rewritten
+    }
+
     /// require(X) from module at path Y
     ///
     /// X: specifier
@@ -275,6 +952,7 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
         ctx: &mut Ctx,
     ) -> Result<CachedPath, ResolveError> {
         ctx.test_for_infinite_recursion()?;
+        ctx.test_for_limits()?;
 
         // enhanced-resolve: parse
         let (parsed, try_fragment_as_path) = self.load_parse(cached_path, specifier, ctx)?;
@@ -291,13 +969,32 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
         specifier: &str,
         ctx: &mut Ctx,
     ) -> Result<CachedPath, ResolveError> {
+        let tsconfig_paths_first =
+            self.options.tsconfig_paths_precedence == TsconfigPathsPrecedence::First;
+
         // tsconfig-paths
-        if let Some(path) = self.load_tsconfig_paths(cached_path, specifier, &mut Ctx::default())? {
+        if tsconfig_paths_first {
+            if let Some(path) = self.load_tsconfig_paths(cached_path, specifier, ctx)? {
+                return Ok(path);
+            }
+        }
+
+        // Parcel: try the "alias" field of the root package.json
+        if let Some(path) = self.load_root_alias(cached_path, specifier, ctx)? {
             return Ok(path);
         }
 
         // enhanced-resolve: try alias
-        if let Some(path) = self.load_alias(cached_path, specifier, &self.options.alias, ctx)? {
+        if !ctx.issuer_in_node_modules {
+            if let Some(path) = self.load_alias(cached_path, specifier, &self.options.alias, ctx)? {
+                return Ok(path);
+            }
+            if let Some(path) = self.load_regex_alias(cached_path, specifier, ctx)? {
+                return Ok(path);
+            }
+        }
+
+        if let Some(path) = self.load_root_prefix(cached_path, specifier, ctx)? {
             return Ok(path);
         }
 
@@ -331,6 +1028,17 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
             if err.is_ignore() {
                 return Err(err);
             }
+            // tsconfig-paths as a fallback
+            if !tsconfig_paths_first {
+                if let Some(path) = self.load_tsconfig_paths(cached_path, specifier, ctx)? {
+                    return Ok(path);
+                }
+            }
+            if self.options.fallback_bare_only
+                && (specifier.starts_with(['.', '/', '#']) || Path::new(specifier).is_absolute())
+            {
+                return Err(err);
+            }
             // enhanced-resolve: try fallback
             self.load_alias(cached_path, specifier, &self.options.fallback, ctx)
                 .and_then(|value| value.ok_or(err))
@@ -341,15 +1049,12 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
     // 3. If packageSpecifier is a Node.js builtin module name, then
     //   1. Return the string "node:" concatenated with packageSpecifier.
     fn require_core(&self, specifier: &str) -> Result<(), ResolveError> {
-        if self.options.builtin_modules {
-            let starts_with_node = specifier.starts_with("node:");
-            if starts_with_node || NODEJS_BUILTINS.binary_search(&specifier).is_ok() {
-                let mut specifier = specifier.to_string();
-                if !starts_with_node {
-                    specifier = format!("node:{specifier}");
-                }
-                return Err(ResolveError::Builtin(specifier));
+        if self.is_builtin(specifier) {
+            let mut specifier = specifier.to_string();
+            if !specifier.starts_with("node:") {
+                specifier = format!("node:{specifier}");
             }
+            return Err(ResolveError::Builtin(specifier));
         }
         Ok(())
     }
@@ -375,13 +1080,53 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
         }
         // 2. If X begins with '/'
         //   a. set Y to be the file system root
-        let path = self.cache.value(Path::new(specifier));
+        let path = self.cache.value(&self.physical_path(Path::new(specifier)), ctx);
         if let Some(path) = self.load_as_file_or_directory(&path, specifier, ctx)? {
             return Ok(path);
         }
         Err(ResolveError::NotFound(specifier.to_string()))
     }
 
+    /// Resolves `~/foo` and `/foo` against the root of [ResolveOptions::root_prefixes].
+    ///
+    /// `/foo` falls back to [ResolveOptions::roots] and the file system root when it is not found.
+    fn load_root_prefix(
+        &self,
+        cached_path: &CachedPath,
+        specifier: &str,
+        ctx: &mut Ctx,
+    ) -> ResolveResult {
+        let Some(root_prefixes) = &self.options.root_prefixes else {
+            return Ok(None);
+        };
+        let (rest, is_tilde) = match specifier.strip_prefix("~/") {
+            Some(rest) if root_prefixes.tilde => (rest, true),
+            _ => match specifier.strip_prefix('/') {
+                Some(rest) if root_prefixes.slash => (rest, false),
+                _ => return Ok(None),
+            },
+        };
+        let root = if let Some(root) = &root_prefixes.root {
+            root.clone()
+        } else if let Some(package_json) =
+            cached_path.find_package_json(&self.cache.fs, &self.options, ctx)?
+        {
+            package_json.directory().to_path_buf()
+        } else if is_tilde {
+            return Err(ResolveError::NotFound(specifier.to_string()));
+        } else {
+            return Ok(None);
+        };
+        let cached_path = self.cache.value(&root.normalize_with(rest), ctx);
+        if let Some(path) = self.load_as_file_or_directory(&cached_path, rest, ctx)? {
+            return Ok(Some(path));
+        }
+        if is_tilde {
+            return Err(ResolveError::NotFound(specifier.to_string()));
+        }
+        Ok(None)
+    }
+
     // 3. If X begins with './' or '/' or '../'
     fn require_relative(
         &self,
@@ -395,7 +1140,7 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
             Component::CurDir | Component::ParentDir | Component::Normal(_)
         )));
         let path = cached_path.path().normalize_with(specifier);
-        let cached_path = self.cache.value(&path);
+        let cached_path = self.cache.value(&path, ctx);
         // a. LOAD_AS_FILE(Y + X)
         // b. LOAD_AS_DIRECTORY(Y + X)
         if let Some(path) = self.load_as_file_or_directory(&cached_path, specifier, ctx)? {
@@ -435,12 +1180,50 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
                 return Ok(path);
             }
         }
+        if let Some(strict_dependencies) = &self.options.strict_dependencies {
+            self.check_declared_dependency(cached_path, specifier, strict_dependencies, ctx)?;
+        }
+        // Vite: resolve dedupe packages from the dedupe root
+        if let Some(dedupe_root) = &self.options.dedupe_root {
+            let (package_name, _) = Self::parse_package_specifier(specifier);
+            if self.options.dedupe.iter().any(|name| name == package_name) {
+                let cached_path = self.cache.value(dedupe_root, ctx);
+                return self.load_package_self_or_node_modules(&cached_path, specifier, ctx);
+            }
+        }
         self.load_package_self_or_node_modules(cached_path, specifier, ctx)
     }
 
+    /// Fails with [ResolveError::UndeclaredDependency] when the nearest package.json of the importer
+    /// does not declare the package of `specifier`.
+    fn check_declared_dependency(
+        &self,
+        cached_path: &CachedPath,
+        specifier: &str,
+        strict_dependencies: &StrictDependencies,
+        ctx: &mut Ctx,
+    ) -> Result<(), ResolveError> {
+        let (package_name, _) = Self::parse_package_specifier(specifier);
+        if is_nodejs_builtin(package_name) {
+            return Ok(());
+        }
+        let Some(package_json) =
+            cached_path.find_package_json(&self.cache.fs, &self.options, ctx)?
+        else {
+            return Ok(());
+        };
+        if package_json.name.as_deref() == Some(package_name)
+            || package_json.declares_dependency(strict_dependencies.fields(), package_name)
+        {
+            return Ok(());
+        }
+        Err(ResolveError::UndeclaredDependency(package_name.to_string(), package_json.path.clone()))
+    }
+
     /// enhanced-resolve: ParsePlugin.
     ///
-    /// It's allowed to escape # as \0# to avoid parsing it as fragment.
+    /// It's allowed to escape # as \0# to avoid parsing it as fragment, and ? as \0? to avoid parsing it as query,
+    /// see [escape_specifier].
     /// enhanced-resolve will try to resolve requests containing `#` as path and as fragment,
     /// so it will automatically figure out if `./some#thing` means `.../some.js#thing` or `.../some#thing.js`.
     /// When a # is resolved as path it will be escaped in the result. Here: `.../some\0#thing.js`.
@@ -456,7 +1239,7 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
         ctx.with_query_fragment(parsed.query, parsed.fragment);
 
         // There is an edge-case where a request with # can be a path or a fragment -> try both
-        if ctx.fragment.is_some() && ctx.query.is_none() {
+        if self.options.fragment_as_path && ctx.fragment.is_some() && ctx.query.is_none() {
             let specifier = parsed.path();
             let fragment = ctx.fragment.take().unwrap();
             let path = format!("{specifier}{fragment}");
@@ -508,7 +1291,9 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
         // 4. let MATCH = PACKAGE_IMPORTS_RESOLVE(X, pathToFileURL(SCOPE), ["node", "require"]) defined in the ESM resolver.
         if let Some(path) = self.package_imports_resolve(specifier, &package_json, ctx)? {
             // 5. RESOLVE_ESM_MATCH(MATCH).
-            return self.resolve_esm_match(specifier, &path, ctx);
+            let resolved = self.resolve_esm_match(specifier, &path, ctx)?;
+            ctx.add_step(GraphEdgeKind::Imports, GraphNode::File(path.to_path_buf()));
+            return Ok(resolved);
         }
         Ok(None)
     }
@@ -530,12 +1315,46 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
         if let Some(path) = self.load_extensions(cached_path, &self.options.extensions, ctx)? {
             return Ok(Some(path));
         }
+        self.load_native_addon(cached_path, ctx)
+    }
+
+    /// Platform specific builds of a `.node` addon, see [ResolveOptions::native_addon_suffixes].
+    fn load_native_addon(&self, cached_path: &CachedPath, ctx: &mut Ctx) -> ResolveResult {
+        if self.options.native_addon_suffixes.is_empty() {
+            return Ok(None);
+        }
+        let path = cached_path.path();
+        let (Some(parent), Some(file_name), Some(file_stem)) =
+            (path.parent(), path.file_name(), path.file_stem())
+        else {
+            return Ok(None);
+        };
+        if path.extension() != Some(OsStr::new("node")) {
+            return Ok(None);
+        }
+        for suffix in &self.options.native_addon_suffixes {
+            // `foo.linux-x64-gnu.node`
+            let mut suffixed_file_name = file_stem.to_os_string();
+            suffixed_file_name.push(".");
+            suffixed_file_name.push(suffix);
+            suffixed_file_name.push(".node");
+            let cached_path = self.cache.value(&parent.join(suffixed_file_name), ctx);
+            if let Some(path) = self.load_alias_or_file(&cached_path, ctx)? {
+                return Ok(Some(path));
+            }
+            // `prebuilds/linux-x64/foo.node`
+            let cached_path =
+                self.cache.value(&parent.join("prebuilds").join(suffix).join(file_name), ctx);
+            if let Some(path) = self.load_alias_or_file(&cached_path, ctx)? {
+                return Ok(Some(path));
+            }
+        }
         Ok(None)
     }
 
     fn load_as_directory(&self, cached_path: &CachedPath, ctx: &mut Ctx) -> ResolveResult {
-        // TODO: Only package.json is supported, so warn about having other values
-        // Checking for empty files is needed for omitting checks on package.json
+        // The first of `description_files` found is read as package.json,
+        // checking for empty files is needed for omitting checks on package.json
         // 1. If X/package.json is a file,
         if !self.options.description_files.is_empty() {
             // a. Parse X/package.json, and look for "main" field.
@@ -547,7 +1366,7 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
                     // c. let M = X + (json main field)
                     let main_field_path = cached_path.path().normalize_with(main_field);
                     // d. LOAD_AS_FILE(M)
-                    let cached_path = self.cache.value(&main_field_path);
+                    let cached_path = self.cache.value(&main_field_path, ctx);
                     if let Ok(Some(path)) = self.load_as_file(&cached_path, ctx) {
                         return Ok(Some(path));
                     }
@@ -573,10 +1392,24 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
         if self.options.resolve_to_context {
             return Ok(cached_path.is_dir(&self.cache.fs, ctx).then(|| cached_path.clone()));
         }
-        if !specifier.ends_with('/') {
-            if let Some(path) = self.load_as_file(cached_path, ctx)? {
-                return Ok(Some(path));
+        if specifier.ends_with('/') {
+            match self.options.trailing_slash {
+                TrailingSlash::Index => {}
+                TrailingSlash::Context => {
+                    return Ok(cached_path
+                        .is_dir(&self.cache.fs, ctx)
+                        .then(|| cached_path.clone()));
+                }
+                TrailingSlash::Strict => {
+                    if cached_path.exists(&self.cache.fs)
+                        && !cached_path.is_dir(&self.cache.fs, ctx)
+                    {
+                        return Err(ResolveError::NotADirectory(cached_path.to_path_buf()));
+                    }
+                }
             }
+        } else if let Some(path) = self.load_as_file(cached_path, ctx)? {
+            return Ok(Some(path));
         }
         if cached_path.is_dir(&self.cache.fs, ctx) {
             if let Some(path) = self.load_as_directory(cached_path, ctx)? {
@@ -596,12 +1429,77 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
             return Ok(None);
         }
         let path = path.path().as_os_str();
+        let ordered = if self.options.adaptive_extensions {
+            self.cache.order_extensions(extensions)
+        } else {
+            None
+        };
+        let extensions = ordered.as_deref().unwrap_or(extensions);
+        let candidates = extensions.len() * (1 + self.options.extension_infixes.len());
+        let directory = match (self.options.directory_listing_threshold, Path::new(path).parent()) {
+            (Some(threshold), Some(directory)) if candidates >= threshold => {
+                ctx.add_fs_operation(directory);
+                Some(self.cache.value(directory, ctx))
+            }
+            _ => None,
+        };
+        let entries = directory.as_ref().and_then(|directory| directory.entries(&self.cache.fs));
         for extension in extensions {
-            let mut path_with_extension = path.to_os_string();
-            path_with_extension.reserve_exact(extension.len());
-            path_with_extension.push(extension);
-            let cached_path = self.cache.value(Path::new(&path_with_extension));
-            if let Some(path) = self.load_alias_or_file(&cached_path, ctx)? {
+            ctx.test_for_limits()?;
+            if let Some(path) = self.load_extension(path, extension, entries, ctx)? {
+                if self.options.adaptive_extensions {
+                    self.cache.add_extension_hit(extension);
+                }
+                return Ok(Some(path));
+            }
+        }
+        Ok(None)
+    }
+
+    fn load_extension(
+        &self,
+        path: &OsStr,
+        extension: &str,
+        entries: Option<&FxHashSet<OsString>>,
+        ctx: &mut Ctx,
+    ) -> ResolveResult {
+        // Candidates not listed in their directory are known to be missing without a `metadata` call.
+        // Names are compared in ASCII lowercase for case-insensitive file systems,
+        // non-ASCII names are always looked up because they may be normalized differently.
+        let value = |path_with_extension: &OsStr, ctx: &mut Ctx| {
+            let cached_path = self.cache.value(Path::new(path_with_extension), ctx);
+            if let (Some(entries), Some(file_name)) = (entries, cached_path.path().file_name()) {
+                if file_name.to_str().is_some_and(str::is_ascii)
+                    && !entries.contains(&file_name.to_ascii_lowercase())
+                {
+                    cached_path.mark_missing();
+                }
+            }
+            cached_path
+        };
+        // Platform specific files, e.g. `Button.ios.js`, are tried before `Button.js`.
+        if !extension.is_empty() {
+            for infix in &self.options.extension_infixes {
+                let mut path_with_extension = path.to_os_string();
+                path_with_extension.reserve_exact(1 + infix.len() + extension.len());
+                path_with_extension.push(".");
+                path_with_extension.push(infix);
+                path_with_extension.push(extension);
+                let cached_path = value(&path_with_extension, ctx);
+                if let Some(path) = self.load_alias_or_file(&cached_path, ctx)? {
+                    return Ok(Some(path));
+                }
+            }
+        }
+        let mut path_with_extension = path.to_os_string();
+        path_with_extension.reserve_exact(extension.len());
+        path_with_extension.push(extension);
+        let cached_path = value(&path_with_extension, ctx);
+        if let Some(path) = self.load_alias_or_file(&cached_path, ctx)? {
+            return Ok(Some(path));
+        }
+        if extension == ".node" {
+            if let Some(path) = self.load_native_addon(&cached_path, ctx)? {
                 return Ok(Some(path));
             }
         }
@@ -609,14 +1507,40 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
     }
 
     fn load_realpath(&self, cached_path: &CachedPath) -> Result<PathBuf, ResolveError> {
-        if self.options.symlinks {
-            cached_path.realpath(&self.cache.fs).map_err(ResolveError::from)
-        } else {
-            Ok(cached_path.to_path_buf())
+        if !self.options.symlinks {
+            return Ok(cached_path.to_path_buf());
+        }
+        let realpath = cached_path.realpath(&self.cache.fs)?;
+        if let Some(symlink_escape) = &self.options.symlink_escape {
+            if realpath != cached_path.path()
+                && !self.is_inside_roots(&realpath, &symlink_escape.roots)
+            {
+                match symlink_escape.policy {
+                    SymlinkEscapePolicy::Canonical => {}
+                    SymlinkEscapePolicy::Original => return Ok(cached_path.to_path_buf()),
+                    SymlinkEscapePolicy::Error => {
+                        return Err(ResolveError::SymlinkEscape(
+                            cached_path.to_path_buf(),
+                            realpath,
+                        ));
+                    }
+                }
+            }
         }
+        Ok(realpath)
     }
 
-    fn check_restrictions(&self, path: &Path) -> Result<(), ResolveError> {
+    /// Whether the real `path` is inside one of `roots`, which are compared by their real paths.
+    fn is_inside_roots(&self, path: &Path, roots: &[PathBuf]) -> bool {
+        let mut ctx = Ctx::default();
+        roots.iter().any(|root| {
+            let root = self.cache.value(root, &mut ctx);
+            let root = root.realpath(&self.cache.fs).unwrap_or_else(|_| root.to_path_buf());
+            path.starts_with(root)
+        })
+    }
+
+    fn check_restrictions(&self, path: &Path, ctx: &mut Ctx) -> Result<(), ResolveError> {
         // https://github.com/webpack/enhanced-resolve/blob/a998c7d218b7a9ec2461fc4fddd1ad5dd7687485/lib/RestrictionsPlugin.js#L19-L24
         fn is_inside(path: &Path, parent: &Path) -> bool {
             if !path.starts_with(parent) {
@@ -631,6 +1555,7 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
             match restriction {
                 Restriction::Path(restricted_path) => {
                     if !is_inside(path, restricted_path) {
+                        ctx.add_attempt(path, ResolveAttemptReason::Restricted);
                         return Err(ResolveError::Restriction(
                             path.to_path_buf(),
                             restricted_path.clone(),
@@ -645,10 +1570,31 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
         Ok(())
     }
 
+    fn check_allowed_roots(
+        &self,
+        cached_path: &CachedPath,
+        path: &Path,
+    ) -> Result<(), ResolveError> {
+        if self.options.allowed_roots.is_empty() {
+            return Ok(());
+        }
+        // Always follow symlinks, so escaping through a symlink is caught when `symlinks` is disabled.
+        let realpath = if self.options.symlinks {
+            Cow::Borrowed(path)
+        } else {
+            Cow::Owned(cached_path.realpath(&self.cache.fs)?)
+        };
+        if self.is_inside_roots(&realpath, &self.options.allowed_roots) {
+            Ok(())
+        } else {
+            Err(ResolveError::OutsideAllowedRoots(realpath.into_owned()))
+        }
+    }
+
     fn load_index(&self, cached_path: &CachedPath, ctx: &mut Ctx) -> ResolveResult {
         for main_file in &self.options.main_files {
             let main_path = cached_path.path().normalize_with(main_file);
-            let cached_path = self.cache.value(&main_path);
+            let cached_path = self.cache.value(&main_path, ctx);
             if self.options.enforce_extension.is_disabled() {
                 if let Some(path) = self.load_alias_or_file(&cached_path, ctx)? {
                     return Ok(Some(path));
@@ -677,11 +1623,13 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
             }
         }
         // enhanced-resolve: try file as alias
-        let alias_specifier = cached_path.path().to_string_lossy();
-        if let Some(path) =
-            self.load_alias(cached_path, &alias_specifier, &self.options.alias, ctx)?
-        {
-            return Ok(Some(path));
+        if !ctx.issuer_in_node_modules {
+            let alias_specifier = cached_path.path().to_string_lossy();
+            if let Some(path) =
+                self.load_alias(cached_path, &alias_specifier, &self.options.alias, ctx)?
+            {
+                return Ok(Some(path));
+            }
         }
         if cached_path.is_file(&self.cache.fs, ctx) {
             return Ok(Some(cached_path.clone()));
@@ -695,6 +1643,10 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
         specifier: &str,
         ctx: &mut Ctx,
     ) -> ResolveResult {
+        if let Some(path) = self.load_workspace_package(specifier, ctx)? {
+            return Ok(Some(path));
+        }
+
         #[cfg(feature = "yarn_pnp")]
         {
             if let Some(resolved_path) = self.load_pnp(cached_path, specifier, ctx)? {
@@ -702,11 +1654,12 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
             }
         }
 
-        let (package_name, subpath) = Self::parse_package_specifier(specifier);
         // 1. let DIRS = NODE_MODULES_PATHS(START)
         // 2. for each DIR in DIRS:
+        let boundary = self.modules_boundary(cached_path, ctx);
         for module_name in &self.options.modules {
-            for cached_path in std::iter::successors(Some(cached_path), |p| p.parent()) {
+            for cached_path in Self::module_ancestors(cached_path, boundary.as_ref()) {
+                ctx.test_for_limits()?;
                 // Skip if /path/to/node_modules does not exist
                 if !cached_path.is_dir(&self.cache.fs, ctx) {
                     continue;
@@ -716,62 +1669,198 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
                 else {
                     continue;
                 };
-                // Optimize node_modules lookup by inspecting whether the package exists
-                // From LOAD_PACKAGE_EXPORTS(X, DIR)
-                // 1. Try to interpret X as a combination of NAME and SUBPATH where the name
-                //    may have a @scope/ prefix and the subpath begins with a slash (`/`).
-                if !package_name.is_empty() {
-                    let package_path = cached_path.path().normalize_with(package_name);
-                    let cached_path = self.cache.value(&package_path);
-                    // Try foo/node_modules/package_name
-                    if cached_path.is_dir(&self.cache.fs, ctx) {
-                        // a. LOAD_PACKAGE_EXPORTS(X, DIR)
-                        if let Some(path) =
-                            self.load_package_exports(specifier, subpath, &cached_path, ctx)?
-                        {
-                            return Ok(Some(path));
-                        }
-                    } else {
-                        // foo/node_modules/package_name is not a directory, so useless to check inside it
-                        if !subpath.is_empty() {
-                            continue;
-                        }
-                        // Skip if the directory lead to the scope package does not exist
-                        // i.e. `foo/node_modules/@scope` is not a directory for `foo/node_modules/@scope/package`
-                        if package_name.starts_with('@') {
-                            if let Some(path) = cached_path.parent() {
-                                if !path.is_dir(&self.cache.fs, ctx) {
-                                    continue;
-                                }
-                            }
-                        }
-                    }
+                if let Some(path) = self.load_module_directory(&cached_path, specifier, ctx)? {
+                    return Ok(Some(path));
                 }
+            }
+        }
+        for cached_path in self.provided_module_directories(cached_path, ctx) {
+            if let Some(path) = self.load_module_directory(&cached_path, specifier, ctx)? {
+                return Ok(Some(path));
+            }
+        }
+        Ok(None)
+    }
 
-                // Try as file or directory for all other cases
-                // b. LOAD_AS_FILE(DIR/X)
-                // c. LOAD_AS_DIRECTORY(DIR/X)
-                let node_module_file = cached_path.path().normalize_with(specifier);
-                let cached_path = self.cache.value(&node_module_file);
-                if let Some(path) = self.load_as_file_or_directory(&cached_path, specifier, ctx)? {
+    /// LOAD_PACKAGE_EXPORTS(X, DIR), LOAD_AS_FILE(DIR/X) and LOAD_AS_DIRECTORY(DIR/X) for a module directory DIR.
+    fn load_module_directory(
+        &self,
+        cached_path: &CachedPath,
+        specifier: &str,
+        ctx: &mut Ctx,
+    ) -> ResolveResult {
+        ctx.add_searched_directory(cached_path.path());
+        let (package_name, subpath) = Self::parse_package_specifier(specifier);
+        // Optimize node_modules lookup by inspecting whether the package exists
+        // From LOAD_PACKAGE_EXPORTS(X, DIR)
+        // 1. Try to interpret X as a combination of NAME and SUBPATH where the name
+        //    may have a @scope/ prefix and the subpath begins with a slash (`/`).
+        if !package_name.is_empty() {
+            let package_path = cached_path.path().normalize_with(package_name);
+            let cached_path = self.cache.value(&package_path, ctx);
+            // Try foo/node_modules/package_name
+            if cached_path.is_dir(&self.cache.fs, ctx) {
+                // a. LOAD_PACKAGE_EXPORTS(X, DIR)
+                if let Some(path) =
+                    self.load_package_exports(specifier, subpath, &cached_path, ctx)?
+                {
                     return Ok(Some(path));
                 }
+            } else {
+                // foo/node_modules/package_name is not a directory, so useless to check inside it
+                if !subpath.is_empty() {
+                    return Ok(None);
+                }
+                // Skip if the directory lead to the scope package does not exist
+                // i.e. `foo/node_modules/@scope` is not a directory for `foo/node_modules/@scope/package`
+                if package_name.starts_with('@') {
+                    if let Some(path) = cached_path.parent() {
+                        if !path.is_dir(&self.cache.fs, ctx) {
+                            return Ok(None);
+                        }
+                    }
+                }
             }
         }
-        Ok(None)
+
+        // Try as file or directory for all other cases
+        // b. LOAD_AS_FILE(DIR/X)
+        // c. LOAD_AS_DIRECTORY(DIR/X)
+        let node_module_file = cached_path.path().normalize_with(specifier);
+        let cached_path = self.cache.value(&node_module_file, ctx);
+        self.load_as_file_or_directory(&cached_path, specifier, ctx)
+    }
+
+    /// Existing module directories returned by [ResolveOptions::modules_provider] for `cached_path`.
+    fn provided_module_directories(
+        &self,
+        cached_path: &CachedPath,
+        ctx: &mut Ctx,
+    ) -> Vec<CachedPath> {
+        let Some(modules_provider) = &self.options.modules_provider else {
+            return vec![];
+        };
+        modules_provider
+            .module_directories(cached_path.path())
+            .into_iter()
+            .filter_map(|path| {
+                let cached_path = self.cache.value(&path, ctx);
+                cached_path.is_dir(&self.cache.fs, ctx).then_some(cached_path)
+            })
+            .collect()
+    }
+
+    fn load_workspaces(&self) -> Result<Option<Arc<Workspaces>>, ResolveError> {
+        let Some(workspace_root) = &self.options.workspace_root else {
+            return Ok(None);
+        };
+        self.cache
+            .workspaces(workspace_root, || {
+                Workspaces::new(&self.cache, &self.options, workspace_root)
+            })
+            .map(Some)
+    }
+
+    /// Resolves a specifier naming a workspace package of [ResolveOptions::workspace_root] in its directory.
+    ///
+    /// Specifiers with the `workspace:` protocol, e.g. `workspace:foo/bar`, only resolve to workspace packages.
+    fn load_workspace_package(&self, specifier: &str, ctx: &mut Ctx) -> ResolveResult {
+        let Some(workspaces) = self.load_workspaces()? else {
+            return Ok(None);
+        };
+        let (specifier, is_workspace_protocol) = specifier
+            .strip_prefix("workspace:")
+            .map_or((specifier, false), |specifier| (specifier, true));
+        let (package_name, subpath) = Self::parse_package_specifier(specifier);
+        let Some(package_path) = workspaces.get(package_name) else {
+            if is_workspace_protocol {
+                return Err(ResolveError::NotFound(format!("workspace:{specifier}")));
+            }
+            return Ok(None);
+        };
+        let cached_path = self.cache.value(package_path, ctx);
+        if let Some(path) = self.load_package_exports(specifier, subpath, &cached_path, ctx)? {
+            return Ok(Some(path));
+        }
+        let cached_path =
+            self.cache.value(&package_path.normalize_with(&*Self::dot_subpath(subpath)), ctx);
+        self.load_as_file_or_directory(&cached_path, specifier, ctx)
     }
 
     #[cfg(feature = "yarn_pnp")]
     fn find_pnp_manifest(
         &self,
         cached_path: &CachedPath,
-    ) -> Ref<'_, CachedPath, Option<pnp::Manifest>> {
-        let entry = self
-            .pnp_cache
-            .entry(cached_path.clone())
-            .or_insert_with(|| pnp::find_pnp_manifest(cached_path.path()).unwrap());
+    ) -> MappedRef<'_, CachedPath, PnpManifest, Option<pnp::Manifest>> {
+        // Only a read lock on the hot path, a changed `.pnp.cjs` is picked up by [Self::invalidate].
+        if !self.options.disable_cache {
+            if let Some(entry) = self.pnp_cache.get(cached_path) {
+                cache::trace_cache("pnp_manifest", cached_path.path(), true);
+                return entry.map(|entry| &entry.manifest);
+            }
+        }
+        cache::trace_cache("pnp_manifest", cached_path.path(), false);
+        let entry = match self.pnp_cache.entry(cached_path.clone()) {
+            // Loaded by another thread in the meantime.
+            Entry::Occupied(entry) if !self.options.disable_cache => entry.into_ref(),
+            entry => entry.insert(self.load_pnp_manifest(cached_path)),
+        };
+        entry.downgrade().map(|entry| &entry.manifest)
+    }
 
-        entry.downgrade()
+    #[cfg(feature = "yarn_pnp")]
+    fn load_pnp_manifest(&self, cached_path: &CachedPath) -> PnpManifest {
+        let manifest = pnp::find_pnp_manifest(cached_path.path()).unwrap();
+        let size = manifest
+            .as_ref()
+            .and_then(|m| self.cache.fs.metadata(&m.manifest_path).ok())
+            .and_then(|metadata| metadata.size)
+            .unwrap_or_default();
+        PnpManifest { manifest, size: usize::try_from(size).unwrap_or(usize::MAX) }
+    }
+
+    /// Maps a path inside a Yarn `__virtual__` folder or inside the cache archive of an unplugged package
+    /// to its physical location, so a directory or absolute specifier taken from a previous PnP resolution finds the real package.
+    #[cfg_attr(not(feature = "yarn_pnp"), allow(clippy::unused_self))]
+    fn physical_path<'a>(&self, path: &'a Path) -> Cow<'a, Path> {
+        #[cfg(feature = "yarn_pnp")]
+        if self.options.enable_pnp {
+            use pnp::fs::{VPath, VPathInfo};
+            if path.to_string_lossy().contains(".zip") {
+                let pnp_manifest =
+                    self.find_pnp_manifest(&self.cache.value(path, &mut Ctx::default()));
+                if let Some(unplugged_path) =
+                    pnp_manifest.as_ref().and_then(|manifest| self.unplugged_path(path, manifest))
+                {
+                    return Cow::Owned(unplugged_path);
+                }
+            }
+            if path.components().any(|c| c.as_os_str() == "__virtual__") {
+                match VPath::from(path) {
+                    Ok(VPath::Virtual(info)) => return Cow::Owned(info.physical_base_path()),
+                    Ok(VPath::Zip(info)) if info.virtual_segments.is_some() => {
+                        return Cow::Owned(info.physical_base_path().join(info.zip_path));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Cow::Borrowed(path)
+    }
+
+    /// Finds the copy of a package unplugged by Yarn for a path inside its cache archive,
+    /// `.yarn/cache/<locator>-<checksum>.zip/node_modules/<name>` is unplugged to `.yarn/unplugged/<locator>/node_modules/<name>`.
+    #[cfg(feature = "yarn_pnp")]
+    fn unplugged_path(&self, path: &Path, manifest: &pnp::Manifest) -> Option<PathBuf> {
+        use pnp::fs::{VPath, VPathInfo};
+        let VPath::Zip(info) = VPath::from(path).ok()? else {
+            return None;
+        };
+        let archive = info.physical_base_path();
+        let locator = archive.file_stem()?.to_str()?.rsplit_once('-')?.0;
+        let unplugged_path =
+            manifest.manifest_dir.join(".yarn/unplugged").join(locator).join(&info.zip_path);
+        self.cache.fs.metadata(&unplugged_path).is_ok().then_some(unplugged_path)
     }
 
     #[cfg(feature = "yarn_pnp")]
@@ -793,7 +1882,9 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
 
             match resolution {
                 Ok(pnp::Resolution::Resolved(path, mut subpath)) => {
-                    let cached_path = self.cache.value(&path);
+                    // A stale manifest may still point into the archive of an unplugged package.
+                    let path = self.unplugged_path(&path, pnp_manifest).unwrap_or(path);
+                    let cached_path = self.cache.value(&path, ctx);
 
                     if let Some(subpath) = subpath.as_mut() {
                         subpath.insert(0, '/');
@@ -819,6 +1910,11 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
                 }
 
                 Ok(pnp::Resolution::Skipped) => Ok(None),
+                // Loose mode: fall back to the `node_modules` lookup.
+                Err(pnp::Error::UndeclaredDependency { message, .. }) if self.options.pnp_loose => {
+                    ctx.add_warning(|| message);
+                    Ok(None)
+                }
                 Err(_) => Err(ResolveError::NotFound(specifier.to_string())),
             }
         } else {
@@ -826,6 +1922,34 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
         }
     }
 
+    /// The directory where the `node_modules` lookup from `cached_path` stops, see [ResolveOptions::modules_boundary].
+    fn modules_boundary(&self, cached_path: &CachedPath, ctx: &mut Ctx) -> Option<CachedPath> {
+        let boundary = match self.options.modules_boundary.as_ref()? {
+            ModulesBoundary::Path(path) => path,
+            ModulesBoundary::Workspace => self.options.workspace_root.as_ref()?,
+            ModulesBoundary::Git => {
+                return std::iter::successors(Some(cached_path), |p| p.parent())
+                    .find(|p| self.cache.value(&p.path().join(".git"), ctx).exists(&self.cache.fs))
+                    .cloned();
+            }
+        };
+        cached_path.path().starts_with(boundary).then(|| self.cache.value(boundary, ctx))
+    }
+
+    /// `cached_path` and its parent directories, up to and including `boundary`.
+    fn module_ancestors<'a>(
+        cached_path: &'a CachedPath,
+        boundary: Option<&'a CachedPath>,
+    ) -> impl Iterator<Item = &'a CachedPath> {
+        std::iter::successors(Some(cached_path), move |p| {
+            if boundary == Some(*p) {
+                None
+            } else {
+                p.parent()
+            }
+        })
+    }
+
     fn get_module_directory(
         &self,
         cached_path: &CachedPath,
@@ -843,6 +1967,71 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
         }
     }
 
+    /// Finds the directory of `package_name` containing a package.json,
+    /// looking up the Yarn PnP manifest and `node_modules` from `cached_path`.
+    fn find_package_directory(
+        &self,
+        cached_path: &CachedPath,
+        package_name: &str,
+        ctx: &mut Ctx,
+    ) -> Result<Option<(CachedPath, Arc<PackageJson>)>, ResolveError> {
+        #[cfg(feature = "yarn_pnp")]
+        {
+            let pnp_manifest = self.find_pnp_manifest(cached_path);
+            if let Some(pnp_manifest) = pnp_manifest.as_ref() {
+                // `resolve_to_unqualified` requires a trailing slash
+                let mut path = cached_path.to_path_buf();
+                path.push("");
+                if let Ok(pnp::Resolution::Resolved(path, _)) =
+                    pnp::resolve_to_unqualified_via_manifest(pnp_manifest, package_name, path)
+                {
+                    let cached_path = self.cache.value(&path, ctx);
+                    if let Some(package_json) =
+                        cached_path.package_json(&self.cache.fs, &self.options, ctx)?
+                    {
+                        return Ok(Some((cached_path, package_json)));
+                    }
+                }
+            }
+        }
+
+        let boundary = self.modules_boundary(cached_path, ctx);
+        for module_name in &self.options.modules {
+            for cached_path in Self::module_ancestors(cached_path, boundary.as_ref()) {
+                let Some(cached_path) = self.get_module_directory(cached_path, module_name, ctx)
+                else {
+                    continue;
+                };
+                if let Some(package) = self.find_package_in(&cached_path, package_name, ctx)? {
+                    return Ok(Some(package));
+                }
+            }
+        }
+        for cached_path in self.provided_module_directories(cached_path, ctx) {
+            if let Some(package) = self.find_package_in(&cached_path, package_name, ctx)? {
+                return Ok(Some(package));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Finds the directory of `package_name` containing a package.json in the module directory `cached_path`.
+    fn find_package_in(
+        &self,
+        cached_path: &CachedPath,
+        package_name: &str,
+        ctx: &mut Ctx,
+    ) -> Result<Option<(CachedPath, Arc<PackageJson>)>, ResolveError> {
+        let package_path = cached_path.path().normalize_with(package_name);
+        let cached_path = self.cache.value(&package_path, ctx);
+        if !cached_path.is_dir(&self.cache.fs, ctx) {
+            return Ok(None);
+        }
+        Ok(cached_path
+            .package_json(&self.cache.fs, &self.options, ctx)?
+            .map(|package_json| (cached_path, package_json)))
+    }
+
     fn load_package_exports(
         &self,
         specifier: &str,
@@ -861,15 +2050,15 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
         // 5. let MATCH = PACKAGE_EXPORTS_RESOLVE(pathToFileURL(DIR/NAME), "." + SUBPATH,
         //    `package.json` "exports", ["node", "require"]) defined in the ESM resolver.
         // Note: The subpath is not prepended with a dot on purpose
+        let subpath = Self::dot_subpath(subpath);
         for exports in package_json.exports_fields(&self.options.exports_fields) {
-            if let Some(path) = self.package_exports_resolve(
-                cached_path.path(),
-                &format!(".{subpath}"),
-                exports,
-                ctx,
-            )? {
+            if let Some(path) =
+                self.package_exports_resolve(cached_path.path(), &subpath, &exports, ctx)?
+            {
                 // 6. RESOLVE_ESM_MATCH(MATCH)
-                return self.resolve_esm_match(specifier, &path, ctx);
+                let resolved = self.resolve_esm_match(specifier, &path, ctx)?;
+                ctx.add_step(GraphEdgeKind::Exports, GraphNode::File(path.to_path_buf()));
+                return Ok(resolved);
             };
         }
         Ok(None)
@@ -901,14 +2090,39 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
             let package_url = package_json.directory();
             // Note: The subpath is not prepended with a dot on purpose
             // because `package_exports_resolve` matches subpath without the leading dot.
+            let dot_subpath = Self::dot_subpath(subpath);
+            let mut has_exports = false;
             for exports in package_json.exports_fields(&self.options.exports_fields) {
+                has_exports = true;
                 if let Some(cached_path) =
-                    self.package_exports_resolve(package_url, &format!(".{subpath}"), exports, ctx)?
+                    self.package_exports_resolve(package_url, &dot_subpath, &exports, ctx)?
                 {
                     // 6. RESOLVE_ESM_MATCH(MATCH)
-                    return self.resolve_esm_match(specifier, &cached_path, ctx);
+                    let resolved = self.resolve_esm_match(specifier, &cached_path, ctx)?;
+                    ctx.add_step(
+                        GraphEdgeKind::Exports,
+                        GraphNode::File(cached_path.to_path_buf()),
+                    );
+                    return Ok(resolved);
                 }
             }
+            if let Some(path) =
+                self.load_browser_field(cached_path, Some(specifier), &package_json, ctx)?
+            {
+                return Ok(Some(path));
+            }
+            // Node.js: `require("<own-name>/sub")` of a package without "exports" resolves from the package root.
+            if !has_exports {
+                let cached_path = self.cache.value(&package_url.normalize_with(&*dot_subpath), ctx);
+                if subpath.is_empty() {
+                    if cached_path.is_dir(&self.cache.fs, ctx) {
+                        return self.load_as_directory(&cached_path, ctx);
+                    }
+                    return Ok(None);
+                }
+                return self.load_as_file_or_directory(&cached_path, specifier, ctx);
+            }
+            return Ok(None);
         }
         self.load_browser_field(cached_path, Some(specifier), &package_json, ctx)
     }
@@ -940,14 +2154,22 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
         ctx: &mut Ctx,
     ) -> ResolveResult {
         let path = cached_path.path();
-        let Some(new_specifier) = package_json.resolve_browser_field(
+        let new_specifier = match package_json.resolve_browser_field(
             path,
             module_specifier,
-            &self.options.alias_fields,
-        )?
-        else {
-            return Ok(None);
+            &self.options.alias_fields,
+            &self.options.alias_field_overrides,
+        ) {
+            Ok(Some(new_specifier)) => new_specifier,
+            Ok(None) => return Ok(None),
+            Err(error) => {
+                if let ResolveError::Ignored(path) = &error {
+                    ctx.add_attempt(path, ResolveAttemptReason::Ignored);
+                }
+                return Err(error);
+            }
         };
+        let new_specifier = new_specifier.as_ref();
         // Abort when resolving recursive module
         if module_specifier.is_some_and(|s| s == new_specifier) {
             return Ok(None);
@@ -965,7 +2187,7 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
         }
         ctx.with_resolving_alias(new_specifier.to_string());
         ctx.with_fully_specified(false);
-        let cached_path = self.cache.value(package_json.directory());
+        let cached_path = self.cache.value(package_json.directory(), ctx);
         self.require(&cached_path, new_specifier, ctx).map(Some)
     }
 
@@ -1010,6 +2232,7 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
                     }
                     AliasValue::Ignore => {
                         let path = cached_path.path().normalize_with(alias_key);
+                        ctx.add_attempt(&path, ResolveAttemptReason::Ignored);
                         return Err(ResolveError::Ignored(path));
                     }
                 }
@@ -1024,6 +2247,67 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
         Ok(None)
     }
 
+    /// Applies [ResolveOptions::regex_alias], substituting the captures of the first matching pattern.
+    fn load_regex_alias(
+        &self,
+        cached_path: &CachedPath,
+        specifier: &str,
+        ctx: &mut Ctx,
+    ) -> ResolveResult {
+        for (regex, values) in &self.options.regex_alias {
+            let Some(captures) = regex.captures(specifier) else {
+                continue;
+            };
+            for value in values {
+                let AliasValue::Path(template) = value else {
+                    let path = cached_path.path().normalize_with(specifier);
+                    ctx.add_attempt(&path, ResolveAttemptReason::Ignored);
+                    return Err(ResolveError::Ignored(path));
+                };
+                let mut new_specifier = String::new();
+                captures.expand(template, &mut new_specifier);
+                ctx.with_fully_specified(false);
+                match self.require(cached_path, &new_specifier, ctx) {
+                    Ok(path) => {
+                        ctx.add_step(
+                            GraphEdgeKind::Alias(regex.as_str().to_string()),
+                            GraphNode::Specifier(new_specifier),
+                        );
+                        return Ok(Some(path));
+                    }
+                    Err(ResolveError::NotFound(_) | ResolveError::MatchedAliasNotFound(..)) => {}
+                    Err(err) => return Err(err),
+                }
+            }
+            return Err(ResolveError::MatchedAliasNotFound(
+                specifier.to_string(),
+                regex.as_str().to_string(),
+            ));
+        }
+        Ok(None)
+    }
+
+    /// Applies the "alias" field of the package.json in [ResolveOptions::alias_root].
+    fn load_root_alias(
+        &self,
+        cached_path: &CachedPath,
+        specifier: &str,
+        ctx: &mut Ctx,
+    ) -> ResolveResult {
+        let Some(alias_root) = &self.options.alias_root else {
+            return Ok(None);
+        };
+        let Some(package_json) =
+            self.cache.value(alias_root, ctx).package_json(&self.cache.fs, &self.options, ctx)?
+        else {
+            return Ok(None);
+        };
+        let Some(alias) = package_json.alias() else {
+            return Ok(None);
+        };
+        self.load_alias(cached_path, specifier, alias, ctx)
+    }
+
     fn load_alias_value(
         &self,
         cached_path: &CachedPath,
@@ -1043,7 +2327,7 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
             } else {
                 let alias_path = Path::new(alias_value).normalize();
                 // Must not append anything to alias_value if it is a file.
-                let alias_value_cached_path = self.cache.value(&alias_path);
+                let alias_value_cached_path = self.cache.value(&alias_path, ctx);
                 if alias_value_cached_path.is_file(&self.cache.fs, ctx) {
                     return Ok(None);
                 }
@@ -1064,7 +2348,13 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
                 Err(ResolveError::NotFound(_) | ResolveError::MatchedAliasNotFound(_, _)) => {
                     Ok(None)
                 }
-                Ok(path) => return Ok(Some(path)),
+                Ok(path) => {
+                    ctx.add_step(
+                        GraphEdgeKind::Alias(alias_key.to_string()),
+                        GraphNode::Specifier(new_specifier.to_string()),
+                    );
+                    return Ok(Some(path));
+                }
                 Err(err) => return Err(err),
             };
         }
@@ -1103,7 +2393,7 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
             let mut path_with_extension = path_without_extension.clone().into_os_string();
             path_with_extension.reserve_exact(extension.len());
             path_with_extension.push(extension);
-            let cached_path = self.cache.value(Path::new(&path_with_extension));
+            let cached_path = self.cache.value(Path::new(&path_with_extension), ctx);
             if let Some(path) = self.load_alias_or_file(&cached_path, ctx)? {
                 ctx.with_fully_specified(false);
                 return Ok(Some(path));
@@ -1114,6 +2404,7 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
             ctx.with_fully_specified(false);
             return Ok(None);
         }
+        ctx.add_attempt(path, ResolveAttemptReason::ExtensionAliased);
         // Create a meaningful error message.
         let dir = path.parent().unwrap().to_path_buf();
         let filename_without_extension = Path::new(filename).with_extension("");
@@ -1138,7 +2429,8 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
         }
         if let Some(specifier) = specifier.strip_prefix(SLASH_START) {
             for root in &self.options.roots {
-                let cached_path = self.cache.value(root);
+                ctx.add_searched_directory(root);
+                let cached_path = self.cache.value(root, ctx);
                 if let Ok(path) = self.require_relative(&cached_path, specifier, ctx) {
                     return Some(path);
                 }
@@ -1153,19 +2445,45 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
         specifier: &str,
         ctx: &mut Ctx,
     ) -> ResolveResult {
-        let Some(tsconfig_options) = &self.options.tsconfig else {
+        // The tsconfig of the longest directory containing the request.
+        let scoped_tsconfig_options = self
+            .options
+            .tsconfig_scopes
+            .iter()
+            .filter(|(directory, _)| cached_path.path().starts_with(directory))
+            .max_by_key(|(directory, _)| directory.components().count())
+            .map(|(_, tsconfig_options)| tsconfig_options);
+        let tsconfig = if let Some(tsconfig_options) =
+            scoped_tsconfig_options.or(self.options.tsconfig.as_ref())
+        {
+            self.load_tsconfig(
+                /* root */ true,
+                &tsconfig_options.config_file,
+                &tsconfig_options.references,
+                tsconfig_options.content.as_deref(),
+            )?
+        } else if self.options.tsconfig_discovery {
+            let Some(tsconfig_path) = self.cache.find_tsconfig(cached_path, ctx) else {
+                return Ok(None);
+            };
+            self.load_tsconfig(
+                /* root */ true,
+                &tsconfig_path,
+                &TsconfigReferences::Disabled,
+                None,
+            )?
+        } else {
             return Ok(None);
         };
-        let tsconfig = self.load_tsconfig(
-            /* root */ true,
-            &tsconfig_options.config_file,
-            &tsconfig_options.references,
-        )?;
+        for dependency in tsconfig.dependencies() {
+            ctx.add_description_dependency(dependency);
+        }
         let paths = tsconfig.resolve(cached_path.path(), specifier);
         for path in paths {
-            let cached_path = self.cache.value(&path);
-            if let Ok(path) = self.require_relative(&cached_path, ".", ctx) {
-                return Ok(Some(path));
+            let cached_path = self.cache.value(&path, ctx);
+            if let Ok(resolved) = self.require_relative(&cached_path, ".", &mut Ctx::default()) {
+                ctx.add_step(GraphEdgeKind::TsconfigPaths, GraphNode::File(path));
+                return Ok(Some(resolved));
             }
         }
         Ok(None)
@@ -1176,29 +2494,29 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
         root: bool,
         path: &Path,
         references: &TsconfigReferences,
+        content: Option<&str>,
     ) -> Result<Arc<TsConfig>, ResolveError> {
-        self.cache.tsconfig(root, path, |tsconfig| {
-            let directory = self.cache.value(tsconfig.directory());
+        let provider = self.options.tsconfig_provider.as_deref();
+        self.cache.tsconfig(root, path, content, provider, |tsconfig| {
+            let directory = self.cache.value(tsconfig.directory(), &mut Ctx::default());
             tracing::trace!(tsconfig = ?tsconfig, "load_tsconfig");
 
             // Extend tsconfig
-            if let Some(extends) = &tsconfig.extends {
-                let extended_tsconfig_paths = match extends {
-                    ExtendsField::Single(s) => {
-                        vec![self.get_extended_tsconfig_path(&directory, tsconfig, s)?]
-                    }
-                    ExtendsField::Multiple(specifiers) => specifiers
+            // A single "extends" is by far the most common case, resolve it without collecting.
+            match &tsconfig.extends {
+                None => {}
+                Some(ExtendsField::Single(specifier)) => {
+                    let path = self.get_extended_tsconfig_path(&directory, tsconfig, specifier)?;
+                    self.extend_tsconfig(tsconfig, &path)?;
+                }
+                Some(ExtendsField::Multiple(specifiers)) => {
+                    let extended_tsconfig_paths = specifiers
                         .iter()
                         .map(|s| self.get_extended_tsconfig_path(&directory, tsconfig, s))
-                        .collect::<Result<Vec<PathBuf>, ResolveError>>()?,
-                };
-                for extended_tsconfig_path in extended_tsconfig_paths {
-                    let extended_tsconfig = self.load_tsconfig(
-                        /* root */ false,
-                        &extended_tsconfig_path,
-                        &TsconfigReferences::Disabled,
-                    )?;
-                    tsconfig.extend_tsconfig(&extended_tsconfig);
+                        .collect::<Result<Vec<PathBuf>, ResolveError>>()?;
+                    for extended_tsconfig_path in extended_tsconfig_paths {
+                        self.extend_tsconfig(tsconfig, &extended_tsconfig_path)?;
+                    }
                 }
             }
 
@@ -1222,6 +2540,8 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
                     let tsconfig = self.cache.tsconfig(
                         /* root */ true,
                         &reference_tsconfig_path,
+                        None,
+                        provider,
                         |reference_tsconfig| {
                             if reference_tsconfig.path == tsconfig.path {
                                 return Err(ResolveError::TsconfigSelfReference(
@@ -1238,6 +2558,21 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
         })
     }
 
+    fn extend_tsconfig(
+        &self,
+        tsconfig: &mut TsConfig,
+        extended_tsconfig_path: &Path,
+    ) -> Result<(), ResolveError> {
+        let extended_tsconfig = self.load_tsconfig(
+            /* root */ false,
+            extended_tsconfig_path,
+            &TsconfigReferences::Disabled,
+            None,
+        )?;
+        tsconfig.extend_tsconfig(&extended_tsconfig);
+        Ok(())
+    }
+
     fn get_extended_tsconfig_path(
         &self,
         directory: &CachedPath,
@@ -1280,81 +2615,93 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
         self.require_core(package_name)?;
 
         // 11. While parentURL is not the file system root,
+        let boundary = self.modules_boundary(cached_path, ctx);
         for module_name in &self.options.modules {
-            for cached_path in std::iter::successors(Some(cached_path), |p| p.parent()) {
+            for cached_path in Self::module_ancestors(cached_path, boundary.as_ref()) {
                 // 1. Let packageURL be the URL resolution of "node_modules/" concatenated with packageSpecifier, relative to parentURL.
                 let Some(cached_path) = self.get_module_directory(cached_path, module_name, ctx)
                 else {
                     continue;
                 };
-                // 2. Set parentURL to the parent folder URL of parentURL.
-                let package_path = cached_path.path().normalize_with(package_name);
-                let cached_path = self.cache.value(&package_path);
-                // 3. If the folder at packageURL does not exist, then
-                //   1. Continue the next loop iteration.
-                if cached_path.is_dir(&self.cache.fs, ctx) {
-                    // 4. Let pjson be the result of READ_PACKAGE_JSON(packageURL).
-                    if let Some(package_json) =
-                        cached_path.package_json(&self.cache.fs, &self.options, ctx)?
-                    {
-                        // 5. If pjson is not null and pjson.exports is not null or undefined, then
-                        // 1. Return the result of PACKAGE_EXPORTS_RESOLVE(packageURL, packageSubpath, pjson.exports, defaultConditions).
-                        for exports in package_json.exports_fields(&self.options.exports_fields) {
-                            if let Some(path) = self.package_exports_resolve(
-                                cached_path.path(),
-                                &format!(".{subpath}"),
-                                exports,
-                                ctx,
-                            )? {
-                                return Ok(Some(path));
-                            }
-                        }
-                        // 6. Otherwise, if packageSubpath is equal to ".", then
-                        if subpath == "." {
-                            // 1. If pjson.main is a string, then
-                            for main_field in package_json.main_fields(&self.options.main_fields) {
-                                // 1. Return the URL resolution of main in packageURL.
-                                let path = cached_path.path().normalize_with(main_field);
-                                let cached_path = self.cache.value(&path);
-                                if cached_path.is_file(&self.cache.fs, ctx) {
-                                    return Ok(Some(cached_path));
-                                }
-                            }
-                        }
-                    }
-                    let subpath = format!(".{subpath}");
-                    ctx.with_fully_specified(false);
-                    return self.require(&cached_path, &subpath, ctx).map(Some);
+                if let Some(path) =
+                    self.package_resolve_in(&cached_path, package_name, subpath, ctx)?
+                {
+                    return Ok(Some(path));
                 }
             }
         }
+        for cached_path in self.provided_module_directories(cached_path, ctx) {
+            if let Some(path) = self.package_resolve_in(&cached_path, package_name, subpath, ctx)? {
+                return Ok(Some(path));
+            }
+        }
 
         Err(ResolveError::NotFound(specifier.to_string()))
     }
 
+    /// PACKAGE_RESOLVE for the module directory `cached_path`, `None` when the package folder does not exist.
+    fn package_resolve_in(
+        &self,
+        cached_path: &CachedPath,
+        package_name: &str,
+        subpath: &str,
+        ctx: &mut Ctx,
+    ) -> ResolveResult {
+        // 2. Set parentURL to the parent folder URL of parentURL.
+        let package_path = cached_path.path().normalize_with(package_name);
+        let cached_path = self.cache.value(&package_path, ctx);
+        // 3. If the folder at packageURL does not exist, then
+        //   1. Continue the next loop iteration.
+        if !cached_path.is_dir(&self.cache.fs, ctx) {
+            return Ok(None);
+        }
+        // 4. Let pjson be the result of READ_PACKAGE_JSON(packageURL).
+        if let Some(package_json) = cached_path.package_json(&self.cache.fs, &self.options, ctx)? {
+            // 5. If pjson is not null and pjson.exports is not null or undefined, then
+            // 1. Return the result of PACKAGE_EXPORTS_RESOLVE(packageURL, packageSubpath, pjson.exports, defaultConditions).
+            let exports_subpath = Self::dot_subpath(subpath);
+            for exports in package_json.exports_fields(&self.options.exports_fields) {
+                if let Some(path) = self.package_exports_resolve(
+                    cached_path.path(),
+                    &exports_subpath,
+                    &exports,
+                    ctx,
+                )? {
+                    return Ok(Some(path));
+                }
+            }
+            // 6. Otherwise, if packageSubpath is equal to ".", then
+            if subpath == "." {
+                // 1. If pjson.main is a string, then
+                for main_field in package_json.main_fields(&self.options.main_fields) {
+                    // 1. Return the URL resolution of main in packageURL.
+                    let path = cached_path.path().normalize_with(main_field);
+                    let cached_path = self.cache.value(&path, ctx);
+                    if cached_path.is_file(&self.cache.fs, ctx) {
+                        return Ok(Some(cached_path));
+                    }
+                }
+            }
+        }
+        let subpath = Self::dot_subpath(subpath);
+        ctx.with_fully_specified(false);
+        self.require(&cached_path, &subpath, ctx).map(Some)
+    }
+
     /// PACKAGE_EXPORTS_RESOLVE(packageURL, subpath, exports, conditions)
     fn package_exports_resolve(
         &self,
         package_url: &Path,
         subpath: &str,
-        exports: &JSONValue,
+        exports: &ExportsField,
         ctx: &mut Ctx,
     ) -> ResolveResult {
-        let conditions = &self.options.condition_names;
+        let condition_names = ctx.condition_names.clone();
+        let conditions = condition_names.as_deref().unwrap_or(&self.options.condition_names);
         // 1. If exports is an Object with both a key starting with "." and a key not starting with ".", throw an Invalid Package Configuration error.
-        if let JSONValue::Object(map) = exports {
-            let mut has_dot = false;
-            let mut without_dot = false;
-            for key in map.keys() {
-                let starts_with_dot_or_hash = key.starts_with(['.', '#']);
-                has_dot = has_dot || starts_with_dot_or_hash;
-                without_dot = without_dot || !starts_with_dot_or_hash;
-                if has_dot && without_dot {
-                    return Err(ResolveError::InvalidPackageConfig(
-                        package_url.join("package.json"),
-                    ));
-                }
-            }
+        // Note: this is validated when the exports field is parsed.
+        if matches!(exports, ExportsField::Invalid) {
+            return Err(ResolveError::InvalidPackageConfig(package_url.join("package.json")));
         }
         // 2. If subpath is equal to ".", then
         // Note: subpath is not prepended with a dot when passed in.
@@ -1371,48 +2718,60 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
                 ));
             }
             // 1. Let mainExport be undefined.
-            let main_export = match exports {
-                // 2. If exports is a String or Array, or an Object containing no keys starting with ".", then
-                JSONValue::String(_) | JSONValue::Array(_) => {
-                    // 1. Set mainExport to exports.
-                    Some(exports)
-                }
-                // 3. Otherwise if exports is an Object containing a "." property, then
-                JSONValue::Object(map) => {
-                    // 1. Set mainExport to exports["."].
-                    map.get(".").map_or_else(
-                        || {
-                            if map.keys().any(|key| key.starts_with("./") || key.starts_with('#')) {
-                                None
-                            } else {
-                                Some(exports)
-                            }
-                        },
-                        Some,
-                    )
-                }
-                _ => None,
-            };
+            // 2. If exports is a String or Array, or an Object containing no keys starting with ".", then
+            //   1. Set mainExport to exports.
+            // 3. Otherwise if exports is an Object containing a "." property, then
+            //   1. Set mainExport to exports["."].
             // 4. If mainExport is not undefined, then
-            if let Some(main_export) = main_export {
-                // 1. Let resolved be the result of PACKAGE_TARGET_RESOLVE( packageURL, mainExport, null, false, conditions).
-                let resolved = self.package_target_resolve(
+            //   1. Let resolved be the result of PACKAGE_TARGET_RESOLVE( packageURL, mainExport, null, false, conditions).
+            let resolved = match exports {
+                ExportsField::String(target) => self.package_target_resolve_string(
+                    package_url,
+                    ".",
+                    target,
+                    None,
+                    /* is_imports */ false,
+                    ctx,
+                )?,
+                ExportsField::Array(targets) => self.package_target_resolve_array(
                     package_url,
                     ".",
-                    main_export,
+                    targets,
                     None,
                     /* is_imports */ false,
                     conditions,
                     ctx,
-                )?;
-                // 2. If resolved is not null or undefined, return resolved.
-                if let Some(path) = resolved {
-                    return Ok(Some(path));
-                }
+                )?,
+                ExportsField::Conditions(target) => self.package_target_resolve_conditions(
+                    package_url,
+                    ".",
+                    target,
+                    None,
+                    /* is_imports */ false,
+                    conditions,
+                    ctx,
+                )?,
+                ExportsField::Map(map) => match map.get(".") {
+                    Some(main_export) => self.package_target_resolve(
+                        package_url,
+                        ".",
+                        main_export,
+                        None,
+                        /* is_imports */ false,
+                        conditions,
+                        ctx,
+                    )?,
+                    None => None,
+                },
+                ExportsField::Null | ExportsField::Invalid => None,
+            };
+            //   2. If resolved is not null or undefined, return resolved.
+            if let Some(path) = resolved {
+                return Ok(Some(path));
             }
         }
         // 3. Otherwise, if exports is an Object and all keys of exports start with ".", then
-        if let JSONValue::Object(exports) = exports {
+        if let ExportsField::Map(exports) = exports {
             // 1. Let matchKey be the string "./" concatenated with subpath.
             // Note: `package_imports_exports_resolve` does not require the leading dot.
             let match_key = &subpath;
@@ -1428,6 +2787,14 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
                 // 3. If resolved is not null or undefined, return resolved.
                 return Ok(Some(path));
             }
+            // Distinguish subpaths the package author blocked with `null` from subpaths that are missing.
+            if let Some((key, ImportsExportsTarget::Null, _)) = exports.find(match_key) {
+                return Err(ResolveError::PackagePathBlocked(
+                    subpath.to_string(),
+                    key.to_string(),
+                    package_url.join("package.json"),
+                ));
+            }
         }
         // 4. Throw a Package Path Not Exported error.
         Err(ResolveError::PackagePathNotExported(
@@ -1455,6 +2822,8 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
 
         // 1. Let resolved be the result of PACKAGE_IMPORTS_EXPORTS_RESOLVE( specifier, pjson.imports, packageURL, true, conditions).
         let mut has_imports = false;
+        let condition_names = ctx.condition_names.clone();
+        let conditions = condition_names.as_deref().unwrap_or(&self.options.condition_names);
         for imports in package_json.imports_fields(&self.options.imports_fields) {
             if !has_imports {
                 has_imports = true;
@@ -1468,10 +2837,10 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
             }
             if let Some(path) = self.package_imports_exports_resolve(
                 specifier,
-                imports,
+                &imports,
                 package_json.directory(),
                 /* is_imports */ true,
-                &self.options.condition_names,
+                conditions,
                 ctx,
             )? {
                 // 2. If resolved is not null or undefined, return resolved.
@@ -1494,7 +2863,7 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
     fn package_imports_exports_resolve(
         &self,
         match_key: &str,
-        match_obj: &JSONMap,
+        match_obj: &ImportsExportsMap,
         package_url: &Path,
         is_imports: bool,
         conditions: &[String],
@@ -1506,66 +2875,20 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
             return Ok(None);
         }
         // 1. If matchKey is a key of matchObj and does not contain "*", then
-        if !match_key.contains('*') {
-            // 1. Let target be the value of matchObj[matchKey].
-            if let Some(target) = match_obj.get(match_key) {
-                // 2. Return the result of PACKAGE_TARGET_RESOLVE(packageURL, target, null, isImports, conditions).
-                return self.package_target_resolve(
-                    package_url,
-                    match_key,
-                    target,
-                    None,
-                    is_imports,
-                    conditions,
-                    ctx,
-                );
-            }
-        }
-
-        let mut best_target = None;
-        let mut best_match = "";
-        let mut best_key = "";
+        //   1. Let target be the value of matchObj[matchKey].
+        //   2. Return the result of PACKAGE_TARGET_RESOLVE(packageURL, target, null, isImports, conditions).
         // 2. Let expansionKeys be the list of keys of matchObj containing only a single "*", sorted by the sorting function PATTERN_KEY_COMPARE which orders in descending order of specificity.
         // 3. For each key expansionKey in expansionKeys, do
-        for (expansion_key, target) in match_obj {
-            if expansion_key.starts_with("./") || expansion_key.starts_with('#') {
-                // 1. Let patternBase be the substring of expansionKey up to but excluding the first "*" character.
-                if let Some((pattern_base, pattern_trailer)) = expansion_key.split_once('*') {
-                    // 2. If matchKey starts with but is not equal to patternBase, then
-                    if match_key.starts_with(pattern_base)
-                        // 1. Let patternTrailer be the substring of expansionKey from the index after the first "*" character.
-                        && !pattern_trailer.contains('*')
-                        // 2. If patternTrailer has zero length, or if matchKey ends with patternTrailer and the length of matchKey is greater than or equal to the length of expansionKey, then
-                        && (pattern_trailer.is_empty()
-                            || (match_key.len() >= expansion_key.len()
-                                && match_key.ends_with(pattern_trailer)))
-                        && Self::pattern_key_compare(best_key, expansion_key).is_gt()
-                    {
-                        // 1. Let target be the value of matchObj[expansionKey].
-                        best_target = Some(target);
-                        // 2. Let patternMatch be the substring of matchKey starting at the index of the length of patternBase up to the length of matchKey minus the length of patternTrailer.
-                        best_match =
-                            &match_key[pattern_base.len()..match_key.len() - pattern_trailer.len()];
-                        best_key = expansion_key;
-                    }
-                } else if expansion_key.ends_with('/')
-                    && match_key.starts_with(expansion_key)
-                    && Self::pattern_key_compare(best_key, expansion_key).is_gt()
-                {
-                    // TODO: [DEP0148] DeprecationWarning: Use of deprecated folder mapping "./dist/" in the "exports" field module resolution of the package at xxx/package.json.
-                    best_target = Some(target);
-                    best_match = &match_key[expansion_key.len()..];
-                    best_key = expansion_key;
-                }
-            }
-        }
-        if let Some(best_target) = best_target {
-            // 3. Return the result of PACKAGE_TARGET_RESOLVE(packageURL, target, patternMatch, isImports, conditions).
+        //   1. Let target be the value of matchObj[expansionKey].
+        //   2. Let patternMatch be the substring of matchKey starting at the index of the length of patternBase up to the length of matchKey minus the length of patternTrailer.
+        //   3. Return the result of PACKAGE_TARGET_RESOLVE(packageURL, target, patternMatch, isImports, conditions).
+        // Note: the keys are sorted when the field is compiled, see [ImportsExportsMap::find].
+        if let Some((key, target, pattern_match)) = match_obj.find(match_key) {
             return self.package_target_resolve(
                 package_url,
-                best_key,
-                best_target,
-                Some(best_match),
+                key,
+                target,
+                pattern_match,
                 is_imports,
                 conditions,
                 ctx,
@@ -1581,23 +2904,74 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
         &self,
         package_url: &Path,
         target_key: &str,
-        target: &JSONValue,
+        target: &ImportsExportsTarget,
         pattern_match: Option<&str>,
         is_imports: bool,
         conditions: &[String],
         ctx: &mut Ctx,
+    ) -> ResolveResult {
+        match target {
+            // 1. If target is a String, then
+            ImportsExportsTarget::String(target) => self.package_target_resolve_string(
+                package_url,
+                target_key,
+                target,
+                pattern_match,
+                is_imports,
+                ctx,
+            ),
+            // 2. Otherwise, if target is a non-null Object, then
+            ImportsExportsTarget::Conditions(target) => self.package_target_resolve_conditions(
+                package_url,
+                target_key,
+                target,
+                pattern_match,
+                is_imports,
+                conditions,
+                ctx,
+            ),
+            // 3. Otherwise, if target is an Array, then
+            ImportsExportsTarget::Array(targets) => self.package_target_resolve_array(
+                package_url,
+                target_key,
+                targets,
+                pattern_match,
+                is_imports,
+                conditions,
+                ctx,
+            ),
+            // 4. Otherwise, if target is null, return null.
+            // 5. Otherwise throw an Invalid Package Target error.
+            ImportsExportsTarget::Null => Ok(None),
+        }
+    }
+
+    /// PACKAGE_TARGET_RESOLVE(packageURL, target, patternMatch, isImports, conditions)
+    /// where target is a String.
+    fn package_target_resolve_string(
+        &self,
+        package_url: &Path,
+        target_key: &str,
+        target: &str,
+        pattern_match: Option<&str>,
+        is_imports: bool,
+        ctx: &mut Ctx,
     ) -> ResolveResult {
         fn normalize_string_target<'a>(
             target_key: &'a str,
             target: &'a str,
             pattern_match: Option<&'a str>,
             package_url: &Path,
+            ctx: &mut Ctx,
         ) -> Result<Cow<'a, str>, ResolveError> {
             let target = if let Some(pattern_match) = pattern_match {
                 if !target_key.contains('*') && !target.contains('*') {
                     // enhanced-resolve behaviour
-                    // TODO: [DEP0148] DeprecationWarning: Use of deprecated folder mapping "./dist/" in the "exports" field module resolution of the package at xxx/package.json.
                     if target_key.ends_with('/') && target.ends_with('/') {
+                        ctx.add_warning(|| format!(
+                            r#"[DEP0148] Use of deprecated folder mapping "{target_key}" in the "exports" field module resolution of the package at {}"#,
+                            package_url.join("package.json").display()
+                        ));
                         Cow::Owned(format!("{target}{pattern_match}"))
                     } else {
                         return Err(ResolveError::InvalidPackageConfigDirectory(
@@ -1613,117 +2987,156 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
             Ok(target)
         }
 
-        match target {
-            // 1. If target is a String, then
-            JSONValue::String(target) => {
-                // 1. If target does not start with "./", then
-                if !target.starts_with("./") {
-                    // 1. If isImports is false, or if target starts with "../" or "/", or if target is a valid URL, then
-                    if !is_imports || target.starts_with("../") || target.starts_with('/') {
-                        // 1. Throw an Invalid Package Target error.
-                        return Err(ResolveError::InvalidPackageTarget(
-                            target.to_string(),
-                            target_key.to_string(),
-                            package_url.join("package.json"),
-                        ));
-                    }
-                    // 2. If patternMatch is a String, then
-                    //   1. Return PACKAGE_RESOLVE(target with every instance of "*" replaced by patternMatch, packageURL + "/").
-                    let target =
-                        normalize_string_target(target_key, target, pattern_match, package_url)?;
-                    let package_url = self.cache.value(package_url);
-                    // // 3. Return PACKAGE_RESOLVE(target, packageURL + "/").
-                    return self.package_resolve(&package_url, &target, ctx);
-                }
-
-                // 2. If target split on "/" or "\" contains any "", ".", "..", or "node_modules" segments after the first "." segment, case insensitive and including percent encoded variants, throw an Invalid Package Target error.
-                // 3. Let resolvedTarget be the URL resolution of the concatenation of packageURL and target.
-                // 4. Assert: resolvedTarget is contained in packageURL.
-                // 5. If patternMatch is null, then
-                let target =
-                    normalize_string_target(target_key, target, pattern_match, package_url)?;
-                if Path::new(target.as_ref()).is_invalid_exports_target() {
-                    return Err(ResolveError::InvalidPackageTarget(
-                        target.to_string(),
-                        target_key.to_string(),
-                        package_url.join("package.json"),
-                    ));
-                }
-                let resolved_target = package_url.normalize_with(target.as_ref());
-                // 6. If patternMatch split on "/" or "\" contains any "", ".", "..", or "node_modules" segments, case insensitive and including percent encoded variants, throw an Invalid Module Specifier error.
-                // 7. Return the URL resolution of resolvedTarget with every instance of "*" replaced with patternMatch.
-                let value = self.cache.value(&resolved_target);
-                return Ok(Some(value));
+        // 1. If target does not start with "./", then
+        if !target.starts_with("./") {
+            // 1. If isImports is false, or if target starts with "../" or "/", or if target is a valid URL, then
+            if !is_imports || target.starts_with("../") || target.starts_with('/') {
+                // 1. Throw an Invalid Package Target error.
+                return Err(ResolveError::InvalidPackageTarget(
+                    target.to_string(),
+                    target_key.to_string(),
+                    package_url.join("package.json"),
+                ));
             }
-            // 2. Otherwise, if target is a non-null Object, then
-            JSONValue::Object(target) => {
-                // 1. If exports contains any index property keys, as defined in ECMA-262 6.1.7 Array Index, throw an Invalid Package Configuration error.
-                // 2. For each property p of target, in object insertion order as,
-                for (key, target_value) in target {
-                    // 1. If p equals "default" or conditions contains an entry for p, then
-                    if key == "default" || conditions.contains(key) {
-                        // 1. Let targetValue be the value of the p property in target.
-                        // 2. Let resolved be the result of PACKAGE_TARGET_RESOLVE( packageURL, targetValue, patternMatch, isImports, conditions).
-                        let resolved = self.package_target_resolve(
-                            package_url,
-                            target_key,
-                            target_value,
-                            pattern_match,
-                            is_imports,
-                            conditions,
-                            ctx,
-                        );
-                        // 3. If resolved is equal to undefined, continue the loop.
-                        if let Some(path) = resolved? {
-                            // 4. Return resolved.
-                            return Ok(Some(path));
-                        }
-                    }
+            // 2. If patternMatch is a String, then
+            //   1. Return PACKAGE_RESOLVE(target with every instance of "*" replaced by patternMatch, packageURL + "/").
+            let target =
+                normalize_string_target(target_key, target, pattern_match, package_url, ctx)?;
+            let package_url = self.cache.value(package_url, ctx);
+            // // 3. Return PACKAGE_RESOLVE(target, packageURL + "/").
+            return self.package_resolve(&package_url, &target, ctx);
+        }
+
+        // 2. If target split on "/" or "\" contains any "", ".", "..", or "node_modules" segments after the first "." segment, case insensitive and including percent encoded variants, throw an Invalid Package Target error.
+        // 3. Let resolvedTarget be the URL resolution of the concatenation of packageURL and target.
+        // 4. Assert: resolvedTarget is contained in packageURL.
+        // 5. If patternMatch is null, then
+        let target = normalize_string_target(target_key, target, pattern_match, package_url, ctx)?;
+        if Path::new(target.as_ref()).is_invalid_exports_target() {
+            return Err(ResolveError::InvalidPackageTarget(
+                target.to_string(),
+                target_key.to_string(),
+                package_url.join("package.json"),
+            ));
+        }
+        let resolved_target = package_url.normalize_with(target.as_ref());
+        // 6. If patternMatch split on "/" or "\" contains any "", ".", "..", or "node_modules" segments, case insensitive and including percent encoded variants, throw an Invalid Module Specifier error.
+        // 7. Return the URL resolution of resolvedTarget with every instance of "*" replaced with patternMatch.
+        let value = self.cache.value(&resolved_target, ctx);
+        Ok(Some(value))
+    }
+
+    /// PACKAGE_TARGET_RESOLVE(packageURL, target, patternMatch, isImports, conditions)
+    /// where target is a non-null Object.
+    #[allow(clippy::too_many_arguments)]
+    fn package_target_resolve_conditions(
+        &self,
+        package_url: &Path,
+        target_key: &str,
+        target: &[(String, ImportsExportsTarget)],
+        pattern_match: Option<&str>,
+        is_imports: bool,
+        conditions: &[String],
+        ctx: &mut Ctx,
+    ) -> ResolveResult {
+        ctx.conditions_evaluated = true;
+        // 1. If exports contains any index property keys, as defined in ECMA-262 6.1.7 Array Index, throw an Invalid Package Configuration error.
+        // 2. For each property p of target, in object insertion order as,
+        for (key, target_value) in target {
+            // 1. If p equals "default" or conditions contains an entry for p, then
+            if key == "default" || conditions.contains(key) {
+                // 1. Let targetValue be the value of the p property in target.
+                // 2. Let resolved be the result of PACKAGE_TARGET_RESOLVE( packageURL, targetValue, patternMatch, isImports, conditions).
+                let resolved = self.package_target_resolve(
+                    package_url,
+                    target_key,
+                    target_value,
+                    pattern_match,
+                    is_imports,
+                    conditions,
+                    ctx,
+                );
+                // 3. If resolved is equal to undefined, continue the loop.
+                if let Some(path) = resolved? {
+                    // 4. Return resolved.
+                    return Ok(Some(path));
                 }
-                // 3. Return undefined.
-                return Ok(None);
             }
-            // 3. Otherwise, if target is an Array, then
-            JSONValue::Array(targets) => {
-                // 1. If _target.length is zero, return null.
-                if targets.is_empty() {
-                    // Note: return PackagePathNotExported has the same effect as return because there are no matches.
-                    return Err(ResolveError::PackagePathNotExported(
-                        pattern_match.unwrap_or(".").to_string(),
-                        package_url.join("package.json"),
-                    ));
-                }
-                // 2. For each item targetValue in target, do
-                for (i, target_value) in targets.iter().enumerate() {
-                    // 1. Let resolved be the result of PACKAGE_TARGET_RESOLVE( packageURL, targetValue, patternMatch, isImports, conditions), continuing the loop on any Invalid Package Target error.
-                    let resolved = self.package_target_resolve(
-                        package_url,
-                        target_key,
-                        target_value,
-                        pattern_match,
-                        is_imports,
-                        conditions,
-                        ctx,
-                    );
+        }
+        // 3. Return undefined.
+        Ok(None)
+    }
 
-                    if resolved.is_err() && i == targets.len() {
-                        return resolved;
-                    }
+    /// PACKAGE_TARGET_RESOLVE(packageURL, target, patternMatch, isImports, conditions)
+    /// where target is an Array.
+    #[allow(clippy::too_many_arguments)]
+    fn package_target_resolve_array(
+        &self,
+        package_url: &Path,
+        target_key: &str,
+        targets: &[ImportsExportsTarget],
+        pattern_match: Option<&str>,
+        is_imports: bool,
+        conditions: &[String],
+        ctx: &mut Ctx,
+    ) -> ResolveResult {
+        // 1. If _target.length is zero, return null.
+        if targets.is_empty() {
+            // Note: return PackagePathNotExported has the same effect as return because there are no matches.
+            return Err(ResolveError::PackagePathNotExported(
+                pattern_match.unwrap_or(".").to_string(),
+                package_url.join("package.json"),
+            ));
+        }
+        // 2. For each item targetValue in target, do
+        for (i, target_value) in targets.iter().enumerate() {
+            // 1. Let resolved be the result of PACKAGE_TARGET_RESOLVE( packageURL, targetValue, patternMatch, isImports, conditions), continuing the loop on any Invalid Package Target error.
+            let resolved = self.package_target_resolve(
+                package_url,
+                target_key,
+                target_value,
+                pattern_match,
+                is_imports,
+                conditions,
+                ctx,
+            );
 
-                    // 2. If resolved is undefined, continue the loop.
-                    if let Ok(Some(path)) = resolved {
-                        // 3. Return resolved.
-                        return Ok(Some(path));
-                    }
-                }
-                // 3. Return or throw the last fallback resolution null return or error.
-                // Note: see `resolved.is_err() && i == targets.len()`
+            if resolved.is_err() && i == targets.len() {
+                return resolved;
+            }
+            if let Err(error) = &resolved {
+                tracing::debug!(target_key = target_key, err = ?error, "skipped a target");
+            }
+
+            // 2. If resolved is undefined, continue the loop.
+            if let Ok(Some(path)) = resolved {
+                // 3. Return resolved.
+                return Ok(Some(path));
             }
-            _ => {}
         }
-        // 4. Otherwise, if target is null, return null.
+        // 3. Return or throw the last fallback resolution null return or error.
+        // Note: see `resolved.is_err() && i == targets.len()`
         Ok(None)
-        // 5. Otherwise throw an Invalid Package Target error.
+    }
+
+    /// Whether `path` is where `specifier` points to or inside it: the requested path with or without an extension
+    /// for relative and absolute specifiers, the package directory for bare specifiers.
+    fn is_specifier_target(directory: &Path, specifier: &str, path: &Path) -> bool {
+        let Ok(parsed) = Specifier::parse(specifier) else {
+            return false;
+        };
+        let specifier = parsed.path();
+        if specifier.starts_with('.') || Path::new(specifier).is_absolute() {
+            let target = directory.normalize_with(specifier);
+            let has_extension = path.parent() == target.parent()
+                && path
+                    .to_str()
+                    .zip(target.to_str())
+                    .is_some_and(|(path, target)| path.starts_with(target));
+            return path.starts_with(&target) || has_extension;
+        }
+        let (package_name, _) = Self::parse_package_specifier(specifier);
+        !package_name.is_empty() && path.ancestors().any(|dir| dir.ends_with(package_name))
     }
 
     // Returns (module, subpath)
@@ -1762,52 +3175,18 @@ impl<Fs: FileSystem> ResolverGeneric<Fs> {
         (package_name, package_subpath)
     }
 
-    /// PATTERN_KEY_COMPARE(keyA, keyB)
-    fn pattern_key_compare(key_a: &str, key_b: &str) -> Ordering {
-        if key_a.is_empty() {
-            return Ordering::Greater;
-        }
-        // 1. Assert: keyA ends with "/" or contains only a single "*".
-        debug_assert!(key_a.ends_with('/') || key_a.match_indices('*').count() == 1, "{key_a}");
-        // 2. Assert: keyB ends with "/" or contains only a single "*".
-        debug_assert!(key_b.ends_with('/') || key_b.match_indices('*').count() == 1, "{key_b}");
-        // 3. Let baseLengthA be the index of "*" in keyA plus one, if keyA contains "*", or the length of keyA otherwise.
-        let a_pos = key_a.chars().position(|c| c == '*');
-        let base_length_a = a_pos.map_or(key_a.len(), |p| p + 1);
-        // 4. Let baseLengthB be the index of "*" in keyB plus one, if keyB contains "*", or the length of keyB otherwise.
-        let b_pos = key_b.chars().position(|c| c == '*');
-        let base_length_b = b_pos.map_or(key_b.len(), |p| p + 1);
-        // 5. If baseLengthA is greater than baseLengthB, return -1.
-        if base_length_a > base_length_b {
-            return Ordering::Less;
-        }
-        // 6. If baseLengthB is greater than baseLengthA, return 1.
-        if base_length_b > base_length_a {
-            return Ordering::Greater;
-        }
-        // 7. If keyA does not contain "*", return 1.
-        if !key_a.contains('*') {
-            return Ordering::Greater;
-        }
-        // 8. If keyB does not contain "*", return -1.
-        if !key_b.contains('*') {
-            return Ordering::Less;
-        }
-        // 9. If the length of keyA is greater than the length of keyB, return -1.
-        if key_a.len() > key_b.len() {
-            return Ordering::Less;
-        }
-        // 10. If the length of keyB is greater than the length of keyA, return 1.
-        if key_b.len() > key_a.len() {
-            return Ordering::Greater;
-        }
-        // 11. Return 0.
-        Ordering::Equal
-    }
-
     fn strip_package_name<'a>(specifier: &'a str, package_name: &'a str) -> Option<&'a str> {
         specifier
             .strip_prefix(package_name)
             .filter(|tail| tail.is_empty() || tail.starts_with(SLASH_START))
     }
+
+    /// Prepends "." to a package subpath, without allocating for the package root.
+    fn dot_subpath(subpath: &str) -> Cow<'_, str> {
+        if subpath.is_empty() {
+            Cow::Borrowed(".")
+        } else {
+            Cow::Owned(format!(".{subpath}"))
+        }
+    }
 }
@@ -1,18 +1,65 @@
 use std::path::Path;
-use std::{fmt, path::PathBuf};
+use std::{fmt, path::PathBuf, sync::Arc};
+
+use indexmap::IndexMap;
+use regex::Regex;
+use serde::{de, Deserialize, Deserializer};
+
+use crate::{error::OptionsWarning, NodeVersion, PackageJsonProvider, TsconfigProvider};
 
 /// Module Resolution Options
 ///
 /// Options are directly ported from [enhanced-resolve](https://github.com/webpack/enhanced-resolve#resolver-options).
 ///
 /// See [webpack resolve](https://webpack.js.org/configuration/resolve/) for information and examples
-#[derive(Debug, Clone)]
+///
+/// Can be deserialized from JSON (or any other serde format) using the field names of this struct,
+/// missing fields fall back to [ResolveOptions::default].
+/// `alias`, `fallback` and `extension_alias` are written as objects, e.g. `{"alias": {"a": ["b", false]}}`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
 pub struct ResolveOptions {
     /// Path to TypeScript configuration file.
     ///
     /// Default `None`
     pub tsconfig: Option<TsconfigOptions>,
 
+    /// TypeScript configuration files scoped by directory, for monorepos where packages have incompatible `paths`.
+    ///
+    /// Requests from under a directory use the tsconfig of the longest matching directory,
+    /// requests from elsewhere fall back to [ResolveOptions::tsconfig] and [ResolveOptions::tsconfig_discovery].
+    ///
+    /// Deserializes from an object of `directory: tsconfig options`.
+    ///
+    /// Default `[]`
+    #[serde(deserialize_with = "deserialize_tsconfig_scopes")]
+    pub tsconfig_scopes: Vec<(PathBuf, TsconfigOptions)>,
+
+    /// Find the closest `tsconfig.json` or `jsconfig.json` above the importing directory
+    /// and apply its `paths`, for monorepos where each package has its own tsconfig.
+    ///
+    /// Only used when [ResolveOptions::tsconfig] is not set and no [ResolveOptions::tsconfig_scopes] matches.
+    ///
+    /// Default `false`
+    pub tsconfig_discovery: bool,
+
+    /// When tsconfig `paths` are tried, relative to aliases, package self-references and node_modules.
+    ///
+    /// Default [TsconfigPathsPrecedence::First]
+    pub tsconfig_paths_precedence: TsconfigPathsPrecedence,
+
+    /// Supplies tsconfig contents in place of reading them from the file system,
+    /// e.g. the project state of a language server.
+    ///
+    /// Called with the path to each tsconfig file, including extended and referenced ones.
+    /// The file system is read when it returns `None`.
+    ///
+    /// Not deserialized, since a callback cannot be represented in JSON.
+    ///
+    /// Default `None`
+    #[serde(skip)]
+    pub tsconfig_provider: Option<Arc<dyn TsconfigProvider>>,
+
     /// Create aliases to import or require certain modules more easily.
     ///
     /// An alias is used to replace a whole path or part of a path.
@@ -21,34 +68,101 @@ pub struct ResolveOptions {
     /// A trailing $ can also be added to the given object's keys to signify an exact match.
     ///
     /// See [webpack's `resolve.alias` documentation](https://webpack.js.org/configuration/resolve/#resolvealias) for a list of use cases.
+    #[serde(deserialize_with = "deserialize_alias")]
     pub alias: Alias,
 
+    /// Aliases whose keys are regular expressions matched against the whole specifier,
+    /// tried after [ResolveOptions::alias].
+    ///
+    /// Values may reference capture groups, e.g. `^@features/(.*)$` to `./src/features/$1/index.ts`,
+    /// see [regex::Captures::expand] for the syntax.
+    ///
+    /// Deserializes from an ordered object of `pattern: values`, failing on invalid patterns.
+    ///
+    /// Default `[]`
+    #[serde(deserialize_with = "deserialize_regex_alias")]
+    pub regex_alias: Vec<(Regex, Vec<AliasValue>)>,
+
+    /// Skip [ResolveOptions::alias] and [ResolveOptions::regex_alias] when the importing directory is inside a node_modules directory,
+    /// so application aliases such as `react -> preact/compat` do not rewrite imports of third-party packages.
+    ///
+    /// Default `false`
+    pub alias_skip_node_modules: bool,
+
     /// A list of alias fields in description files.
     ///
     /// Specify a field, such as `browser`, to be parsed according to [this specification](https://github.com/defunctzombie/package-browser-field-spec).
     /// Can be a path to json object such as `["path", "to", "exports"]`.
     ///
     /// Default `[]`
+    #[serde(deserialize_with = "deserialize_field_paths")]
     pub alias_fields: Vec<Vec<String>>,
 
+    /// Overrides the alias fields of packages by their package.json "name",
+    /// for packages whose "browser" field is broken for the target.
+    ///
+    /// Applies only when [ResolveOptions::alias_fields] is not empty.
+    ///
+    /// Default `[]`
+    #[serde(deserialize_with = "deserialize_alias_field_overrides")]
+    pub alias_field_overrides: Vec<(String, AliasFieldOverride)>,
+
     /// Condition names for exports field which defines entry points of a package.
     ///
     /// The key order in the exports field is significant. During condition matching, earlier entries have higher priority and take precedence over later entries.
     ///
+    /// The `"..."` item is expanded to the default values.
+    ///
     /// Default `[]`
     pub condition_names: Vec<String>,
 
     /// The JSON files to use for descriptions. (There was once a `bower.json`.)
     ///
+    /// The first file found in a directory is used, `package.json` is used when the list is empty.
+    ///
     /// Default `["package.json"]`
     pub description_files: Vec<String>,
 
+    /// Description files parsed as JSONC, allowing comments and trailing commas,
+    /// e.g. `["deno.jsonc"]` for tools emitting JSONC manifests.
+    ///
+    /// Files not listed, including `package.json` by default, are parsed as strict JSON.
+    ///
+    /// Default `[]`
+    pub jsonc_description_files: Vec<String>,
+
+    /// Stop looking up description files in parent directories at this boundary,
+    /// instead of walking up to the file system root.
+    ///
+    /// Saves stat-ing and caching directories far outside the workspace.
+    /// Importers outside of a [DescriptionFilesBoundary::Path] are not limited.
+    ///
+    /// Default `None`
+    pub description_files_boundary: Option<DescriptionFilesBoundary>,
+
     /// Whether the resolver should check for the presence of a .pnp.cjs file up the dependency tree.
     ///
     /// Default `true`
     #[cfg(feature = "yarn_pnp")]
     pub enable_pnp: bool,
 
+    /// Yarn PnP loose mode, for projects migrating from `node_modules`.
+    ///
+    /// When the PnP manifest rejects an undeclared dependency, the `node_modules` lookup is tried instead
+    /// and the rejection is reported in [crate::ResolveContext::warnings].
+    ///
+    /// Default `false`
+    #[cfg(feature = "yarn_pnp")]
+    pub pnp_loose: bool,
+
+    /// Top level fields removed from [crate::PackageJson::raw_json] to reduce memory usage.
+    ///
+    /// Set to `[]` to retain all fields, e.g. when rules on the description data reference them.
+    ///
+    /// Default `["description", "keywords", "scripts", "dependencies", "devDependencies", "peerDependencies", "optionalDependencies"]`
+    #[cfg(feature = "package_json_raw_json_api")]
+    pub package_json_removed_fields: Vec<String>,
+
     /// Set to [EnforceExtension::Enabled] for [ESM Mandatory file extensions](https://nodejs.org/api/esm.html#mandatory-file-extensions).
     ///
     /// If `enforce_extension` is set to [EnforceExtension::Enabled], resolution will not allow extension-less files.
@@ -69,6 +183,7 @@ pub struct ResolveOptions {
     /// Can be a path to a JSON object such as `["path", "to", "exports"]`.
     ///
     /// Default `[["exports"]]`.
+    #[serde(deserialize_with = "deserialize_field_paths")]
     pub exports_fields: Vec<Vec<String>>,
 
     /// Fields from `package.json` which are used to provide the internal requests of a package
@@ -77,11 +192,13 @@ pub struct ResolveOptions {
     /// Can be a path to a JSON object such as `["path", "to", "imports"]`.
     ///
     /// Default `[["imports"]]`.
+    #[serde(deserialize_with = "deserialize_field_paths")]
     pub imports_fields: Vec<Vec<String>>,
 
     /// An object which maps extension to extension aliases.
     ///
     /// Default `{}`
+    #[serde(deserialize_with = "deserialize_extension_alias")]
     pub extension_alias: Vec<(String, Vec<String>)>,
 
     /// Attempt to resolve these extensions in order.
@@ -90,15 +207,70 @@ pub struct ResolveOptions {
     /// will resolve the one with the extension listed first in the array and skip the rest.
     ///
     /// All extensions must have a leading dot.
+    /// The `"..."` item is expanded to the default values.
     ///
     /// Default `[".js", ".json", ".node"]`
     pub extensions: Vec<String>,
 
+    /// Try [ResolveOptions::extensions] in the order of how often each of them resolved a file, most frequent first.
+    ///
+    /// The frequencies are counted for all resolvers sharing the cache and reset by [crate::ResolverGeneric::clear_cache],
+    /// see [crate::CacheStats::extension_hits]. The order is only sorted again after a number of new hits.
+    /// The result is not deterministic when a specifier matches files with different extensions.
+    ///
+    /// Default `false`
+    pub adaptive_extensions: bool,
+
+    /// Read a directory once instead of looking up each candidate in it
+    /// when [ResolveOptions::extensions] and [ResolveOptions::extension_infixes] produce at least this many candidates.
+    ///
+    /// Candidates missing from the cached directory listing are skipped without a `metadata` call.
+    /// File names are compared case-insensitively, and names with non-ASCII characters are always looked up,
+    /// so the listing does not hide files on case-insensitive file systems.
+    /// Candidates are looked up one by one when [crate::FileSystem::read_dir] fails.
+    /// The listing is read again after a file in the directory is passed to [crate::ResolverGeneric::invalidate].
+    ///
+    /// Default `None`
+    pub directory_listing_threshold: Option<usize>,
+
+    /// Infixes tried in order between the file name and each of [ResolveOptions::extensions],
+    /// e.g. `["ios", "native"]` tries `Button.ios.js` and `Button.native.js` before `Button.js`.
+    ///
+    /// Infixes do not have a leading dot.
+    ///
+    /// Default `[]`
+    pub extension_infixes: Vec<String>,
+
+    /// Platform suffixes of native addons, e.g. `["linux-x64-gnu", "linux-x64"]`.
+    ///
+    /// When `foo.node` is not found, `foo.{suffix}.node` (napi-rs) and `prebuilds/{suffix}/foo.node` (prebuildify)
+    /// are tried for each suffix in order.
+    ///
+    /// Default `[]`
+    pub native_addon_suffixes: Vec<String>,
+
     /// Redirect module requests when normal resolving fails.
     ///
     /// Default `[]`
+    #[serde(deserialize_with = "deserialize_alias")]
     pub fallback: Alias,
 
+    /// Only consult [ResolveOptions::fallback] for bare specifiers such as `path` or `@scope/pkg`,
+    /// like webpack's `resolve.fallback` for Node.js core module polyfills,
+    /// so a mistyped relative or absolute request is not redirected to a polyfill.
+    ///
+    /// Default `false`
+    pub fallback_bare_only: bool,
+
+    /// Option sets tried in order, sharing the cache and the [crate::ResolveContext], until one of them resolves.
+    ///
+    /// For example, resolve with ESM conditions first, then with CommonJS conditions, then without `fully_specified`.
+    /// The fallbacks are complete option sets, build them from the base options with struct update syntax.
+    /// A fallback is not tried after [crate::ResolveError::Ignored].
+    ///
+    /// Default `[]`
+    pub fallback_options: Vec<ResolveOptions>,
+
     /// Request passed to resolve is already fully specified and extensions or main files are not resolved for it (they are still resolved for internal requests).
     ///
     /// See also webpack configuration [resolve.fullySpecified](https://webpack.js.org/configuration/module/#resolvefullyspecified)
@@ -106,8 +278,38 @@ pub struct ResolveOptions {
     /// Default `false`
     pub fully_specified: bool,
 
+    /// Try a relative or absolute specifier containing `#` as a literal path first,
+    /// before treating the `#` as the start of a fragment.
+    ///
+    /// Disable when file names never contain `#` to halve the lookups of specifiers with fragments,
+    /// a literal `#` can still be escaped as `\0#`.
+    ///
+    /// Default `true`
+    pub fragment_as_path: bool,
+
+    /// Return [crate::Resolution::path] with `/` separators on Windows,
+    /// e.g. `C:/project/index.js` and `//server/share/index.js` for a UNC path.
+    ///
+    /// Has no effect on other platforms, where `\` is a valid file name character.
+    ///
+    /// Default `false`
+    pub forward_slashes: bool,
+
+    /// Also return [crate::Resolution::relative_path], the resolved path relative to this absolute directory,
+    /// with `..` for paths outside of it.
+    ///
+    /// Useful for machine-independent paths in persistent caches, combined with [ResolveOptions::forward_slashes] on Windows.
+    /// No relative path is returned for a path on another drive or UNC share.
+    ///
+    /// Default `None`
+    pub relative_path_base: Option<PathBuf>,
+
     /// A list of main fields in description files
     ///
+    /// A field with dots such as `"spm.main"` is a path to a JSON object, i.e. `spm` > `main`,
+    /// unless the description file has a field with that exact name.
+    /// The `"..."` item is expanded to the default values.
+    ///
     /// Default `["main"]`.
     pub main_fields: Vec<String>,
 
@@ -121,11 +323,47 @@ pub struct ResolveOptions {
     /// Default `["node_modules"]`
     pub modules: Vec<String>,
 
+    /// Stop looking up [ResolveOptions::modules] in parent directories after this directory,
+    /// instead of walking up to the file system root.
+    ///
+    /// Prevents resolving from a stray `node_modules` in e.g. the home directory, and saves the stats to get there.
+    /// Importers outside of the boundary are not limited.
+    ///
+    /// Default `None`
+    pub modules_boundary: Option<ModulesBoundary>,
+
+    /// Module directories for layouts which [ResolveOptions::modules] can not describe, e.g. Bazel runfiles.
+    ///
+    /// Called with the directory of the importer, the returned directories are tried in order
+    /// after the [ResolveOptions::modules] lookup.
+    ///
+    /// Not deserialized, since a callback cannot be represented in JSON.
+    ///
+    /// Default `None`
+    #[serde(skip)]
+    pub modules_provider: Option<ModulesProvider>,
+
+    /// Supplies already parsed package.json files, e.g. from the module graph of the bundler.
+    ///
+    /// Consulted once per directory before reading `package.json` from the file system,
+    /// the file system is read when it returns `None`.
+    ///
+    /// Not deserialized, since a callback cannot be represented in JSON.
+    ///
+    /// Default `None`
+    #[serde(skip)]
+    pub package_json_provider: Option<Arc<dyn PackageJsonProvider>>,
+
     /// Resolve to a context instead of a file.
     ///
     /// Default `false`
     pub resolve_to_context: bool,
 
+    /// How requests ending in `/`, e.g. `./dir/`, are resolved.
+    ///
+    /// Default [TrailingSlash::Index]
+    pub trailing_slash: TrailingSlash,
+
     /// Prefer to resolve module requests as relative requests instead of using modules from node_modules directories.
     ///
     /// Default `false`
@@ -141,12 +379,72 @@ pub struct ResolveOptions {
     /// Default `[]`
     pub restrictions: Vec<Restriction>,
 
+    /// A list of allowed root directories.
+    ///
+    /// When not empty, resolving to a path whose real path is outside all of these directories fails with
+    /// [crate::ResolveError::OutsideAllowedRoots]. Unlike [ResolveOptions::restrictions], symlinks are always followed
+    /// for this check, so escaping through a symlink is rejected even when [ResolveOptions::symlinks] is disabled.
+    ///
+    /// Default `[]`
+    pub allowed_roots: Vec<PathBuf>,
+
     /// A list of directories where requests of server-relative URLs (starting with '/') are resolved.
     /// On non-Windows systems these requests are resolved as an absolute path first.
     ///
     /// Default `[]`
     pub roots: Vec<PathBuf>,
 
+    /// Resolve `~/foo` and `/foo` against a project or package root, as in Parcel, Vite and Nuxt.
+    ///
+    /// The root is the configured [RootPrefixOptions::root], or else the directory of the nearest package.json of the importer,
+    /// so these specifiers keep working inside nested packages.
+    ///
+    /// Default `None`
+    pub root_prefixes: Option<RootPrefixOptions>,
+
+    /// Root directory of a monorepo.
+    ///
+    /// When set, the "packages" globs of its pnpm-workspace.yaml, or the "workspaces" globs of its package.json,
+    /// are expanded to find workspace packages,
+    /// and bare specifiers naming a workspace package resolve to its directory before `node_modules` are looked up.
+    /// Resolutions fail with [crate::ResolveError::IOError] when the pnpm-workspace.yaml uses YAML features that are not supported,
+    /// such as anchors or block scalars.
+    ///
+    /// Default `None`
+    pub workspace_root: Option<PathBuf>,
+
+    /// Directory of the application's root package.json whose Parcel style "alias" field is applied.
+    ///
+    /// The field maps specifiers to other specifiers, to paths relative to the package.json, or to `false` to ignore the module,
+    /// e.g. `{ "alias": { "react": "preact/compat", "fs": false } }`.
+    /// It is applied before [ResolveOptions::alias], so the manifest takes the highest priority.
+    ///
+    /// <https://parceljs.org/features/dependency-resolution/#aliases>
+    ///
+    /// Default `None`
+    pub alias_root: Option<PathBuf>,
+
+    /// Package names which always resolve from the `node_modules` of [ResolveOptions::dedupe_root],
+    /// regardless of the copy nearest to the importer, as in Vite's `resolve.dedupe`.
+    ///
+    /// Avoids duplicated instances of packages such as React in linked package setups.
+    ///
+    /// Default `[]`
+    pub dedupe: Vec<String>,
+
+    /// The directory the [ResolveOptions::dedupe] packages resolve from, [ResolveOptions::dedupe] has no effect when it is not set.
+    ///
+    /// Default `None`
+    pub dedupe_root: Option<PathBuf>,
+
+    /// Only resolve bare specifiers declared in the dependency fields of the importer's nearest package.json,
+    /// failing with [crate::ResolveError::UndeclaredDependency] for phantom dependencies otherwise.
+    ///
+    /// Self-references and Node.js builtin modules are always allowed.
+    ///
+    /// Default `None`
+    pub strict_dependencies: Option<StrictDependencies>,
+
     /// Whether to resolve symlinks to their symlinked location.
     /// When enabled, symlinked resources are resolved to their real path, not their symlinked location.
     /// Note that this may cause module resolution to fail when using tools that symlink packages (like npm link).
@@ -154,11 +452,67 @@ pub struct ResolveOptions {
     /// Default `true`
     pub symlinks: bool,
 
+    /// What to do when a real path leaves the given roots, e.g. for globally linked packages with `npm link`.
+    ///
+    /// Only used when [ResolveOptions::symlinks] is enabled.
+    ///
+    /// Default `None`, the real path is always used.
+    pub symlink_escape: Option<SymlinkEscapeOptions>,
+
+    /// Specifiers left to the runtime instead of being bundled, queried with [crate::ResolverGeneric::is_external].
+    ///
+    /// Not deserialized, since [External::Function] cannot be represented in JSON.
+    ///
+    /// Default `[]`
+    #[serde(skip)]
+    pub externals: Vec<External>,
+
+    /// Rewrites applied in order to the specifier before it is resolved,
+    /// for framework conventions such as `virtual:` or `astro:` specifiers.
+    ///
+    /// Errors report the rewritten specifier.
+    ///
+    /// Not deserialized, since [SpecifierRewrite::Function] cannot be represented in JSON.
+    ///
+    /// Default `[]`
+    #[serde(skip)]
+    pub specifier_rewrites: Vec<SpecifierRewrite>,
+
     /// Whether to parse [module.builtinModules](https://nodejs.org/api/module.html#modulebuiltinmodules) or not.
     /// For example, "zlib" will throw [crate::ResolveError::Builtin] when set to true.
     ///
     /// Default `false`
     pub builtin_modules: bool,
+
+    /// Recognize the builtin modules of this Node.js version for [ResolveOptions::builtin_modules],
+    /// including [crate::NODEJS_VERSIONED_BUILTINS] such as `node:sqlite` when the version has them.
+    ///
+    /// When set, `node:` specifiers which are not builtin modules of the version are resolved like other specifiers.
+    /// When `None`, [crate::NODEJS_BUILTINS] and all `node:` specifiers are builtin modules.
+    ///
+    /// Deserializes from a version string such as `"22.5.0"`.
+    ///
+    /// Default `None`
+    pub builtin_modules_node_version: Option<NodeVersion>,
+
+    /// Maximum number of paths a single resolution may probe on the file system, including cached lookups,
+    /// before failing with [crate::ResolveError::FsOperationBudgetExceeded].
+    ///
+    /// Guards against configurations where many extensions, main files and ancestor directories multiply
+    /// into an excessive number of lookups. The error lists the directories where most lookups were spent.
+    ///
+    /// Default `None`
+    pub fs_operation_budget: Option<usize>,
+
+    /// Bypass the path, package.json, tsconfig, workspace and Yarn PnP manifest caches,
+    /// reading everything from the file system again for every request.
+    /// A path is still read once within a request.
+    ///
+    /// For debugging suspected stale cache results, this is much slower.
+    /// Resolvers created by [crate::ResolverGeneric::clone_with_options] share the cache and this setting.
+    ///
+    /// Default `false`
+    pub disable_cache: bool,
 }
 
 impl ResolveOptions {
@@ -176,6 +530,41 @@ impl ResolveOptions {
         self
     }
 
+    /// Adds the conditions passed with `--conditions` or `-C` in `node_options`,
+    /// which is formatted like the `NODE_OPTIONS` environment variable.
+    ///
+    /// Conditions already in [ResolveOptions::condition_names] are not added again.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use rspack_resolver::ResolveOptions;
+    ///
+    /// let options = ResolveOptions::default()
+    ///     .with_condition_names(&["node"])
+    ///     .with_node_options_conditions("--conditions=development -C node --inspect");
+    /// assert_eq!(options.condition_names, vec!["node", "development"])
+    /// ```
+    #[must_use]
+    pub fn with_node_options_conditions(mut self, node_options: &str) -> Self {
+        for condition in parse_node_options_conditions(node_options) {
+            if !self.condition_names.contains(&condition) {
+                self.condition_names.push(condition);
+            }
+        }
+        self
+    }
+
+    /// Adds the conditions passed with `--conditions` or `-C` in the `NODE_OPTIONS` environment variable,
+    /// see [ResolveOptions::with_node_options_conditions].
+    #[must_use]
+    pub fn with_node_options_conditions_from_env(self) -> Self {
+        match std::env::var("NODE_OPTIONS") {
+            Ok(node_options) => self.with_node_options_conditions(&node_options),
+            Err(_) => self,
+        }
+    }
+
     /// ## Examples
     ///
     /// ```
@@ -355,11 +744,65 @@ impl ResolveOptions {
         self
     }
 
+    /// Detects option values which are likely misconfigured,
+    /// such as extensions without a leading dot or aliases pointing to absolute paths which do not exist.
+    ///
+    /// The options are not modified, [crate::ResolverGeneric] resolves with them as they are.
+    /// [ResolveOptions::fallback_options] are validated as well.
+    pub fn validate(&self) -> Vec<OptionsWarning> {
+        let mut warnings = vec![];
+        if self.condition_names.iter().any(|c| c == "import")
+            && self.condition_names.iter().any(|c| c == "require")
+        {
+            warnings.push(OptionsWarning::ConflictingConditions);
+        }
+        for (option, field_paths) in [
+            ("alias_fields", &self.alias_fields),
+            ("exports_fields", &self.exports_fields),
+            ("imports_fields", &self.imports_fields),
+        ] {
+            if field_paths.iter().any(Vec::is_empty) {
+                warnings.push(OptionsWarning::EmptyFieldPath(option));
+            }
+        }
+        let extension_aliases = self.extension_alias.iter().flat_map(|(ext, exts)| {
+            std::iter::once(ext).chain(exts).map(|ext| (ext, "extension_alias"))
+        });
+        for (extension, option) in
+            self.extensions.iter().map(|ext| (ext, "extensions")).chain(extension_aliases)
+        {
+            if !extension.is_empty() && !extension.starts_with('.') {
+                warnings
+                    .push(OptionsWarning::ExtensionWithoutLeadingDot(extension.clone(), option));
+            }
+        }
+        for (key, values) in self.alias.iter().chain(&self.fallback) {
+            for value in values {
+                let AliasValue::Path(path) = value else { continue };
+                let path = Path::new(path);
+                if path.is_absolute() && !path.exists() {
+                    warnings
+                        .push(OptionsWarning::AliasTargetNotFound(key.clone(), path.to_path_buf()));
+                }
+            }
+        }
+        for options in &self.fallback_options {
+            warnings.extend(options.validate());
+        }
+        warnings
+    }
+
     pub(crate) fn sanitize(mut self) -> Self {
+        // Expand webpack's `"..."` item to the defaults, e.g. `extensions: [".ts", "..."]`.
+        let default = Self::default();
+        self.extensions = expand_spread(self.extensions, default.extensions);
+        self.condition_names = expand_spread(self.condition_names, default.condition_names);
+        self.main_fields = expand_spread(self.main_fields, default.main_fields);
         debug_assert!(
             self.extensions.iter().filter(|e| !e.is_empty()).all(|e| e.starts_with('.')),
             "All extensions must start with a leading dot"
         );
+        self.fallback_options = self.fallback_options.into_iter().map(Self::sanitize).collect();
         // Set `enforceExtension` to `true` when [ResolveOptions::extensions] contains an empty string.
         // See <https://github.com/webpack/enhanced-resolve/pull/285>
         if self.enforce_extension == EnforceExtension::Auto {
@@ -373,8 +816,75 @@ impl ResolveOptions {
     }
 }
 
+/// Expands the `"..."` item to the default values, the defaults are inserted once.
+fn expand_spread(values: Vec<String>, defaults: Vec<String>) -> Vec<String> {
+    if !values.iter().any(|value| value == "...") {
+        return values;
+    }
+    let mut defaults = Some(defaults);
+    let mut expanded = vec![];
+    for value in values {
+        if value == "..." {
+            expanded.extend(defaults.take().unwrap_or_default());
+        } else {
+            expanded.push(value);
+        }
+    }
+    expanded
+}
+
+/// Parses the values of `--conditions` and `-C` from Node.js command line options.
+///
+/// Supports `--conditions=name`, `--conditions name`, `-C=name` and `-C name`.
+fn parse_node_options_conditions(node_options: &str) -> Vec<String> {
+    let mut conditions = vec![];
+    let mut args = node_options.split_whitespace();
+    while let Some(arg) = args.next() {
+        let value = match arg {
+            "--conditions" | "-C" => args.next(),
+            _ => arg.strip_prefix("--conditions=").or_else(|| arg.strip_prefix("-C=")),
+        };
+        if let Some(value) = value.map(|value| value.trim_matches(|c| c == '"' || c == '\'')) {
+            if !value.is_empty() {
+                conditions.push(value.to_string());
+            }
+        }
+    }
+    conditions
+}
+
+/// Value for [ResolveOptions::tsconfig_paths_precedence]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TsconfigPathsPrecedence {
+    /// Try tsconfig `paths` before everything else, including aliases and package exports.
+    #[default]
+    First,
+
+    /// Try tsconfig `paths` only when the specifier cannot be resolved otherwise,
+    /// before [ResolveOptions::fallback].
+    Fallback,
+}
+
+/// Value for [ResolveOptions::trailing_slash]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrailingSlash {
+    /// Load the directory as a module, from its package.json main fields or index files.
+    #[default]
+    Index,
+
+    /// Resolve to the directory itself.
+    Context,
+
+    /// Same as [TrailingSlash::Index],
+    /// but fail with [crate::ResolveError::NotADirectory] when the path exists and is not a directory.
+    Strict,
+}
+
 /// Value for [ResolveOptions::enforce_extension]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum EnforceExtension {
     Auto,
     Enabled,
@@ -417,17 +927,286 @@ where
     }
 }
 
+/// Deserializes from a string for [AliasValue::Path] or `false` for [AliasValue::Ignore].
+impl<'de> Deserialize<'de> for AliasValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum RawAliasValue {
+            Path(String),
+            Bool(bool),
+        }
+        match RawAliasValue::deserialize(deserializer)? {
+            RawAliasValue::Path(path) => Ok(Self::Path(path)),
+            RawAliasValue::Bool(false) => Ok(Self::Ignore),
+            RawAliasValue::Bool(true) => {
+                Err(de::Error::custom("alias value must be a string or `false`"))
+            }
+        }
+    }
+}
+
+/// Value for [ResolveOptions::alias_field_overrides]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AliasFieldOverride {
+    /// Ignore the alias fields of the package.
+    Ignore,
+
+    /// Use these entries in place of the alias fields of the package,
+    /// keyed like the "browser" field, e.g. `("./lib/node.js", AliasValue::Path("./lib/browser.js".into()))`.
+    Replace(Vec<(String, AliasValue)>),
+}
+
+/// Deserializes from `false` for [AliasFieldOverride::Ignore] or an object like the "browser" field for [AliasFieldOverride::Replace].
+impl<'de> Deserialize<'de> for AliasFieldOverride {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum RawAliasFieldOverride {
+            Replace(IndexMap<String, AliasValue>),
+            Bool(bool),
+        }
+        match RawAliasFieldOverride::deserialize(deserializer)? {
+            RawAliasFieldOverride::Replace(map) => Ok(Self::Replace(map.into_iter().collect())),
+            RawAliasFieldOverride::Bool(false) => Ok(Self::Ignore),
+            RawAliasFieldOverride::Bool(true) => {
+                Err(de::Error::custom("alias field override must be an object or `false`"))
+            }
+        }
+    }
+}
+
+/// Value for [ResolveOptions::externals]
+#[derive(Clone)]
+pub enum External {
+    /// Matches the specifier exactly.
+    Exact(String),
+
+    /// Matches specifiers starting with the prefix, e.g. `node:`.
+    Prefix(String),
+
+    /// Matches the package and all of its subpaths, e.g. `lodash` matches `lodash` and `lodash/get`.
+    Package(String),
+
+    /// Matches when the function returns `true` for the specifier and the directory it is requested from.
+    ///
+    /// Use this for regular expressions.
+    #[allow(clippy::type_complexity)]
+    Function(Arc<dyn Fn(&str, &Path) -> bool + Send + Sync>),
+}
+
+impl External {
+    /// Whether `specifier` requested from `directory` matches.
+    pub fn matches(&self, specifier: &str, directory: &Path) -> bool {
+        match self {
+            Self::Exact(name) => specifier == name,
+            Self::Prefix(prefix) => specifier.starts_with(prefix.as_str()),
+            Self::Package(name) => specifier
+                .strip_prefix(name.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/')),
+            Self::Function(f) => f(specifier, directory),
+        }
+    }
+}
+
+/// Value for [ResolveOptions::specifier_rewrites]
+#[derive(Clone)]
+pub enum SpecifierRewrite {
+    /// Replaces the prefix `.0` with `.1`, e.g. `~/` with `./src/`.
+    Prefix(String, String),
+
+    /// Strips the scheme, e.g. `virtual` rewrites `virtual:foo` to `foo`.
+    Scheme(String),
+
+    /// Rewrites the specifier to the returned value, `None` keeps the specifier.
+    ///
+    /// Use this for regular expressions.
+    #[allow(clippy::type_complexity)]
+    Function(Arc<dyn Fn(&str) -> Option<String> + Send + Sync>),
+}
+
+impl SpecifierRewrite {
+    /// Returns the rewritten `specifier`, or `None` when this rewrite does not apply.
+    pub fn rewrite(&self, specifier: &str) -> Option<String> {
+        match self {
+            Self::Prefix(from, to) => {
+                specifier.strip_prefix(from.as_str()).map(|rest| format!("{to}{rest}"))
+            }
+            Self::Scheme(scheme) => specifier
+                .strip_prefix(scheme.as_str())
+                .and_then(|rest| rest.strip_prefix(':'))
+                .map(ToString::to_string),
+            Self::Function(f) => f(specifier),
+        }
+    }
+}
+
+impl fmt::Debug for SpecifierRewrite {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Prefix(from, to) => f.debug_tuple("Prefix").field(from).field(to).finish(),
+            Self::Scheme(scheme) => f.debug_tuple("Scheme").field(scheme).finish(),
+            Self::Function(_) => f.write_str("Function"),
+        }
+    }
+}
+
+impl fmt::Debug for External {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Exact(name) => f.debug_tuple("Exact").field(name).finish(),
+            Self::Prefix(prefix) => f.debug_tuple("Prefix").field(prefix).finish(),
+            Self::Package(name) => f.debug_tuple("Package").field(name).finish(),
+            Self::Function(_) => f.write_str("Function"),
+        }
+    }
+}
+
+/// Value for [ResolveOptions::modules_boundary]
+///
+/// Deserializes from `{"path": "/path/to/dir"}`, `"git"` or `"workspace"`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModulesBoundary {
+    /// Stop at this directory.
+    Path(PathBuf),
+
+    /// Stop at the nearest directory containing `.git`.
+    Git,
+
+    /// Stop at [ResolveOptions::workspace_root].
+    Workspace,
+}
+
+/// Value for [ResolveOptions::description_files_boundary]
+///
+/// Deserializes from `{"path": "/path/to/dir"}` or `"node_modules"`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DescriptionFilesBoundary {
+    /// Stop at this directory.
+    Path(PathBuf),
+
+    /// Stop below the nearest `node_modules` directory,
+    /// so a file inside a package never uses a description file above the package.
+    #[serde(rename = "node_modules")]
+    NodeModules,
+}
+
+/// Callback for [ResolveOptions::modules_provider]
+#[derive(Clone)]
+#[allow(clippy::type_complexity)]
+pub struct ModulesProvider(Arc<dyn Fn(&Path) -> Vec<PathBuf> + Send + Sync>);
+
+impl ModulesProvider {
+    /// `f` returns the module directories for the directory of the importer.
+    pub fn new<F: Fn(&Path) -> Vec<PathBuf> + Send + Sync + 'static>(f: F) -> Self {
+        Self(Arc::new(f))
+    }
+
+    /// Module directories for the importer `directory`.
+    pub fn module_directories(&self, directory: &Path) -> Vec<PathBuf> {
+        (self.0)(directory)
+    }
+}
+
+impl fmt::Debug for ModulesProvider {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("ModulesProvider")
+    }
+}
+
 /// Value for [ResolveOptions::restrictions]
-#[derive(Debug, Clone)]
+///
+/// Deserializes from `{"path": "/path/to/dir"}` or `{"regex": "pattern"}`.
+#[derive(Debug, Clone, Deserialize)]
 pub enum Restriction {
+    #[serde(rename = "path")]
     Path(PathBuf),
+    #[serde(rename = "regex")]
     RegExp(String),
 }
 
+/// Options for [ResolveOptions::root_prefixes]
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RootPrefixOptions {
+    /// Resolve `~/foo` against the root.
+    #[serde(default)]
+    pub tilde: bool,
+
+    /// Resolve `/foo` against the root, before [ResolveOptions::roots] and the file system root are tried.
+    #[serde(default)]
+    pub slash: bool,
+
+    /// The root directory, the directory of the nearest package.json of the importer when `None`.
+    #[serde(default)]
+    pub root: Option<PathBuf>,
+}
+
+/// Options for [ResolveOptions::strict_dependencies]
+///
+/// Packages in "dependencies" are always allowed.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct StrictDependencies {
+    /// Allow packages in "devDependencies".
+    pub dev_dependencies: bool,
+
+    /// Allow packages in "peerDependencies".
+    pub peer_dependencies: bool,
+
+    /// Allow packages in "optionalDependencies".
+    pub optional_dependencies: bool,
+}
+
+impl Default for StrictDependencies {
+    fn default() -> Self {
+        Self { dev_dependencies: true, peer_dependencies: true, optional_dependencies: true }
+    }
+}
+
+impl StrictDependencies {
+    /// The package.json fields declaring the allowed packages.
+    pub fn fields(&self) -> impl Iterator<Item = &'static str> {
+        [
+            ("dependencies", true),
+            ("devDependencies", self.dev_dependencies),
+            ("peerDependencies", self.peer_dependencies),
+            ("optionalDependencies", self.optional_dependencies),
+        ]
+        .into_iter()
+        .filter_map(|(field, allowed)| allowed.then_some(field))
+    }
+}
+
+/// Options for [ResolveOptions::symlink_escape]
+#[derive(Debug, Clone, Deserialize)]
+pub struct SymlinkEscapeOptions {
+    /// Project or workspace roots, a real path outside all of them is an escape.
+    pub roots: Vec<PathBuf>,
+
+    /// What to do when a real path escapes [SymlinkEscapeOptions::roots].
+    #[serde(default)]
+    pub policy: SymlinkEscapePolicy,
+}
+
+/// Value for [SymlinkEscapeOptions::policy]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SymlinkEscapePolicy {
+    /// Keep the real path.
+    #[default]
+    Canonical,
+    /// Keep the original symlinked path.
+    Original,
+    /// Fail with [crate::ResolveError::SymlinkEscape].
+    Error,
+}
+
 /// Tsconfig Options for [ResolveOptions::tsconfig]
 ///
 /// Derived from [tsconfig-paths-webpack-plugin](https://github.com/dividab/tsconfig-paths-webpack-plugin#options)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct TsconfigOptions {
     /// Allows you to specify where to find the TypeScript configuration file.
     /// You may provide
@@ -436,12 +1215,34 @@ pub struct TsconfigOptions {
     pub config_file: PathBuf,
 
     /// Support for Typescript Project References.
+    ///
+    /// Deserializes from `"auto"`, `"disabled"` or a list of paths, defaults to [TsconfigReferences::Disabled].
+    #[serde(default)]
     pub references: TsconfigReferences,
+
+    /// The tsconfig content used in place of reading `config_file`,
+    /// which then only gives its virtual location: `extends` and relative paths resolve against its directory.
+    ///
+    /// Deserializes from a string or a tsconfig object.
+    #[serde(default, deserialize_with = "deserialize_tsconfig_content")]
+    pub content: Option<String>,
+}
+
+/// Deserializes [TsconfigOptions::content] from a string or a tsconfig object.
+fn deserialize_tsconfig_content<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    Ok(match Option::<serde_json::Value>::deserialize(deserializer)? {
+        None => None,
+        Some(serde_json::Value::String(content)) => Some(content),
+        Some(value) => Some(value.to_string()),
+    })
 }
 
 /// Configuration for [TsconfigOptions::references]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub enum TsconfigReferences {
+    #[default]
     Disabled,
     /// Use the `references` field from tsconfig of `config_file`.
     Auto,
@@ -449,49 +1250,498 @@ pub enum TsconfigReferences {
     Paths(Vec<PathBuf>),
 }
 
+impl<'de> Deserialize<'de> for TsconfigReferences {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum RawTsconfigReferences {
+            Keyword(String),
+            Paths(Vec<PathBuf>),
+        }
+        match RawTsconfigReferences::deserialize(deserializer)? {
+            RawTsconfigReferences::Keyword(keyword) => match keyword.as_str() {
+                "auto" => Ok(Self::Auto),
+                "disabled" => Ok(Self::Disabled),
+                _ => Err(de::Error::unknown_variant(&keyword, &["auto", "disabled"])),
+            },
+            RawTsconfigReferences::Paths(paths) => Ok(Self::Paths(paths)),
+        }
+    }
+}
+
+/// A single alias value or a list of alias values.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AliasValues {
+    Single(AliasValue),
+    Multiple(Vec<AliasValue>),
+}
+
+impl From<AliasValues> for Vec<AliasValue> {
+    fn from(values: AliasValues) -> Self {
+        match values {
+            AliasValues::Single(value) => vec![value],
+            AliasValues::Multiple(values) => values,
+        }
+    }
+}
+
+/// Deserializes [Alias] from an ordered object of `key: value | [values]`.
+fn deserialize_alias<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Alias, D::Error> {
+    let map = IndexMap::<String, AliasValues>::deserialize(deserializer)?;
+    Ok(map.into_iter().map(|(key, values)| (key, values.into())).collect())
+}
+
+/// Deserializes [ResolveOptions::regex_alias] from an ordered object of `pattern: values`.
+fn deserialize_regex_alias<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<(Regex, Vec<AliasValue>)>, D::Error> {
+    deserialize_alias(deserializer)?
+        .into_iter()
+        .map(|(pattern, values)| Ok((Regex::new(&pattern).map_err(de::Error::custom)?, values)))
+        .collect()
+}
+
+/// Deserializes [ResolveOptions::alias_field_overrides] from an ordered object of `name: override`.
+fn deserialize_alias_field_overrides<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<(String, AliasFieldOverride)>, D::Error> {
+    let map = IndexMap::<String, AliasFieldOverride>::deserialize(deserializer)?;
+    Ok(map.into_iter().collect())
+}
+
+/// Deserializes [ResolveOptions::tsconfig_scopes] from an ordered object of `directory: tsconfig options`.
+fn deserialize_tsconfig_scopes<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<(PathBuf, TsconfigOptions)>, D::Error> {
+    let map = IndexMap::<PathBuf, TsconfigOptions>::deserialize(deserializer)?;
+    Ok(map.into_iter().collect())
+}
+
+/// Deserializes [ResolveOptions::extension_alias] from an ordered object of `extension: [extensions]`.
+fn deserialize_extension_alias<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<(String, Vec<String>)>, D::Error> {
+    let map = IndexMap::<String, Vec<String>>::deserialize(deserializer)?;
+    Ok(map.into_iter().collect())
+}
+
+/// A description file field name `"browser"` or a path to a field `["path", "to", "field"]`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FieldPath {
+    Single(String),
+    Path(Vec<String>),
+}
+
+impl From<FieldPath> for Vec<String> {
+    fn from(path: FieldPath) -> Self {
+        match path {
+            FieldPath::Single(field) => vec![field],
+            FieldPath::Path(path) => path,
+        }
+    }
+}
+
+/// Deserializes a list of description file field paths where each item is either
+/// a single field name `"browser"` or a path `["path", "to", "field"]`.
+fn deserialize_field_paths<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<Vec<String>>, D::Error> {
+    let paths = Vec::<FieldPath>::deserialize(deserializer)?;
+    Ok(paths.into_iter().map(Into::into).collect())
+}
+
 impl Default for ResolveOptions {
     fn default() -> Self {
         Self {
             tsconfig: None,
+            tsconfig_scopes: vec![],
+            tsconfig_discovery: false,
+            tsconfig_paths_precedence: TsconfigPathsPrecedence::First,
             alias: vec![],
+            regex_alias: vec![],
+            alias_skip_node_modules: false,
             alias_fields: vec![],
+            alias_field_overrides: vec![],
             condition_names: vec![],
             description_files: vec!["package.json".into()],
+            jsonc_description_files: vec![],
+            description_files_boundary: None,
             enforce_extension: EnforceExtension::Auto,
             extension_alias: vec![],
             exports_fields: vec![vec!["exports".into()]],
             imports_fields: vec![vec!["imports".into()]],
             extensions: vec![".js".into(), ".json".into(), ".node".into()],
+            adaptive_extensions: false,
+            directory_listing_threshold: None,
+            extension_infixes: vec![],
+            native_addon_suffixes: vec![],
             fallback: vec![],
+            fallback_bare_only: false,
+            fallback_options: vec![],
             fully_specified: false,
+            fragment_as_path: true,
+            forward_slashes: false,
+            relative_path_base: None,
             main_fields: vec!["main".into()],
             main_files: vec!["index".into()],
             modules: vec!["node_modules".into()],
+            modules_boundary: None,
+            modules_provider: None,
+            package_json_provider: None,
+            tsconfig_provider: None,
             #[cfg(feature = "yarn_pnp")]
             enable_pnp: true,
+            #[cfg(feature = "yarn_pnp")]
+            pnp_loose: false,
+            #[cfg(feature = "package_json_raw_json_api")]
+            package_json_removed_fields: vec![
+                "description".into(),
+                "keywords".into(),
+                "scripts".into(),
+                "dependencies".into(),
+                "devDependencies".into(),
+                "peerDependencies".into(),
+                "optionalDependencies".into(),
+            ],
             resolve_to_context: false,
+            trailing_slash: TrailingSlash::Index,
             prefer_relative: false,
             prefer_absolute: false,
             restrictions: vec![],
+            allowed_roots: vec![],
             roots: vec![],
+            root_prefixes: None,
+            workspace_root: None,
+            alias_root: None,
+            dedupe: vec![],
+            dedupe_root: None,
+            strict_dependencies: None,
             symlinks: true,
+            symlink_escape: None,
+            externals: vec![],
+            specifier_rewrites: vec![],
             builtin_modules: false,
+            builtin_modules_node_version: None,
+            fs_operation_budget: None,
+            disable_cache: false,
+        }
+    }
+}
+
+/// webpack / Rspack `resolve` configuration in the shape of [enhanced-resolve](https://github.com/webpack/enhanced-resolve#resolver-options).
+///
+/// Deserialize it from the `resolve` object of a webpack or Rspack configuration (camelCase keys)
+/// and convert it into [ResolveOptions] with [From]:
+///
+/// ```
+/// use rspack_resolver::{EnhancedResolveOptions, ResolveOptions};
+///
+/// let json = r#"{ "alias": { "fs": false }, "extensions": [".ts", "..."] }"#;
+/// let options: EnhancedResolveOptions = serde_json::from_str(json).unwrap();
+/// let options = ResolveOptions::from(options);
+/// assert_eq!(options.extensions, vec![".ts", ".js", ".json", ".node"]);
+/// ```
+///
+/// Missing fields fall back to [ResolveOptions::default].
+/// The `"..."` item in list options is expanded to the default values of that option.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnhancedResolveOptions {
+    /// `{ [key]: string | false | (string | false)[] }` or `{ name, alias, onlyModule }[]`
+    pub alias: Option<EnhancedResolveAlias>,
+    /// `(string | string[])[]`
+    pub alias_fields: Option<Vec<EnhancedResolveFieldPath>>,
+    pub builtin_modules: Option<bool>,
+    pub condition_names: Option<Vec<String>>,
+    pub description_files: Option<Vec<String>>,
+    /// `true` maps to [EnforceExtension::Enabled] and `false` maps to [EnforceExtension::Disabled].
+    pub enforce_extension: Option<bool>,
+    /// `(string | string[])[]`
+    pub exports_fields: Option<Vec<EnhancedResolveFieldPath>>,
+    /// `{ [extension]: string | string[] }`
+    pub extension_alias: Option<IndexMap<String, EnhancedResolveStrOrStrList>>,
+    pub extensions: Option<Vec<String>>,
+    /// Same shape as `alias`.
+    pub fallback: Option<EnhancedResolveAlias>,
+    pub fully_specified: Option<bool>,
+    /// `(string | string[])[]`
+    pub imports_fields: Option<Vec<EnhancedResolveFieldPath>>,
+    /// `string | (string | string[])[]`
+    pub main_fields: Option<EnhancedResolveFieldPathList>,
+    /// `string | string[]`
+    pub main_files: Option<EnhancedResolveStrOrStrList>,
+    /// `string | string[]`
+    pub modules: Option<EnhancedResolveStrOrStrList>,
+    /// Rspack `resolve.pnp`
+    pub pnp: Option<bool>,
+    pub prefer_absolute: Option<bool>,
+    pub prefer_relative: Option<bool>,
+    pub resolve_to_context: Option<bool>,
+    /// `string[]`, restrictions by path
+    pub restrictions: Option<Vec<PathBuf>>,
+    pub roots: Option<Vec<PathBuf>>,
+    pub symlinks: Option<bool>,
+    /// Rspack `resolve.tsConfig`, `string | { configFile, references }`
+    #[serde(alias = "tsConfig")]
+    pub tsconfig: Option<EnhancedResolveTsconfig>,
+}
+
+/// Value for [EnhancedResolveOptions::alias] and [EnhancedResolveOptions::fallback]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum EnhancedResolveAlias {
+    /// `{ [key]: string | false | (string | false)[] }`
+    #[serde(deserialize_with = "deserialize_alias")]
+    Map(Alias),
+    /// `{ name, alias, onlyModule }[]`
+    List(Vec<EnhancedResolveAliasItem>),
+}
+
+/// An item of the list form of [EnhancedResolveAlias]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnhancedResolveAliasItem {
+    pub name: String,
+    pub alias: Vec<AliasValue>,
+    /// Only match the exact module name, same as a trailing `$` in the name.
+    #[serde(default)]
+    pub only_module: bool,
+}
+
+/// `string | string[]`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum EnhancedResolveStrOrStrList {
+    Str(String),
+    List(Vec<String>),
+}
+
+/// A field name `"browser"` or a path to a field `["path", "to", "field"]`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum EnhancedResolveFieldPath {
+    Field(String),
+    Path(Vec<String>),
+}
+
+/// `string | (string | string[])[]`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum EnhancedResolveFieldPathList {
+    Field(String),
+    List(Vec<EnhancedResolveFieldPath>),
+}
+
+/// Value for [EnhancedResolveOptions::tsconfig]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum EnhancedResolveTsconfig {
+    /// Path to the configuration file
+    ConfigFile(PathBuf),
+    /// `{ configFile, references?: "auto" | string[], content?: string | object }`
+    Options {
+        #[serde(rename = "configFile")]
+        config_file: PathBuf,
+        #[serde(default)]
+        references: TsconfigReferences,
+        #[serde(default, deserialize_with = "deserialize_tsconfig_content")]
+        content: Option<String>,
+    },
+}
+
+impl From<EnhancedResolveAlias> for Alias {
+    fn from(alias: EnhancedResolveAlias) -> Self {
+        match alias {
+            EnhancedResolveAlias::Map(alias) => alias,
+            EnhancedResolveAlias::List(items) => items
+                .into_iter()
+                .map(|item| {
+                    let name = if item.only_module && !item.name.ends_with('$') {
+                        format!("{}$", item.name)
+                    } else {
+                        item.name
+                    };
+                    (name, item.alias)
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<EnhancedResolveStrOrStrList> for Vec<String> {
+    fn from(value: EnhancedResolveStrOrStrList) -> Self {
+        match value {
+            EnhancedResolveStrOrStrList::Str(s) => vec![s],
+            EnhancedResolveStrOrStrList::List(list) => list,
+        }
+    }
+}
+
+impl From<EnhancedResolveFieldPath> for Vec<String> {
+    fn from(value: EnhancedResolveFieldPath) -> Self {
+        match value {
+            EnhancedResolveFieldPath::Field(field) => vec![field],
+            EnhancedResolveFieldPath::Path(path) => path,
+        }
+    }
+}
+
+/// A path such as `["spm", "main"]` is joined into the [ResolveOptions::main_fields] item `"spm.main"`.
+impl From<EnhancedResolveFieldPathList> for Vec<String> {
+    fn from(value: EnhancedResolveFieldPathList) -> Self {
+        match value {
+            EnhancedResolveFieldPathList::Field(field) => vec![field],
+            EnhancedResolveFieldPathList::List(list) => {
+                list.into_iter().map(|path| Self::from(path).join(".")).collect()
+            }
+        }
+    }
+}
+
+impl From<EnhancedResolveTsconfig> for TsconfigOptions {
+    fn from(value: EnhancedResolveTsconfig) -> Self {
+        match value {
+            EnhancedResolveTsconfig::ConfigFile(config_file) => {
+                Self { config_file, references: TsconfigReferences::Disabled, content: None }
+            }
+            EnhancedResolveTsconfig::Options { config_file, references, content } => {
+                Self { config_file, references, content }
+            }
+        }
+    }
+}
+
+impl From<EnhancedResolveOptions> for ResolveOptions {
+    fn from(options: EnhancedResolveOptions) -> Self {
+        fn expand(values: Option<Vec<String>>, defaults: Vec<String>) -> Vec<String> {
+            match values {
+                Some(values) => expand_spread(values, defaults),
+                None => defaults,
+            }
+        }
+        fn field_paths(paths: Vec<EnhancedResolveFieldPath>) -> Vec<Vec<String>> {
+            paths.into_iter().map(Into::into).collect()
+        }
+
+        let default = Self::default();
+        Self {
+            tsconfig: options.tsconfig.map(Into::into).or(default.tsconfig),
+            tsconfig_scopes: default.tsconfig_scopes,
+            tsconfig_discovery: default.tsconfig_discovery,
+            tsconfig_paths_precedence: default.tsconfig_paths_precedence,
+            alias: options.alias.map_or(default.alias, Into::into),
+            regex_alias: default.regex_alias,
+            alias_skip_node_modules: default.alias_skip_node_modules,
+            alias_fields: options.alias_fields.map_or(default.alias_fields, field_paths),
+            alias_field_overrides: default.alias_field_overrides,
+            condition_names: expand(options.condition_names, default.condition_names),
+            description_files: options.description_files.unwrap_or(default.description_files),
+            jsonc_description_files: default.jsonc_description_files,
+            description_files_boundary: default.description_files_boundary,
+            #[cfg(feature = "yarn_pnp")]
+            enable_pnp: options.pnp.unwrap_or(default.enable_pnp),
+            #[cfg(feature = "yarn_pnp")]
+            pnp_loose: default.pnp_loose,
+            #[cfg(feature = "package_json_raw_json_api")]
+            package_json_removed_fields: default.package_json_removed_fields,
+            enforce_extension: options.enforce_extension.map_or(
+                default.enforce_extension,
+                |enforce| {
+                    if enforce {
+                        EnforceExtension::Enabled
+                    } else {
+                        EnforceExtension::Disabled
+                    }
+                },
+            ),
+            exports_fields: options.exports_fields.map_or(default.exports_fields, field_paths),
+            imports_fields: options.imports_fields.map_or(default.imports_fields, field_paths),
+            extension_alias: options.extension_alias.map_or(default.extension_alias, |map| {
+                map.into_iter().map(|(ext, exts)| (ext, exts.into())).collect()
+            }),
+            extensions: expand(options.extensions, default.extensions),
+            adaptive_extensions: default.adaptive_extensions,
+            directory_listing_threshold: default.directory_listing_threshold,
+            extension_infixes: default.extension_infixes,
+            native_addon_suffixes: default.native_addon_suffixes,
+            fallback: options.fallback.map_or(default.fallback, Into::into),
+            fallback_bare_only: default.fallback_bare_only,
+            fallback_options: default.fallback_options,
+            fully_specified: options.fully_specified.unwrap_or(default.fully_specified),
+            fragment_as_path: default.fragment_as_path,
+            forward_slashes: default.forward_slashes,
+            relative_path_base: default.relative_path_base,
+            main_fields: expand(options.main_fields.map(Into::into), default.main_fields),
+            main_files: expand(options.main_files.map(Into::into), default.main_files),
+            modules: expand(options.modules.map(Into::into), default.modules),
+            modules_boundary: default.modules_boundary,
+            modules_provider: default.modules_provider,
+            package_json_provider: default.package_json_provider,
+            tsconfig_provider: default.tsconfig_provider,
+            resolve_to_context: options.resolve_to_context.unwrap_or(default.resolve_to_context),
+            trailing_slash: default.trailing_slash,
+            prefer_relative: options.prefer_relative.unwrap_or(default.prefer_relative),
+            prefer_absolute: options.prefer_absolute.unwrap_or(default.prefer_absolute),
+            restrictions: options.restrictions.map_or(default.restrictions, |restrictions| {
+                restrictions.into_iter().map(Restriction::Path).collect()
+            }),
+            allowed_roots: default.allowed_roots,
+            roots: options.roots.unwrap_or(default.roots),
+            root_prefixes: default.root_prefixes,
+            workspace_root: default.workspace_root,
+            alias_root: default.alias_root,
+            dedupe: default.dedupe,
+            dedupe_root: default.dedupe_root,
+            strict_dependencies: default.strict_dependencies,
+            symlinks: options.symlinks.unwrap_or(default.symlinks),
+            symlink_escape: default.symlink_escape,
+            externals: default.externals,
+            specifier_rewrites: default.specifier_rewrites,
+            builtin_modules: options.builtin_modules.unwrap_or(default.builtin_modules),
+            builtin_modules_node_version: default.builtin_modules_node_version,
+            fs_operation_budget: default.fs_operation_budget,
+            disable_cache: default.disable_cache,
         }
     }
 }
 
 // For tracing
 impl fmt::Display for ResolveOptions {
+    // One branch per option.
+    #[allow(clippy::cognitive_complexity)]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         if let Some(tsconfig) = &self.tsconfig {
             write!(f, "tsconfig:{tsconfig:?},")?;
         }
+        if !self.tsconfig_scopes.is_empty() {
+            write!(f, "tsconfig_scopes:{:?},", self.tsconfig_scopes)?;
+        }
+        if self.tsconfig_discovery {
+            write!(f, "tsconfig_discovery:{:?},", self.tsconfig_discovery)?;
+        }
+        if self.tsconfig_paths_precedence != TsconfigPathsPrecedence::First {
+            write!(f, "tsconfig_paths_precedence:{:?},", self.tsconfig_paths_precedence)?;
+        }
+        if let Some(tsconfig_provider) = &self.tsconfig_provider {
+            write!(f, "tsconfig_provider:{tsconfig_provider:?},")?;
+        }
         if !self.alias.is_empty() {
             write!(f, "alias:{:?},", self.alias)?;
         }
+        if !self.regex_alias.is_empty() {
+            write!(f, "regex_alias:{:?},", self.regex_alias)?;
+        }
+        if self.alias_skip_node_modules {
+            write!(f, "alias_skip_node_modules:{:?},", self.alias_skip_node_modules)?;
+        }
         if !self.alias_fields.is_empty() {
             write!(f, "alias_fields:{:?},", self.alias_fields)?;
         }
+        if !self.alias_field_overrides.is_empty() {
+            write!(f, "alias_field_overrides:{:?},", self.alias_field_overrides)?;
+        }
         if !self.condition_names.is_empty() {
             write!(f, "condition_names:{:?},", self.condition_names)?;
         }
@@ -510,12 +1760,43 @@ impl fmt::Display for ResolveOptions {
         if !self.extensions.is_empty() {
             write!(f, "extensions:{:?},", self.extensions)?;
         }
+        if self.adaptive_extensions {
+            write!(f, "adaptive_extensions:{:?},", self.adaptive_extensions)?;
+        }
+        if let Some(directory_listing_threshold) = &self.directory_listing_threshold {
+            write!(f, "directory_listing_threshold:{directory_listing_threshold:?},")?;
+        }
+        if !self.extension_infixes.is_empty() {
+            write!(f, "extension_infixes:{:?},", self.extension_infixes)?;
+        }
+        if !self.native_addon_suffixes.is_empty() {
+            write!(f, "native_addon_suffixes:{:?},", self.native_addon_suffixes)?;
+        }
         if !self.fallback.is_empty() {
             write!(f, "fallback:{:?},", self.fallback)?;
         }
+        if self.fallback_bare_only {
+            write!(f, "fallback_bare_only:{:?},", self.fallback_bare_only)?;
+        }
+        if !self.fallback_options.is_empty() {
+            write!(f, "fallback_options:[")?;
+            for options in &self.fallback_options {
+                write!(f, "{{{options}}},")?;
+            }
+            write!(f, "],")?;
+        }
         if self.fully_specified {
             write!(f, "fully_specified:{:?},", self.fully_specified)?;
         }
+        if !self.fragment_as_path {
+            write!(f, "fragment_as_path:{:?},", self.fragment_as_path)?;
+        }
+        if self.forward_slashes {
+            write!(f, "forward_slashes:{:?},", self.forward_slashes)?;
+        }
+        if let Some(relative_path_base) = &self.relative_path_base {
+            write!(f, "relative_path_base:{relative_path_base:?},")?;
+        }
         if !self.main_fields.is_empty() {
             write!(f, "main_fields:{:?},", self.main_fields)?;
         }
@@ -525,9 +1806,24 @@ impl fmt::Display for ResolveOptions {
         if !self.modules.is_empty() {
             write!(f, "modules:{:?},", self.modules)?;
         }
+        if let Some(description_files_boundary) = &self.description_files_boundary {
+            write!(f, "description_files_boundary:{description_files_boundary:?},")?;
+        }
+        if let Some(modules_boundary) = &self.modules_boundary {
+            write!(f, "modules_boundary:{modules_boundary:?},")?;
+        }
+        if let Some(modules_provider) = &self.modules_provider {
+            write!(f, "modules_provider:{modules_provider:?},")?;
+        }
+        if let Some(package_json_provider) = &self.package_json_provider {
+            write!(f, "package_json_provider:{package_json_provider:?},")?;
+        }
         if self.resolve_to_context {
             write!(f, "resolve_to_context:{:?},", self.resolve_to_context)?;
         }
+        if self.trailing_slash != TrailingSlash::Index {
+            write!(f, "trailing_slash:{:?},", self.trailing_slash)?;
+        }
         if self.prefer_relative {
             write!(f, "prefer_relative:{:?},", self.prefer_relative)?;
         }
@@ -537,15 +1833,54 @@ impl fmt::Display for ResolveOptions {
         if !self.restrictions.is_empty() {
             write!(f, "restrictions:{:?},", self.restrictions)?;
         }
+        if !self.allowed_roots.is_empty() {
+            write!(f, "allowed_roots:{:?},", self.allowed_roots)?;
+        }
         if !self.roots.is_empty() {
             write!(f, "roots:{:?},", self.roots)?;
         }
+        if let Some(root_prefixes) = &self.root_prefixes {
+            write!(f, "root_prefixes:{root_prefixes:?},")?;
+        }
+        if let Some(workspace_root) = &self.workspace_root {
+            write!(f, "workspace_root:{workspace_root:?},")?;
+        }
+        if let Some(alias_root) = &self.alias_root {
+            write!(f, "alias_root:{alias_root:?},")?;
+        }
+        if !self.dedupe.is_empty() {
+            write!(f, "dedupe:{:?},", self.dedupe)?;
+        }
+        if let Some(dedupe_root) = &self.dedupe_root {
+            write!(f, "dedupe_root:{dedupe_root:?},")?;
+        }
+        if let Some(strict_dependencies) = &self.strict_dependencies {
+            write!(f, "strict_dependencies:{strict_dependencies:?},")?;
+        }
         if self.symlinks {
             write!(f, "symlinks:{:?},", self.symlinks)?;
         }
+        if let Some(symlink_escape) = &self.symlink_escape {
+            write!(f, "symlink_escape:{symlink_escape:?},")?;
+        }
+        if !self.externals.is_empty() {
+            write!(f, "externals:{:?},", self.externals)?;
+        }
+        if !self.specifier_rewrites.is_empty() {
+            write!(f, "specifier_rewrites:{:?},", self.specifier_rewrites)?;
+        }
         if self.builtin_modules {
             write!(f, "builtin_modules:{:?},", self.builtin_modules)?;
         }
+        if let Some(version) = &self.builtin_modules_node_version {
+            write!(f, "builtin_modules_node_version:{version:?},")?;
+        }
+        if let Some(fs_operation_budget) = &self.fs_operation_budget {
+            write!(f, "fs_operation_budget:{fs_operation_budget:?},")?;
+        }
+        if self.disable_cache {
+            write!(f, "disable_cache:{:?},", self.disable_cache)?;
+        }
         Ok(())
     }
 }
@@ -553,8 +1888,11 @@ impl fmt::Display for ResolveOptions {
 #[cfg(test)]
 mod test {
     use super::{
-        AliasValue, EnforceExtension, ResolveOptions, Restriction, TsconfigOptions,
-        TsconfigReferences,
+        parse_node_options_conditions, AliasFieldOverride, AliasValue, DescriptionFilesBoundary,
+        EnforceExtension, EnhancedResolveOptions, External, ModulesBoundary, NodeVersion,
+        OptionsWarning, Regex, ResolveOptions, Restriction, RootPrefixOptions, SpecifierRewrite,
+        SymlinkEscapeOptions, SymlinkEscapePolicy, TrailingSlash, TsconfigOptions,
+        TsconfigPathsPrecedence, TsconfigReferences,
     };
     use std::path::PathBuf;
 
@@ -573,61 +1911,313 @@ mod test {
         assert!(EnforceExtension::Disabled.is_disabled());
     }
 
+    #[test]
+    fn node_options_conditions() {
+        assert_eq!(
+            parse_node_options_conditions(
+                "--max-old-space-size=4096 --conditions=development -C production --conditions 'custom' -C=\"quoted\" -C"
+            ),
+            vec!["development", "production", "custom", "quoted"]
+        );
+        assert!(parse_node_options_conditions("--inspect --conditions=").is_empty());
+
+        let options = ResolveOptions::default()
+            .with_condition_names(&["import", "development"])
+            .with_node_options_conditions("--conditions=development -C custom");
+        assert_eq!(options.condition_names, vec!["import", "development", "custom"]);
+    }
+
+    #[test]
+    fn validate() {
+        let missing = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("missing");
+        let options = ResolveOptions {
+            condition_names: vec!["import".into(), "require".into()],
+            exports_fields: vec![vec![]],
+            extensions: vec![".js".into(), "ts".into(), String::new()],
+            extension_alias: vec![(".js".into(), vec!["mjs".into()])],
+            alias: vec![
+                ("a".into(), vec![AliasValue::Path(missing.to_string_lossy().into())]),
+                ("b".into(), vec![AliasValue::Path(env!("CARGO_MANIFEST_DIR").into())]),
+                ("c".into(), vec![AliasValue::Path("./relative".into()), AliasValue::Ignore]),
+            ],
+            fallback_options: vec![ResolveOptions {
+                alias_fields: vec![vec![]],
+                ..ResolveOptions::default()
+            }],
+            ..ResolveOptions::default()
+        };
+        assert_eq!(
+            options.validate(),
+            vec![
+                OptionsWarning::ConflictingConditions,
+                OptionsWarning::EmptyFieldPath("exports_fields"),
+                OptionsWarning::ExtensionWithoutLeadingDot("ts".into(), "extensions"),
+                OptionsWarning::ExtensionWithoutLeadingDot("mjs".into(), "extension_alias"),
+                OptionsWarning::AliasTargetNotFound("a".into(), missing),
+                OptionsWarning::EmptyFieldPath("alias_fields"),
+            ]
+        );
+        assert_eq!(
+            OptionsWarning::ExtensionWithoutLeadingDot("ts".into(), "extensions").to_string(),
+            "Extension 'ts' in extensions does not start with a leading dot"
+        );
+        assert!(ResolveOptions::default().validate().is_empty());
+    }
+
     #[test]
     fn display() {
         let options = ResolveOptions {
             tsconfig: Some(TsconfigOptions {
                 config_file: PathBuf::from("tsconfig.json"),
                 references: TsconfigReferences::Auto,
+                content: None,
             }),
+            tsconfig_scopes: vec![(
+                PathBuf::from("packages/a"),
+                TsconfigOptions {
+                    config_file: PathBuf::from("packages/a/tsconfig.json"),
+                    references: TsconfigReferences::Disabled,
+                    content: None,
+                },
+            )],
+            tsconfig_discovery: true,
+            tsconfig_paths_precedence: TsconfigPathsPrecedence::Fallback,
             alias: vec![("a".into(), vec![AliasValue::Ignore])],
+            regex_alias: vec![(Regex::new("^b-(.*)$").unwrap(), vec![AliasValue::Ignore])],
+            alias_skip_node_modules: true,
             alias_fields: vec![vec!["browser".into()]],
+            alias_field_overrides: vec![("ws".into(), AliasFieldOverride::Ignore)],
             condition_names: vec!["require".into()],
             enforce_extension: EnforceExtension::Enabled,
             extension_alias: vec![(".js".into(), vec![".ts".into()])],
+            adaptive_extensions: true,
+            directory_listing_threshold: Some(4),
+            extension_infixes: vec!["ios".into()],
+            native_addon_suffixes: vec!["linux-x64".into()],
             exports_fields: vec![vec!["exports".into()]],
             imports_fields: vec![vec!["imports".into()]],
             fallback: vec![("fallback".into(), vec![AliasValue::Ignore])],
+            fallback_bare_only: true,
+            fallback_options: vec![ResolveOptions {
+                condition_names: vec!["import".into()],
+                ..ResolveOptions::default()
+            }],
             fully_specified: true,
+            fragment_as_path: false,
+            forward_slashes: true,
+            relative_path_base: Some(PathBuf::from("relative_path_base")),
+            description_files_boundary: Some(DescriptionFilesBoundary::NodeModules),
+            modules_boundary: Some(ModulesBoundary::Git),
             resolve_to_context: true,
+            trailing_slash: TrailingSlash::Strict,
             prefer_relative: true,
             prefer_absolute: true,
             restrictions: vec![Restriction::Path(PathBuf::from("restrictions"))],
+            allowed_roots: vec![PathBuf::from("allowed_roots")],
             roots: vec![PathBuf::from("roots")],
+            root_prefixes: Some(RootPrefixOptions { tilde: true, slash: false, root: None }),
+            workspace_root: Some(PathBuf::from("workspace_root")),
+            alias_root: Some(PathBuf::from("alias_root")),
+            dedupe: vec!["react".into()],
+            dedupe_root: Some(PathBuf::from("dedupe_root")),
+            symlink_escape: Some(SymlinkEscapeOptions {
+                roots: vec![PathBuf::from("roots")],
+                policy: SymlinkEscapePolicy::Original,
+            }),
+            externals: vec![External::Exact("react".into()), External::Prefix("node:".into())],
+            specifier_rewrites: vec![SpecifierRewrite::Scheme("virtual".into())],
             builtin_modules: true,
+            builtin_modules_node_version: Some(NodeVersion::new(22, 5, 0)),
+            fs_operation_budget: Some(1000),
+            disable_cache: true,
             ..ResolveOptions::default()
         };
 
-        let expected = r#"tsconfig:TsconfigOptions { config_file: "tsconfig.json", references: Auto },alias:[("a", [Ignore])],alias_fields:[["browser"]],condition_names:["require"],enforce_extension:Enabled,exports_fields:[["exports"]],imports_fields:[["imports"]],extension_alias:[(".js", [".ts"])],extensions:[".js", ".json", ".node"],fallback:[("fallback", [Ignore])],fully_specified:true,main_fields:["main"],main_files:["index"],modules:["node_modules"],resolve_to_context:true,prefer_relative:true,prefer_absolute:true,restrictions:[Path("restrictions")],roots:["roots"],symlinks:true,builtin_modules:true,"#;
+        let expected = r#"tsconfig:TsconfigOptions { config_file: "tsconfig.json", references: Auto, content: None },tsconfig_scopes:[("packages/a", TsconfigOptions { config_file: "packages/a/tsconfig.json", references: Disabled, content: None })],tsconfig_discovery:true,tsconfig_paths_precedence:Fallback,alias:[("a", [Ignore])],regex_alias:[(Regex("^b-(.*)$"), [Ignore])],alias_skip_node_modules:true,alias_fields:[["browser"]],alias_field_overrides:[("ws", Ignore)],condition_names:["require"],enforce_extension:Enabled,exports_fields:[["exports"]],imports_fields:[["imports"]],extension_alias:[(".js", [".ts"])],extensions:[".js", ".json", ".node"],adaptive_extensions:true,directory_listing_threshold:4,extension_infixes:["ios"],native_addon_suffixes:["linux-x64"],fallback:[("fallback", [Ignore])],fallback_bare_only:true,fallback_options:[{condition_names:["import"],exports_fields:[["exports"]],imports_fields:[["imports"]],extensions:[".js", ".json", ".node"],main_fields:["main"],main_files:["index"],modules:["node_modules"],symlinks:true,},],fully_specified:true,fragment_as_path:false,forward_slashes:true,relative_path_base:"relative_path_base",main_fields:["main"],main_files:["index"],modules:["node_modules"],description_files_boundary:NodeModules,modules_boundary:Git,resolve_to_context:true,trailing_slash:Strict,prefer_relative:true,prefer_absolute:true,restrictions:[Path("restrictions")],allowed_roots:["allowed_roots"],roots:["roots"],root_prefixes:RootPrefixOptions { tilde: true, slash: false, root: None },workspace_root:"workspace_root",alias_root:"alias_root",dedupe:["react"],dedupe_root:"dedupe_root",symlinks:true,symlink_escape:SymlinkEscapeOptions { roots: ["roots"], policy: Original },externals:[Exact("react"), Prefix("node:")],specifier_rewrites:[Scheme("virtual")],builtin_modules:true,builtin_modules_node_version:NodeVersion { major: 22, minor: 5, patch: 0 },fs_operation_budget:1000,disable_cache:true,"#;
         assert_eq!(format!("{options}"), expected);
 
         let options = ResolveOptions {
             alias: vec![],
+            regex_alias: vec![],
+            alias_skip_node_modules: false,
             alias_fields: vec![],
+            alias_field_overrides: vec![],
             builtin_modules: false,
+            builtin_modules_node_version: None,
+            fs_operation_budget: None,
+            disable_cache: false,
             condition_names: vec![],
             description_files: vec![],
+            jsonc_description_files: vec![],
+            description_files_boundary: None,
             #[cfg(feature = "yarn_pnp")]
             enable_pnp: true,
+            #[cfg(feature = "yarn_pnp")]
+            pnp_loose: false,
+            #[cfg(feature = "package_json_raw_json_api")]
+            package_json_removed_fields: vec![],
             enforce_extension: EnforceExtension::Disabled,
             exports_fields: vec![],
             extension_alias: vec![],
             extensions: vec![],
+            adaptive_extensions: false,
+            directory_listing_threshold: None,
+            extension_infixes: vec![],
+            native_addon_suffixes: vec![],
             fallback: vec![],
+            fallback_bare_only: false,
+            fallback_options: vec![],
             fully_specified: false,
+            fragment_as_path: true,
+            forward_slashes: false,
+            relative_path_base: None,
             imports_fields: vec![],
             main_fields: vec![],
             main_files: vec![],
             modules: vec![],
+            modules_boundary: None,
+            modules_provider: None,
+            package_json_provider: None,
+            tsconfig_provider: None,
             prefer_absolute: false,
             prefer_relative: false,
             resolve_to_context: false,
+            trailing_slash: TrailingSlash::Index,
             restrictions: vec![],
+            allowed_roots: vec![],
             roots: vec![],
+            root_prefixes: None,
+            workspace_root: None,
+            alias_root: None,
+            dedupe: vec![],
+            dedupe_root: None,
+            strict_dependencies: None,
             symlinks: false,
+            symlink_escape: None,
+            externals: vec![],
+            specifier_rewrites: vec![],
             tsconfig: None,
+            tsconfig_scopes: vec![],
+            tsconfig_discovery: false,
+            tsconfig_paths_precedence: TsconfigPathsPrecedence::First,
         };
 
         assert_eq!(format!("{options}"), "");
     }
+
+    #[test]
+    fn deserialize() {
+        let json = r#"{
+            "tsconfig": { "config_file": "tsconfig.json", "references": "auto" },
+            "alias": { "a": "b", "c$": ["d", false] },
+            "alias_fields": ["browser", ["path", "to", "browser"]],
+            "alias_field_overrides": { "ws": false, "debug": { "./src/index.js": "./src/node.js" } },
+            "enforce_extension": "enabled",
+            "extension_alias": { ".js": [".ts", ".js"] },
+            "fallback": { "path": false },
+            "restrictions": [{ "path": "restrictions" }, { "regex": "\\.js$" }],
+            "roots": ["roots"],
+            "symlinks": false
+        }"#;
+        let options: ResolveOptions = serde_json::from_str(json).unwrap();
+        assert!(matches!(
+            options.tsconfig,
+            Some(TsconfigOptions { references: TsconfigReferences::Auto, .. })
+        ));
+        assert_eq!(
+            options.alias,
+            vec![
+                ("a".into(), vec![AliasValue::Path("b".into())]),
+                ("c$".into(), vec![AliasValue::Path("d".into()), AliasValue::Ignore]),
+            ]
+        );
+        assert_eq!(
+            options.alias_fields,
+            vec![vec!["browser".to_string()], vec!["path".into(), "to".into(), "browser".into()]]
+        );
+        assert_eq!(
+            options.alias_field_overrides,
+            vec![
+                ("ws".into(), AliasFieldOverride::Ignore),
+                (
+                    "debug".into(),
+                    AliasFieldOverride::Replace(vec![(
+                        "./src/index.js".into(),
+                        AliasValue::Path("./src/node.js".into())
+                    )])
+                ),
+            ]
+        );
+        assert_eq!(options.enforce_extension, EnforceExtension::Enabled);
+        assert_eq!(options.extension_alias, vec![(".js".into(), vec![".ts".into(), ".js".into()])]);
+        assert_eq!(options.fallback, vec![("path".into(), vec![AliasValue::Ignore])]);
+        assert!(matches!(options.restrictions[0], Restriction::Path(_)));
+        assert!(matches!(options.restrictions[1], Restriction::RegExp(_)));
+        assert_eq!(options.roots, vec![PathBuf::from("roots")]);
+        assert!(!options.symlinks);
+        // Missing fields use the default values
+        assert_eq!(options.extensions, ResolveOptions::default().extensions);
+        assert_eq!(options.main_fields, vec!["main".to_string()]);
+
+        let options: ResolveOptions = serde_json::from_str("{}").unwrap();
+        assert_eq!(format!("{options}"), format!("{}", ResolveOptions::default()));
+
+        assert!(serde_json::from_str::<ResolveOptions>(r#"{"alias":{"a":true}}"#).is_err());
+        assert!(serde_json::from_str::<ResolveOptions>(
+            r#"{"tsconfig":{"config_file":"a","references":"all"}}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn enhanced_resolve() {
+        let json = r#"{
+            "alias": [{ "name": "a", "alias": ["b"], "onlyModule": true }, { "name": "c", "alias": [false] }],
+            "aliasFields": ["browser"],
+            "enforceExtension": false,
+            "extensionAlias": { ".js": [".ts", ".js"], ".mjs": ".mts" },
+            "extensions": [".ts", "..."],
+            "fallback": { "path": "path-browserify", "fs": false },
+            "mainFields": ["module", ["spm", "main"], "..."],
+            "modules": "node_modules",
+            "restrictions": ["restrictions"],
+            "tsConfig": { "configFile": "tsconfig.json", "references": "auto" }
+        }"#;
+        let options =
+            ResolveOptions::from(serde_json::from_str::<EnhancedResolveOptions>(json).unwrap());
+        assert_eq!(
+            options.alias,
+            vec![
+                ("a$".into(), vec![AliasValue::Path("b".into())]),
+                ("c".into(), vec![AliasValue::Ignore]),
+            ]
+        );
+        assert_eq!(options.alias_fields, vec![vec!["browser".to_string()]]);
+        assert_eq!(options.enforce_extension, EnforceExtension::Disabled);
+        assert_eq!(
+            options.extension_alias,
+            vec![
+                (".js".into(), vec![".ts".into(), ".js".into()]),
+                (".mjs".into(), vec![".mts".into()])
+            ]
+        );
+        assert_eq!(options.extensions, vec![".ts", ".js", ".json", ".node"]);
+        assert_eq!(
+            options.fallback,
+            vec![
+                ("path".into(), vec![AliasValue::Path("path-browserify".into())]),
+                ("fs".into(), vec![AliasValue::Ignore])
+            ]
+        );
+        assert_eq!(options.main_fields, vec!["module", "spm.main", "main"]);
+        assert_eq!(options.modules, vec!["node_modules"]);
+        assert!(matches!(options.restrictions[..], [Restriction::Path(_)]));
+        assert!(matches!(
+            options.tsconfig,
+            Some(TsconfigOptions { references: TsconfigReferences::Auto, .. })
+        ));
+
+        let options = ResolveOptions::from(EnhancedResolveOptions::default());
+        assert_eq!(format!("{options}"), format!("{}", ResolveOptions::default()));
+    }
 }
@@ -1,11 +1,21 @@
 //! package.json definitions
 //!
 //! Code related to export field are copied from [Parcel's resolver](https://github.com/parcel-bundler/parcel/blob/v2/packages/utils/node-resolver-rs/src/package_json.rs)
-use std::path::{Path, PathBuf};
+use std::{
+    borrow::Cow,
+    fmt,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
+use rustc_hash::FxHashMap;
 use serde_json::Value as JSONValue;
 
-use crate::{path::PathUtil, ResolveError};
+use crate::{
+    imports_exports::{ExportsField, ImportsExportsMap},
+    path::PathUtil,
+    Alias, AliasFieldOverride, AliasValue, ResolveError, ResolveOptions,
+};
 
 pub type JSONMap = serde_json::Map<String, JSONValue>;
 
@@ -24,6 +34,11 @@ pub struct PackageJson {
     /// <https://nodejs.org/api/packages.html#name>
     pub name: Option<String>,
 
+    /// The "version" field.
+    ///
+    /// <https://docs.npmjs.com/cli/configuring-npm/package-json#version>
+    pub version: Option<String>,
+
     /// The "type" field.
     ///
     /// <https://nodejs.org/api/packages.html#type>
@@ -34,56 +49,124 @@ pub struct PackageJson {
     /// <https://webpack.js.org/guides/tree-shaking>
     pub side_effects: Option<JSONValue>,
 
-    raw_json: std::sync::Arc<JSONValue>,
+    raw_json: Arc<JSONValue>,
+
+    /// The compiled top level "exports" field.
+    exports: Option<ExportsField>,
+
+    /// The compiled top level "imports" field.
+    imports: Option<ImportsExportsMap>,
+
+    /// The normalized top level "browser" field.
+    browser: Option<BrowserField>,
+
+    /// The Parcel style top level "alias" field.
+    alias: Option<Alias>,
 }
 
 impl PackageJson {
     /// # Panics
     /// # Errors
+    #[cfg_attr(not(feature = "package_json_raw_json_api"), allow(unused_variables))]
     pub(crate) fn parse(
         path: PathBuf,
         realpath: PathBuf,
         json: &str,
+        options: &ResolveOptions,
     ) -> Result<Self, serde_json::Error> {
+        #[cfg_attr(not(feature = "package_json_raw_json_api"), allow(unused_mut))]
         let mut raw_json: JSONValue = serde_json::from_str(json)?;
-        let mut package_json = Self::default();
 
+        // Remove large fields that are useless for pragmatic use.
+        #[cfg(feature = "package_json_raw_json_api")]
         if let Some(json_object) = raw_json.as_object_mut() {
-            // Remove large fields that are useless for pragmatic use.
-            #[cfg(feature = "package_json_raw_json_api")]
-            {
-                json_object.remove("description");
-                json_object.remove("keywords");
-                json_object.remove("scripts");
-                json_object.remove("dependencies");
-                json_object.remove("devDependencies");
-                json_object.remove("peerDependencies");
-                json_object.remove("optionalDependencies");
+            for field in &options.package_json_removed_fields {
+                // The dependency fields are read by `strict_dependencies`.
+                if options
+                    .strict_dependencies
+                    .as_ref()
+                    .is_some_and(|strict| strict.fields().any(|f| f == field))
+                {
+                    continue;
+                }
+                json_object.remove(field);
             }
+        }
+
+        Ok(Self::from_value(path, realpath, raw_json))
+    }
 
+    /// Creates a package.json from an already parsed JSON value,
+    /// see [crate::ResolveOptions::package_json_provider].
+    ///
+    /// `path` and `realpath` are paths to `package.json`, they are the same when the directory is not a symlink.
+    pub fn from_value(path: PathBuf, realpath: PathBuf, raw_json: JSONValue) -> Self {
+        let mut package_json = Self::default();
+
+        if let Some(json_object) = raw_json.as_object() {
             // Add name, type and sideEffects.
             package_json.name =
                 json_object.get("name").and_then(|field| field.as_str()).map(ToString::to_string);
+            package_json.version = json_object
+                .get("version")
+                .and_then(|field| field.as_str())
+                .map(ToString::to_string);
             package_json.r#type = json_object.get("type").cloned();
             package_json.side_effects = json_object.get("sideEffects").cloned();
+            package_json.exports = json_object.get("exports").map(ExportsField::new);
+            package_json.imports = json_object
+                .get("imports")
+                .and_then(JSONValue::as_object)
+                .map(ImportsExportsMap::new);
+            package_json.browser = json_object
+                .get("browser")
+                .and_then(JSONValue::as_object)
+                .map(|object| BrowserField::new(&path, object));
+            package_json.alias = json_object
+                .get("alias")
+                .and_then(JSONValue::as_object)
+                .map(|object| Self::parse_alias(&path, object));
         }
 
         package_json.path = path;
         package_json.realpath = realpath;
-        package_json.raw_json = std::sync::Arc::new(raw_json);
-        Ok(package_json)
+        package_json.raw_json = Arc::new(raw_json);
+        package_json
     }
 
-    fn get_value_by_path<'a>(
+    /// Relative values are resolved against the package.json directory, `false` ignores the module.
+    ///
+    /// <https://parceljs.org/features/dependency-resolution/#aliases>
+    fn parse_alias(path: &Path, object: &JSONMap) -> Alias {
+        let dir = path.parent().unwrap_or(path);
+        object
+            .iter()
+            .filter_map(|(key, value)| {
+                let value = match value {
+                    JSONValue::String(value)
+                        if value.starts_with("./") || value.starts_with("../") =>
+                    {
+                        AliasValue::Path(dir.normalize_with(value).to_string_lossy().to_string())
+                    }
+                    JSONValue::String(value) => AliasValue::Path(value.clone()),
+                    JSONValue::Bool(false) => AliasValue::Ignore,
+                    _ => return None,
+                };
+                Some((key.clone(), vec![value]))
+            })
+            .collect()
+    }
+
+    fn get_value_by_path<'a, S: AsRef<str>>(
         fields: &'a serde_json::Map<String, JSONValue>,
-        path: &[String],
+        path: &[S],
     ) -> Option<&'a JSONValue> {
         if path.is_empty() {
             return None;
         }
-        let mut value = fields.get(&path[0])?;
+        let mut value = fields.get(path[0].as_ref())?;
         for key in path.iter().skip(1) {
-            if let Some(inner_value) = value.as_object().and_then(|o| o.get(key)) {
+            if let Some(inner_value) = value.as_object().and_then(|o| o.get(key.as_ref())) {
                 value = inner_value;
             } else {
                 return None;
@@ -98,24 +181,129 @@ impl PackageJson {
     /// * getting the `sideEffects` field
     /// * query in <https://www.rspack.dev/config/module.html#ruledescriptiondata> - search on GitHub indicates query on the `type` field.
     ///
-    /// To reduce overall memory consumption, large fields that useless for pragmatic use are removed.
-    /// They are: `description`, `keywords`, `scripts`,
-    /// `dependencies` and `devDependencies`, `peerDependencies`, `optionalDependencies`.
+    /// To reduce overall memory consumption, large fields that useless for pragmatic use are removed,
+    /// see [crate::ResolveOptions::package_json_removed_fields].
     #[cfg(feature = "package_json_raw_json_api")]
     pub fn raw_json(&self) -> &std::sync::Arc<JSONValue> {
         &self.raw_json
     }
 
+    /// The value at a path of object keys, e.g. `&["rspack", "entry"]`, for reading custom fields.
+    ///
+    /// Returns `None` for an empty path.
+    /// With the `package_json_raw_json_api` feature, the large fields it removes, such as `dependencies`, are not available.
+    pub fn get_field(&self, path: &[&str]) -> Option<&JSONValue> {
+        self.raw_json.as_object().and_then(|json_object| Self::get_value_by_path(json_object, path))
+    }
+
+    /// Approximate number of bytes held by this package.json, including the parsed JSON document.
+    ///
+    /// The compiled "exports", "imports", "browser" and "alias" fields are estimated by their JSON values.
+    pub(crate) fn approximate_memory_usage(&self) -> usize {
+        fn json_size(value: &JSONValue) -> usize {
+            std::mem::size_of::<JSONValue>()
+                + match value {
+                    JSONValue::String(string) => string.capacity(),
+                    JSONValue::Array(values) => values.iter().map(json_size).sum(),
+                    JSONValue::Object(object) => {
+                        object.iter().map(|(key, value)| key.capacity() + json_size(value)).sum()
+                    }
+                    _ => 0,
+                }
+        }
+        let string_size = |string: &Option<String>| string.as_ref().map_or(0, String::capacity);
+        let compiled_fields = ["exports", "imports", "browser", "alias"]
+            .iter()
+            .filter_map(|field| self.raw_json.get(field))
+            .map(json_size)
+            .sum::<usize>();
+        std::mem::size_of::<Self>()
+            + self.path.capacity()
+            + self.realpath.capacity()
+            + string_size(&self.name)
+            + string_size(&self.version)
+            + self.r#type.as_ref().map_or(0, json_size)
+            + self.side_effects.as_ref().map_or(0, json_size)
+            + json_size(&self.raw_json)
+            + compiled_fields
+    }
+
     /// Directory to `package.json`
     ///
     /// # Panics
     ///
     /// * When the package.json path is misconfigured.
     pub fn directory(&self) -> &Path {
-        debug_assert!(self.realpath.file_name().is_some_and(|x| x == "package.json"));
+        // Other description files are allowed by `ResolveOptions::description_files`.
+        debug_assert!(self.realpath.file_name().is_some());
         self.realpath.parent().unwrap()
     }
 
+    /// The top level "exports" field, classified when parsed.
+    ///
+    /// <https://nodejs.org/api/packages.html#exports>
+    pub fn exports(&self) -> Option<&ExportsField> {
+        self.exports.as_ref()
+    }
+
+    /// The raw top level "exports" field, for consumers which still need the JSON value.
+    pub fn raw_exports(&self) -> Option<&JSONValue> {
+        self.raw_json.get("exports")
+    }
+
+    /// The executable named `bin_name` in the "bin" field.
+    ///
+    /// The string form defines a single executable named after the package without its scope.
+    ///
+    /// <https://docs.npmjs.com/cli/configuring-npm/package-json#bin>
+    pub(crate) fn bin(&self, bin_name: &str) -> Option<&str> {
+        match self.raw_json.get("bin")? {
+            JSONValue::String(path) => {
+                let name = self.name.as_deref()?;
+                let name = name.rsplit_once('/').map_or(name, |(_, name)| name);
+                (name == bin_name).then_some(path.as_str())
+            }
+            JSONValue::Object(map) => map.get(bin_name)?.as_str(),
+            _ => None,
+        }
+    }
+
+    /// Whether `package_name` is declared in one of the dependency `fields`, see [crate::ResolveOptions::strict_dependencies].
+    pub(crate) fn declares_dependency<'a>(
+        &self,
+        mut fields: impl Iterator<Item = &'a str>,
+        package_name: &str,
+    ) -> bool {
+        fields.any(|field| {
+            self.raw_json
+                .get(field)
+                .and_then(JSONValue::as_object)
+                .is_some_and(|dependencies| dependencies.contains_key(package_name))
+        })
+    }
+
+    /// The Parcel style "alias" field, see [crate::ResolveOptions::alias_root].
+    pub(crate) const fn alias(&self) -> Option<&Alias> {
+        self.alias.as_ref()
+    }
+
+    /// The globs of the "workspaces" field, either an array or the "packages" of an object.
+    ///
+    /// <https://docs.npmjs.com/cli/using-npm/workspaces>
+    pub(crate) fn workspaces(&self) -> Vec<&str> {
+        let workspaces = match self.raw_json.get("workspaces") {
+            Some(JSONValue::Array(workspaces)) => workspaces,
+            Some(JSONValue::Object(workspaces)) => {
+                match workspaces.get("packages").and_then(JSONValue::as_array) {
+                    Some(workspaces) => workspaces,
+                    None => return vec![],
+                }
+            }
+            _ => return vec![],
+        };
+        workspaces.iter().filter_map(JSONValue::as_str).collect()
+    }
+
     /// The "main" field defines the entry point of a package when imported by name via a node_modules lookup. Its value is a path.
     ///
     /// When a package has an "exports" field, this will take precedence over the "main" field when importing the package by name.
@@ -129,59 +317,93 @@ impl PackageJson {
     ) -> impl Iterator<Item = &'a str> + '_ {
         main_fields
             .iter()
-            .filter_map(|main_field| self.raw_json.get(main_field))
+            .filter_map(|main_field| {
+                self.raw_json.get(main_field).or_else(|| {
+                    // `"spm.main"` is the path `spm` > `main`.
+                    let fields = self.raw_json.as_object()?;
+                    let (field, rest) = main_field.split_once('.')?;
+                    rest.split('.').try_fold(fields.get(field)?, |value, key| value.get(key))
+                })
+            })
             .filter_map(|value| value.as_str())
     }
 
     /// The "exports" field allows defining the entry points of a package when imported by name loaded either via a node_modules lookup or a self-reference to its own name.
     ///
+    /// The top level "exports" field is compiled once when parsed,
+    /// other fields configured by [ResolveOptions::exports_fields] are compiled on demand.
+    ///
     /// <https://nodejs.org/api/packages.html#exports>
     pub(crate) fn exports_fields<'a>(
         &'a self,
         exports_fields: &'a [Vec<String>],
-    ) -> impl Iterator<Item = &'a JSONValue> + '_ {
+    ) -> impl Iterator<Item = Cow<'a, ExportsField>> + '_ {
         exports_fields.iter().filter_map(|object_path| {
+            if Self::is_top_level_field(object_path, "exports") {
+                return self.exports.as_ref().map(Cow::Borrowed);
+            }
             self.raw_json
                 .as_object()
                 .and_then(|json_object| Self::get_value_by_path(json_object, object_path))
+                .map(|value| Cow::Owned(ExportsField::new(value)))
         })
     }
 
     /// In addition to the "exports" field, there is a package "imports" field to create private mappings that only apply to import specifiers from within the package itself.
     ///
+    /// The top level "imports" field is compiled once when parsed,
+    /// other fields configured by [ResolveOptions::imports_fields] are compiled on demand.
+    ///
     /// <https://nodejs.org/api/packages.html#subpath-imports>
     pub(crate) fn imports_fields<'a>(
         &'a self,
         imports_fields: &'a [Vec<String>],
-    ) -> impl Iterator<Item = &'a JSONMap> + '_ {
+    ) -> impl Iterator<Item = Cow<'a, ImportsExportsMap>> + '_ {
         imports_fields.iter().filter_map(|object_path| {
+            if Self::is_top_level_field(object_path, "imports") {
+                return self.imports.as_ref().map(Cow::Borrowed);
+            }
             self.raw_json
                 .as_object()
                 .and_then(|json_object| Self::get_value_by_path(json_object, object_path))
                 .and_then(|value| value.as_object())
+                .map(|value| Cow::Owned(ImportsExportsMap::new(value)))
         })
     }
 
+    fn is_top_level_field(object_path: &[String], field: &str) -> bool {
+        matches!(object_path, [name] if name == field)
+    }
+
     /// The "browser" field is provided by a module author as a hint to javascript bundlers or component tools when packaging modules for client side use.
     /// Multiple values are configured by [ResolveOptions::alias_fields].
     ///
+    /// The top level "browser" field is normalized once when parsed,
+    /// other fields are normalized on demand.
+    ///
     /// <https://github.com/defunctzombie/package-browser-field-spec>
     fn browser_fields<'a>(
         &'a self,
         alias_fields: &'a [Vec<String>],
-    ) -> impl Iterator<Item = &'a JSONMap> + '_ {
+    ) -> impl Iterator<Item = Cow<'a, BrowserField>> + '_ {
         alias_fields.iter().filter_map(|object_path| {
+            if Self::is_top_level_field(object_path, "browser") {
+                return self.browser.as_ref().map(Cow::Borrowed);
+            }
             self.raw_json
                 .as_object()
                 .and_then(|json_object| Self::get_value_by_path(json_object, object_path))
                 // Only object is valid, all other types are invalid
                 // https://github.com/webpack/enhanced-resolve/blob/3a28f47788de794d9da4d1702a3a583d8422cd48/lib/AliasFieldPlugin.js#L44-L52
                 .and_then(|value| value.as_object())
+                .map(|object| Cow::Owned(BrowserField::new(&self.path, object)))
         })
     }
 
     /// Resolve the request string for this package.json by looking at the `browser` field.
     ///
+    /// An entry of `alias_field_overrides` matching the package name takes the place of the alias fields.
+    ///
     /// # Errors
     ///
     /// * Returns [ResolveError::Ignored] for `"path": false` in `browser` field.
@@ -190,30 +412,130 @@ impl PackageJson {
         path: &Path,
         request: Option<&str>,
         alias_fields: &'a [Vec<String>],
-    ) -> Result<Option<&str>, ResolveError> {
-        for object in self.browser_fields(alias_fields) {
-            if let Some(request) = request {
-                if let Some(value) = object.get(request) {
-                    return Self::alias_value(path, value);
+        alias_field_overrides: &'a [(String, AliasFieldOverride)],
+    ) -> Result<Option<Cow<'a, str>>, ResolveError> {
+        let alias_field_override = self.name.as_ref().and_then(|name| {
+            alias_field_overrides.iter().find(|(key, _)| key == name).map(|(_, value)| value)
+        });
+        match alias_field_override {
+            Some(AliasFieldOverride::Ignore) => return Ok(None),
+            Some(AliasFieldOverride::Replace(entries)) => {
+                let field = BrowserField::from_entries(
+                    &self.path,
+                    entries.iter().map(|(key, value)| {
+                        let value = match value {
+                            AliasValue::Path(value) => BrowserFieldValue::Alias(value.clone()),
+                            AliasValue::Ignore => BrowserFieldValue::Ignore,
+                        };
+                        (key, value)
+                    }),
+                );
+                return field.get(path, request).map_or(Ok(None), |value| {
+                    value
+                        .resolve(path)
+                        .map(|value| value.map(|value| Cow::Owned(value.to_string())))
+                });
+            }
+            None => {}
+        }
+        for field in self.browser_fields(alias_fields) {
+            match field {
+                Cow::Borrowed(field) => {
+                    if let Some(value) = field.get(path, request) {
+                        return value.resolve(path).map(|value| value.map(Cow::Borrowed));
+                    }
                 }
-            } else {
-                let dir = self.path.parent().unwrap();
-                for (key, value) in object {
-                    let joined = dir.normalize_with(key);
-                    if joined == path {
-                        return Self::alias_value(path, value);
+                Cow::Owned(field) => {
+                    if let Some(value) = field.get(path, request) {
+                        return value
+                            .resolve(path)
+                            .map(|value| value.map(|value| Cow::Owned(value.to_string())));
                     }
                 }
             }
         }
         Ok(None)
     }
+}
+
+/// Supplies package.json files the embedder has already loaded, see [crate::ResolveOptions::package_json_provider].
+pub trait PackageJsonProvider: Send + Sync {
+    /// Returns the package.json in `directory`, or `None` to read it from the file system.
+    fn package_json(&self, directory: &Path) -> Option<Arc<PackageJson>>;
+}
+
+impl<F: Fn(&Path) -> Option<Arc<PackageJson>> + Send + Sync> PackageJsonProvider for F {
+    fn package_json(&self, directory: &Path) -> Option<Arc<PackageJson>> {
+        self(directory)
+    }
+}
+
+impl fmt::Debug for dyn PackageJsonProvider {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("PackageJsonProvider")
+    }
+}
+
+/// A normalized alias field object, e.g. the "browser" field.
+#[derive(Debug, Clone, Default)]
+pub struct BrowserField {
+    /// Values keyed by the raw key, looked up by module specifiers.
+    specifiers: FxHashMap<String, BrowserFieldValue>,
+
+    /// Values keyed by the key resolved against the package.json directory, looked up by paths.
+    paths: FxHashMap<PathBuf, BrowserFieldValue>,
+}
+
+#[derive(Debug, Clone)]
+enum BrowserFieldValue {
+    /// `"./a.js": "./b.js"`
+    Alias(String),
+    /// `"./a.js": false`
+    Ignore,
+    /// All other values, which stop the lookup without a match.
+    Invalid,
+}
+
+impl BrowserField {
+    fn new(package_json_path: &Path, object: &JSONMap) -> Self {
+        Self::from_entries(
+            package_json_path,
+            object.iter().map(|(key, value)| {
+                let value = match value {
+                    JSONValue::String(value) => BrowserFieldValue::Alias(value.clone()),
+                    JSONValue::Bool(false) => BrowserFieldValue::Ignore,
+                    _ => BrowserFieldValue::Invalid,
+                };
+                (key, value)
+            }),
+        )
+    }
+
+    fn from_entries<'a>(
+        package_json_path: &Path,
+        entries: impl Iterator<Item = (&'a String, BrowserFieldValue)>,
+    ) -> Self {
+        let dir = package_json_path.parent().unwrap();
+        let mut field = Self::default();
+        for (key, value) in entries {
+            // The first key wins when multiple keys are normalized to the same path.
+            field.paths.entry(dir.normalize_with(key)).or_insert_with(|| value.clone());
+            field.specifiers.insert(key.clone(), value);
+        }
+        field
+    }
+
+    fn get(&self, path: &Path, request: Option<&str>) -> Option<&BrowserFieldValue> {
+        request.map_or_else(|| self.paths.get(path), |request| self.specifiers.get(request))
+    }
+}
 
-    fn alias_value<'a>(key: &Path, value: &'a JSONValue) -> Result<Option<&'a str>, ResolveError> {
-        match value {
-            JSONValue::String(value) => Ok(Some(value.as_str())),
-            JSONValue::Bool(b) if !b => Err(ResolveError::Ignored(key.to_path_buf())),
-            _ => Ok(None),
+impl BrowserFieldValue {
+    fn resolve(&self, key: &Path) -> Result<Option<&str>, ResolveError> {
+        match self {
+            Self::Alias(value) => Ok(Some(value.as_str())),
+            Self::Ignore => Err(ResolveError::Ignored(key.to_path_buf())),
+            Self::Invalid => Ok(None),
         }
     }
 }
@@ -3,10 +3,81 @@
 //! Code adapted from the following libraries
 //! * [path-absolutize](https://docs.rs/path-absolutize)
 //! * [normalize_path](https://docs.rs/normalize-path)
-use std::path::{Component, Path, PathBuf};
+use std::path::{Component, Path, PathBuf, Prefix};
 
 pub const SLASH_START: &[char; 2] = &['/', '\\'];
 
+/// Normalizes a path the way the resolver normalizes [crate::Resolution::path], without performing I/O.
+///
+/// Redundant separators, `.` and `..` are collapsed, Windows prefixes are kept and links are not resolved.
+pub fn normalize_path<P: AsRef<Path>>(path: P) -> PathBuf {
+    path.as_ref().normalize()
+}
+
+/// Joins `subpath` onto the normalized `base` the way the resolver joins relative specifiers, without performing I/O.
+///
+/// An absolute `subpath` is returned as is.
+pub fn normalize_path_with<B: AsRef<Path>, P: AsRef<Path>>(base: B, subpath: P) -> PathBuf {
+    base.as_ref().normalize_with(subpath)
+}
+
+/// Converts the separators of a Windows path to `/` for [crate::ResolveOptions::forward_slashes].
+///
+/// The `\\?\` prefix is dropped from drive and UNC paths because verbatim paths cannot use `/`,
+/// e.g. `\\?\C:\a` becomes `C:/a` and `\\?\UNC\server\share\a` becomes `//server/share/a`.
+pub fn to_forward_slashes(path: &str) -> String {
+    if let Some(unc) = path.strip_prefix(r"\\?\UNC\") {
+        return format!("//{}", unc.replace('\\', "/"));
+    }
+    let path = match path.strip_prefix(r"\\?\") {
+        Some(disk) if disk.as_bytes().get(1) == Some(&b':') => disk,
+        _ => path,
+    };
+    path.replace('\\', "/")
+}
+
+/// Returns `path` relative to the directory `base` for [crate::ResolveOptions::relative_path_base],
+/// with `..` for the components of `base` not shared with `path`, e.g. `../shared/index.js`.
+///
+/// Both paths are normalized. `None` when they are on different drives or UNC shares, or only one of them is absolute.
+pub fn relative_path(base: &Path, path: &Path) -> Option<PathBuf> {
+    fn split(path: &Path) -> (Option<Prefix<'_>>, Vec<Component<'_>>) {
+        let mut components = path.components().peekable();
+        let prefix = match components.peek() {
+            Some(Component::Prefix(prefix)) => Some(prefix.kind()),
+            _ => None,
+        };
+        (prefix, components.filter(|c| !matches!(c, Component::Prefix(_))).collect())
+    }
+    let (base_prefix, base) = split(base);
+    let (path_prefix, path) = split(path);
+    // `C:\` and `\\?\C:\` are the same drive, drive letters and UNC names are case-insensitive.
+    let same_prefix = match (base_prefix, path_prefix) {
+        (None, None) => true,
+        (
+            Some(Prefix::Disk(a) | Prefix::VerbatimDisk(a)),
+            Some(Prefix::Disk(b) | Prefix::VerbatimDisk(b)),
+        ) => a.eq_ignore_ascii_case(&b),
+        (
+            Some(Prefix::UNC(a_server, a_share) | Prefix::VerbatimUNC(a_server, a_share)),
+            Some(Prefix::UNC(b_server, b_share) | Prefix::VerbatimUNC(b_server, b_share)),
+        ) => a_server.eq_ignore_ascii_case(b_server) && a_share.eq_ignore_ascii_case(b_share),
+        (a, b) => a == b,
+    };
+    let is_absolute =
+        |components: &[Component<'_>]| components.first() == Some(&Component::RootDir);
+    if !same_prefix || is_absolute(&base) != is_absolute(&path) {
+        return None;
+    }
+    let common = base.iter().zip(&path).take_while(|(a, b)| a == b).count();
+    let mut relative = PathBuf::new();
+    for _ in common..base.len() {
+        relative.push("..");
+    }
+    relative.extend(&path[common..]);
+    Some(relative)
+}
+
 /// Extension trait to add path normalization to std's [`Path`].
 pub trait PathUtil {
     /// Normalize this path without performing I/O.
@@ -123,6 +194,39 @@ fn is_invalid_exports_target() {
     assert!(!Path::new("/").is_invalid_exports_target());
 }
 
+#[test]
+fn normalize_path_helpers() {
+    assert_eq!(normalize_path("/foo/.././bar//baz/"), Path::new("/bar/baz"));
+    assert_eq!(normalize_path_with("/foo/bar", "../baz/./a.js"), Path::new("/foo/baz/a.js"));
+    assert_eq!(normalize_path_with("/foo/bar", "/baz"), Path::new("/baz"));
+}
+
+#[test]
+fn forward_slashes() {
+    assert_eq!(to_forward_slashes(r"C:\project\index.js"), "C:/project/index.js");
+    assert_eq!(to_forward_slashes(r"\\?\C:\project\index.js"), "C:/project/index.js");
+    assert_eq!(to_forward_slashes(r"\\server\share\index.js"), "//server/share/index.js");
+    assert_eq!(to_forward_slashes(r"\\?\UNC\server\share\index.js"), "//server/share/index.js");
+    assert_eq!(to_forward_slashes("/project/index.js"), "/project/index.js");
+}
+
+#[test]
+fn relative() {
+    assert_eq!(relative_path(Path::new("/a/b"), Path::new("/a/b/c.js")), Some("c.js".into()));
+    assert_eq!(relative_path(Path::new("/a/b"), Path::new("/a/d/e.js")), Some("../d/e.js".into()));
+    assert_eq!(relative_path(Path::new("/a/b"), Path::new("/d.js")), Some("../../d.js".into()));
+    assert_eq!(relative_path(Path::new("/a/b"), Path::new("/a/b")), Some(PathBuf::new()));
+    assert_eq!(relative_path(Path::new("/a/b"), Path::new("a/b")), None);
+    if cfg!(windows) {
+        let relative = |base: &str, path: &str| relative_path(Path::new(base), Path::new(path));
+        assert_eq!(relative(r"C:\a\b", r"C:\a\c.js"), Some(r"..\c.js".into()));
+        assert_eq!(relative(r"C:\a", r"\\?\c:\a\c.js"), Some("c.js".into()));
+        assert_eq!(relative(r"C:\a", r"D:\a\c.js"), None);
+        assert_eq!(relative(r"\\server\share\a", r"\\SERVER\share\b.js"), Some(r"..\b.js".into()));
+        assert_eq!(relative(r"\\server\share\a", r"\\server\other\b.js"), None);
+    }
+}
+
 #[test]
 fn normalize() {
     assert_eq!(Path::new("/foo/.././foo/").normalize(), Path::new("/foo"));
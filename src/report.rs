@@ -0,0 +1,65 @@
+//! Roll-up of the failures of a batch resolution, see [crate::ResolverGeneric::not_found_report].
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use rustc_hash::FxHashMap;
+
+/// Failed resolutions grouped by missing package name and by importing directory.
+///
+/// Groups are sorted by count, most frequent first, then by key.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct NotFoundReport {
+    /// Number of entries not found.
+    pub total: usize,
+
+    /// Missing packages of bare specifiers, e.g. `lodash` for `lodash/get`.
+    pub by_package: Vec<(String, usize)>,
+
+    /// Directories the entries not found were resolved from.
+    pub by_directory: Vec<(PathBuf, usize)>,
+}
+
+impl NotFoundReport {
+    pub(crate) fn new<'a>(failures: impl Iterator<Item = (&'a Path, Option<&'a str>)>) -> Self {
+        let mut total = 0;
+        let mut by_package = FxHashMap::<&str, usize>::default();
+        let mut by_directory = FxHashMap::<&Path, usize>::default();
+        for (directory, package_name) in failures {
+            total += 1;
+            if let Some(package_name) = package_name {
+                *by_package.entry(package_name).or_default() += 1;
+            }
+            *by_directory.entry(directory).or_default() += 1;
+        }
+        Self {
+            total,
+            by_package: Self::sorted(by_package, ToString::to_string),
+            by_directory: Self::sorted(by_directory, Path::to_path_buf),
+        }
+    }
+
+    fn sorted<K: Ord + Copy, T>(
+        counts: FxHashMap<K, usize>,
+        to_owned: impl Fn(K) -> T,
+    ) -> Vec<(T, usize)> {
+        let mut counts = counts.into_iter().collect::<Vec<_>>();
+        counts.sort_unstable_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+        counts.into_iter().map(|(key, count)| (to_owned(key), count)).collect()
+    }
+}
+
+/// Displays one line per group, e.g. `3 not found in package 'lodash'`.
+impl fmt::Display for NotFoundReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} not found", self.total)?;
+        for (package_name, count) in &self.by_package {
+            writeln!(f, "  {count} not found in package '{package_name}'")?;
+        }
+        for (directory, count) in &self.by_directory {
+            writeln!(f, "  {count} not found from '{}'", directory.display())?;
+        }
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use crate::package_json::PackageJson;
+use crate::{package_json::PackageJson, ResolveError};
 use std::{
     fmt,
     path::{Path, PathBuf},
@@ -10,6 +10,9 @@ use std::{
 pub struct Resolution {
     pub(crate) path: PathBuf,
 
+    /// Hash of `path`.
+    pub(crate) path_hash: u64,
+
     /// path query `?query`, contains `?`.
     pub(crate) query: Option<String>,
 
@@ -17,6 +20,9 @@ pub struct Resolution {
     pub(crate) fragment: Option<String>,
 
     pub(crate) package_json: Option<Arc<PackageJson>>,
+
+    /// `path` relative to [crate::ResolveOptions::relative_path_base].
+    pub(crate) relative_path: Option<PathBuf>,
 }
 
 impl fmt::Debug for Resolution {
@@ -26,7 +32,7 @@ impl fmt::Debug for Resolution {
             .field("query", &self.query)
             .field("fragment", &self.fragment)
             .field("package_json", &self.package_json.as_ref().map(|p| &p.path))
-            .finish()
+            .finish_non_exhaustive()
     }
 }
 
@@ -48,6 +54,14 @@ impl Resolution {
         self.path
     }
 
+    /// Returns a precomputed hash of [Resolution::path].
+    ///
+    /// Cheap to use as the key of a module graph instead of hashing the path again.
+    /// It is deterministic for the same path, but may change between versions of this crate.
+    pub fn path_hash(&self) -> u64 {
+        self.path_hash
+    }
+
     /// Returns the path query `?query`, contains the leading `?`
     pub fn query(&self) -> Option<&str> {
         self.query.as_deref()
@@ -63,9 +77,41 @@ impl Resolution {
         self.package_json.as_ref()
     }
 
+    /// Returns the `name` field of the closest package.json
+    pub fn package_name(&self) -> Option<&str> {
+        self.package_json.as_ref()?.name.as_deref()
+    }
+
+    /// Returns the directory of the closest package.json
+    pub fn package_root(&self) -> Option<&Path> {
+        self.package_json.as_deref().map(PackageJson::directory)
+    }
+
+    /// Returns the path relative to [Resolution::package_root], e.g. `dist/index.js`
+    pub fn package_relative_path(&self) -> Option<&Path> {
+        self.path.strip_prefix(self.package_root()?).ok()
+    }
+
+    /// Returns the path relative to [crate::ResolveOptions::relative_path_base] without query and fragment,
+    /// e.g. `../shared/index.js`.
+    ///
+    /// `None` when the option is not set or the path is on another drive.
+    pub fn relative_path(&self) -> Option<&Path> {
+        self.relative_path.as_deref()
+    }
+
+    /// Returns [Resolution::relative_path] with query and fragment
+    pub fn relative_full_path(&self) -> Option<PathBuf> {
+        self.relative_path.as_deref().map(|path| self.with_query_and_fragment(path))
+    }
+
     /// Returns the full path with query and fragment
     pub fn full_path(&self) -> PathBuf {
-        let mut path = self.path.clone().into_os_string();
+        self.with_query_and_fragment(&self.path)
+    }
+
+    fn with_query_and_fragment(&self, path: &Path) -> PathBuf {
+        let mut path = path.as_os_str().to_os_string();
         if let Some(query) = &self.query {
             path.push(query);
         }
@@ -74,19 +120,219 @@ impl Resolution {
         }
         PathBuf::from(path)
     }
+
+    /// Returns the path without query and fragment, `None` when it is not valid UTF-8.
+    #[cfg(feature = "camino")]
+    pub fn utf8_path(&self) -> Option<&camino::Utf8Path> {
+        camino::Utf8Path::from_path(&self.path)
+    }
+
+    /// Returns the full path with query and fragment, `None` when it is not valid UTF-8.
+    #[cfg(feature = "camino")]
+    pub fn utf8_full_path(&self) -> Option<camino::Utf8PathBuf> {
+        camino::Utf8PathBuf::from_path_buf(self.full_path()).ok()
+    }
+}
+
+/// The outcome of a resolution that did not fail.
+///
+/// Unlike [crate::ResolverGeneric::resolve], which reports ignored modules as
+/// [ResolveError::Ignored], ignored modules are an expected outcome here and are
+/// returned as [ResolveOutcome::Ignored].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolveOutcome {
+    /// Resolved to a file.
+    Resolved(Resolution),
+
+    /// Ignored by a `false` value in an alias field (e.g. the browser field) or an alias,
+    /// consumers should treat it as an empty module.
+    ///
+    /// Contains the path that was ignored.
+    Ignored(PathBuf),
+}
+
+impl ResolveOutcome {
+    /// Converts a [crate::ResolverGeneric::resolve] result, mapping [ResolveError::Ignored] to [ResolveOutcome::Ignored].
+    ///
+    /// # Errors
+    ///
+    /// * Any [ResolveError] other than [ResolveError::Ignored]
+    pub fn from_result(result: Result<Resolution, ResolveError>) -> Result<Self, ResolveError> {
+        match result {
+            Ok(resolution) => Ok(Self::Resolved(resolution)),
+            Err(ResolveError::Ignored(path)) => Ok(Self::Ignored(path)),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Converts back to the [crate::ResolverGeneric::resolve] result, where ignored modules are an error.
+    ///
+    /// # Errors
+    ///
+    /// * [ResolveError::Ignored] for [ResolveOutcome::Ignored]
+    pub fn into_result(self) -> Result<Resolution, ResolveError> {
+        match self {
+            Self::Resolved(resolution) => Ok(resolution),
+            Self::Ignored(path) => Err(ResolveError::Ignored(path)),
+        }
+    }
+
+    /// Returns the resolution, or `None` when the module is ignored.
+    pub fn resolution(&self) -> Option<&Resolution> {
+        match self {
+            Self::Resolved(resolution) => Some(resolution),
+            Self::Ignored(_) => None,
+        }
+    }
+
+    /// Returns `true` when the module is ignored.
+    pub const fn is_ignored(&self) -> bool {
+        matches!(self, Self::Ignored(_))
+    }
+}
+
+/// Serializes `path`, `query`, `fragment`, the path to the closest `package.json`,
+/// `module_type` (the "type" field in `package.json`) and `relative_path` when it is set.
+#[cfg(feature = "serialize")]
+impl serde::Serialize for Resolution {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let package_json = self.package_json.as_ref();
+        let mut state = serializer.serialize_struct("Resolution", 6)?;
+        state.serialize_field("path", &self.path)?;
+        state.serialize_field("query", &self.query)?;
+        state.serialize_field("fragment", &self.fragment)?;
+        state.serialize_field("package_json", &package_json.map(|p| &p.path))?;
+        state.serialize_field(
+            "module_type",
+            &package_json.and_then(|p| p.r#type.as_ref()).and_then(|t| t.as_str()),
+        )?;
+        if let Some(relative_path) = &self.relative_path {
+            state.serialize_field("relative_path", relative_path)?;
+        } else {
+            state.skip_field("relative_path")?;
+        }
+        state.end()
+    }
 }
 
 #[test]
 fn test() {
     let resolution = Resolution {
         path: PathBuf::from("foo"),
+        path_hash: 0,
         query: Some("?query".to_string()),
         fragment: Some("#fragment".to_string()),
         package_json: None,
+        relative_path: Some(PathBuf::from("../foo")),
     };
     assert_eq!(resolution.path(), Path::new("foo"));
     assert_eq!(resolution.query(), Some("?query"));
     assert_eq!(resolution.fragment(), Some("#fragment"));
     assert_eq!(resolution.full_path(), PathBuf::from("foo?query#fragment"));
+    assert_eq!(resolution.relative_path(), Some(Path::new("../foo")));
+    assert_eq!(resolution.relative_full_path(), Some(PathBuf::from("../foo?query#fragment")));
     assert_eq!(resolution.into_path_buf(), PathBuf::from("foo"));
 }
+
+#[test]
+fn package() {
+    let resolution = Resolution {
+        path: PathBuf::from("/node_modules/foo/dist/index.js"),
+        path_hash: 0,
+        query: None,
+        fragment: None,
+        package_json: Some(Arc::new(
+            PackageJson::parse(
+                PathBuf::from("/node_modules/foo/package.json"),
+                PathBuf::from("/node_modules/foo/package.json"),
+                r#"{"name":"foo"}"#,
+                &crate::ResolveOptions::default(),
+            )
+            .unwrap(),
+        )),
+        relative_path: None,
+    };
+    assert_eq!(resolution.package_name(), Some("foo"));
+    assert_eq!(resolution.package_root(), Some(Path::new("/node_modules/foo")));
+    assert_eq!(resolution.package_relative_path(), Some(Path::new("dist/index.js")));
+
+    let resolution = Resolution {
+        path: PathBuf::from("foo"),
+        path_hash: 0,
+        query: None,
+        fragment: None,
+        package_json: None,
+        relative_path: None,
+    };
+    assert_eq!(resolution.package_name(), None);
+    assert_eq!(resolution.package_root(), None);
+    assert_eq!(resolution.package_relative_path(), None);
+}
+
+#[test]
+fn outcome() {
+    let resolution = Resolution {
+        path: PathBuf::from("foo"),
+        path_hash: 0,
+        query: None,
+        fragment: None,
+        package_json: None,
+        relative_path: None,
+    };
+    let outcome = ResolveOutcome::from_result(Ok(resolution.clone())).unwrap();
+    assert!(!outcome.is_ignored());
+    assert_eq!(outcome.resolution(), Some(&resolution));
+    assert_eq!(outcome.into_result(), Ok(resolution));
+
+    let outcome = ResolveOutcome::from_result(Err(ResolveError::Ignored("foo".into()))).unwrap();
+    assert!(outcome.is_ignored());
+    assert_eq!(outcome.resolution(), None);
+    assert_eq!(outcome.into_result(), Err(ResolveError::Ignored("foo".into())));
+
+    let err = ResolveError::NotFound("foo".into());
+    assert_eq!(ResolveOutcome::from_result(Err(err.clone())), Err(err));
+}
+
+#[cfg(feature = "camino")]
+#[test]
+fn utf8() {
+    let resolution = Resolution {
+        path: PathBuf::from("/foo/index.js"),
+        path_hash: 0,
+        query: Some("?query".to_string()),
+        fragment: Some("#fragment".to_string()),
+        package_json: None,
+        relative_path: None,
+    };
+    assert_eq!(resolution.utf8_path(), Some(camino::Utf8Path::new("/foo/index.js")));
+    assert_eq!(
+        resolution.utf8_full_path(),
+        Some(camino::Utf8PathBuf::from("/foo/index.js?query#fragment"))
+    );
+}
+
+#[cfg(feature = "serialize")]
+#[test]
+fn serialize() {
+    let resolution = Resolution {
+        path: PathBuf::from("/foo/index.js"),
+        path_hash: 0,
+        query: Some("?query".to_string()),
+        fragment: None,
+        package_json: Some(Arc::new(
+            PackageJson::parse(
+                PathBuf::from("/foo/package.json"),
+                PathBuf::from("/foo/package.json"),
+                r#"{"type":"module"}"#,
+                &crate::ResolveOptions::default(),
+            )
+            .unwrap(),
+        )),
+        relative_path: None,
+    };
+    assert_eq!(
+        serde_json::to_string(&resolution).unwrap(),
+        r#"{"path":"/foo/index.js","query":"?query","fragment":null,"package_json":"/foo/package.json","module_type":"module"}"#
+    );
+}
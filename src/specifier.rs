@@ -1,18 +1,68 @@
 use crate::error::SpecifierError;
 use std::borrow::Cow;
 
+/// Escapes `#` and `?` in a path as `\0#` and `\0?`,
+/// so they are resolved as part of the path instead of starting a fragment or query.
+///
+/// <https://github.com/webpack/enhanced-resolve#escaping>
+pub fn escape_specifier(path: &str) -> Cow<'_, str> {
+    if !path.contains(['#', '?']) {
+        return Cow::Borrowed(path);
+    }
+    let mut escaped = String::with_capacity(path.len() + 2);
+    for c in path.chars() {
+        if matches!(c, '#' | '?') {
+            escaped.push('\0');
+        }
+        escaped.push(c);
+    }
+    Cow::Owned(escaped)
+}
+
+/// Removes the `\0` escaping `#` and `?`, the inverse of [escape_specifier].
+pub fn unescape_specifier(specifier: &str) -> Cow<'_, str> {
+    if !specifier.contains("\0#") && !specifier.contains("\0?") {
+        return Cow::Borrowed(specifier);
+    }
+    Cow::Owned(specifier.replace("\0#", "#").replace("\0?", "?"))
+}
+
+/// A specifier split into its path, query and fragment, the way the resolver splits it.
+///
+/// `\0` escapes a `#` or `?` belonging to the path, see [escape_specifier].
+///
+/// ```
+/// use rspack_resolver::Specifier;
+///
+/// let specifier = Specifier::parse("./a\0#b.js?query#fragment").unwrap();
+/// assert_eq!(specifier.path(), "./a#b.js");
+/// assert_eq!(specifier.query, Some("?query"));
+/// assert_eq!(specifier.fragment, Some("#fragment"));
+/// ```
 #[derive(Debug)]
 pub struct Specifier<'a> {
     path: Cow<'a, str>,
+
+    /// The query including the leading `?`.
     pub query: Option<&'a str>,
+
+    /// The fragment including the leading `#`.
     pub fragment: Option<&'a str>,
 }
 
 impl<'a> Specifier<'a> {
-    pub fn path(&'a self) -> &'a str {
+    /// The path with escaping `\0` removed.
+    pub fn path(&self) -> &str {
         self.path.as_ref()
     }
 
+    /// Splits `specifier` into path, query and fragment.
+    ///
+    /// A leading `#`, e.g. of a package import `#internal`, is part of the path.
+    ///
+    /// # Errors
+    ///
+    /// * [SpecifierError::Empty] when the specifier or its path is empty.
     pub fn parse(specifier: &'a str) -> Result<Self, SpecifierError> {
         if specifier.is_empty() {
             return Err(SpecifierError::Empty(specifier.to_string()));
@@ -39,7 +89,11 @@ impl<'a> Specifier<'a> {
         let mut escaped_indexes = vec![];
         for (i, c) in specifier.char_indices().skip(skip) {
             if c == '?' && query_start.is_none() {
-                query_start = Some(i);
+                if prev == '\0' {
+                    escaped_indexes.push(i - 1);
+                } else {
+                    query_start = Some(i);
+                }
             }
             if c == '#' {
                 if prev == '\0' {
@@ -67,8 +121,7 @@ impl<'a> Specifier<'a> {
         } else {
             // Remove the `\0` characters for a legal path.
             Cow::Owned(
-                path.chars()
-                    .enumerate()
+                path.char_indices()
                     .filter_map(|(i, c)| (!escaped_indexes.contains(&i)).then_some(c))
                     .collect::<String>(),
             )
@@ -80,7 +133,7 @@ impl<'a> Specifier<'a> {
 
 #[cfg(test)]
 mod tests {
-    use super::{Specifier, SpecifierError};
+    use super::{escape_specifier, unescape_specifier, Specifier, SpecifierError};
 
     #[test]
     fn debug() {
@@ -227,4 +280,30 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn escape() -> Result<(), SpecifierError> {
+        let data = [
+            ("./a.js", "./a.js"),
+            ("./a#b.js", "./a\0#b.js"),
+            ("./a?b.js", "./a\0?b.js"),
+            ("./测试#?.js", "./测试\0#\0?.js"),
+        ];
+
+        for (path, escaped) in data {
+            assert_eq!(escape_specifier(path), escaped);
+            assert_eq!(unescape_specifier(escaped), path);
+            let specifier = Specifier::parse(escaped)?;
+            assert_eq!(specifier.path, path);
+            assert_eq!(specifier.query, None);
+            assert_eq!(specifier.fragment, None);
+        }
+
+        let specifier = Specifier::parse("./a\0?b.js?query#fragment")?;
+        assert_eq!(specifier.path, "./a?b.js");
+        assert_eq!(specifier.query, Some("?query"));
+        assert_eq!(specifier.fragment, Some("#fragment"));
+
+        Ok(())
+    }
 }
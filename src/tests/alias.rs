@@ -3,7 +3,10 @@
 use normalize_path::NormalizePath;
 use std::path::Path;
 
-use crate::{AliasValue, Resolution, ResolveContext, ResolveError, ResolveOptions, Resolver};
+use crate::{
+    AliasValue, Resolution, ResolveAttempt, ResolveAttemptReason, ResolveContext, ResolveError,
+    ResolveOptions, Resolver,
+};
 
 #[test]
 #[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
@@ -282,3 +285,158 @@ fn alias_try_fragment_as_path() {
     let resolution = resolver.resolve(&f, "#/a").map(|r| r.full_path());
     assert_eq!(resolution, Ok(f.join("#").join("a.js")));
 }
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn root_package_alias() {
+    use super::memory_fs::MemoryFS;
+    use crate::ResolverGeneric;
+    use std::path::PathBuf;
+
+    let f = Path::new("/app/src");
+
+    let file_system = MemoryFS::new(&[
+        (
+            "/app/package.json",
+            r#"{ "alias": { "react": "preact/compat", "fs": false, "utils": "./src/utils.js" } }"#,
+        ),
+        ("/app/src/utils.js", ""),
+        ("/app/node_modules/preact/compat/index.js", ""),
+        ("/app/node_modules/react/index.js", ""),
+        ("/app/node_modules/other/index.js", ""),
+    ]);
+
+    let resolver = ResolverGeneric::<MemoryFS>::new_with_file_system(
+        file_system,
+        ResolveOptions {
+            alias_root: Some(PathBuf::from("/app")),
+            alias: vec![("react".into(), vec![AliasValue::Path("other".into())])],
+            ..ResolveOptions::default()
+        },
+    );
+
+    #[rustfmt::skip]
+    let pass = [
+        ("before options alias", "react", "/app/node_modules/preact/compat/index.js"),
+        ("relative to package.json", "utils", "/app/src/utils.js"),
+    ];
+
+    for (comment, request, expected) in pass {
+        let resolved_path = resolver.resolve(f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(PathBuf::from(expected)), "{comment} {request}");
+    }
+
+    assert_eq!(resolver.resolve(f, "fs"), Err(ResolveError::Ignored(f.join("fs"))));
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn alias_skip_node_modules() {
+    use super::memory_fs::MemoryFS;
+    use crate::ResolverGeneric;
+    use std::path::PathBuf;
+
+    let file_system = MemoryFS::new(&[
+        ("/app/node_modules/react/index.js", ""),
+        ("/app/node_modules/preact/compat.js", ""),
+        ("/app/node_modules/lib/index.js", ""),
+    ]);
+    let resolver = ResolverGeneric::<MemoryFS>::new_with_file_system(
+        file_system,
+        ResolveOptions {
+            alias: vec![("react".into(), vec![AliasValue::from("preact/compat")])],
+            alias_skip_node_modules: true,
+            ..ResolveOptions::default()
+        },
+    );
+
+    // Application code is aliased.
+    let resolution = resolver.resolve(Path::new("/app/src"), "react").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(PathBuf::from("/app/node_modules/preact/compat.js")));
+
+    // Third-party packages are not.
+    let resolution =
+        resolver.resolve(Path::new("/app/node_modules/lib"), "react").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(PathBuf::from("/app/node_modules/react/index.js")));
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn regex_alias() {
+    use super::memory_fs::MemoryFS;
+    use crate::{Regex, ResolverGeneric};
+    use std::path::PathBuf;
+
+    let file_system = MemoryFS::new(&[
+        ("/app/src/features/auth/index.ts", ""),
+        ("/app/src/legacy/button.js", ""),
+    ]);
+    let resolver = ResolverGeneric::<MemoryFS>::new_with_file_system(
+        file_system,
+        ResolveOptions {
+            extensions: vec![".js".into()],
+            regex_alias: vec![
+                (
+                    Regex::new("^@features/(.*)$").unwrap(),
+                    vec![AliasValue::from("./features/$1/index.ts")],
+                ),
+                (
+                    Regex::new("^legacy-(?<name>.*)$").unwrap(),
+                    vec![
+                        AliasValue::from("./missing/${name}"),
+                        AliasValue::from("./legacy/${name}"),
+                    ],
+                ),
+                (Regex::new("^ignored-.*$").unwrap(), vec![AliasValue::Ignore]),
+            ],
+            ..ResolveOptions::default()
+        },
+    );
+
+    let f = Path::new("/app/src");
+    let resolution = resolver.resolve(f, "@features/auth").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(PathBuf::from("/app/src/features/auth/index.ts")));
+
+    let resolution = resolver.resolve(f, "legacy-button").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(PathBuf::from("/app/src/legacy/button.js")));
+
+    let resolution = resolver.resolve(f, "@features/missing");
+    assert_eq!(
+        resolution,
+        Err(ResolveError::MatchedAliasNotFound(
+            "@features/missing".into(),
+            "^@features/(.*)$".into()
+        ))
+    );
+
+    let resolution = resolver.resolve(f, "ignored-a");
+    assert_eq!(resolution, Err(ResolveError::Ignored(PathBuf::from("/app/src/ignored-a"))));
+
+    // Deserializes from an ordered object of patterns.
+    let options: ResolveOptions =
+        serde_json::from_str(r#"{ "regex_alias": { "^~/(.*)$": "./$1", "^x$": false } }"#).unwrap();
+    assert_eq!(options.regex_alias.len(), 2);
+    assert_eq!(options.regex_alias[0].0.as_str(), "^~/(.*)$");
+    assert!(serde_json::from_str::<ResolveOptions>(r#"{ "regex_alias": { "(": "x" } }"#).is_err());
+}
+
+#[test]
+fn ignored_attempt() {
+    let f = super::fixture();
+
+    let resolver = Resolver::new(ResolveOptions {
+        alias: vec![("ignored".into(), vec![AliasValue::Ignore])],
+        ..ResolveOptions::default()
+    });
+
+    let mut ctx = ResolveContext { attempts: Some(vec![]), ..ResolveContext::default() };
+    let resolution = resolver.resolve_with_context(&f, "ignored", &mut ctx);
+    assert_eq!(resolution, Err(ResolveError::Ignored(f.join("ignored"))));
+    assert_eq!(
+        ctx.attempts,
+        Some(vec![ResolveAttempt {
+            path: f.join("ignored"),
+            reason: ResolveAttemptReason::Ignored
+        }])
+    );
+}
@@ -0,0 +1,52 @@
+//! Tests for [crate::ResolverGeneric::resolve_bin]
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn resolve_bin() {
+    use super::memory_fs::MemoryFS;
+    use crate::{ResolveError, ResolveOptions, ResolverGeneric};
+    use std::path::{Path, PathBuf};
+
+    let f = Path::new("/app/src");
+
+    let file_system = MemoryFS::new(&[
+        ("/app/node_modules/single/package.json", r#"{ "name": "single", "bin": "./cli.js" }"#),
+        ("/app/node_modules/single/cli.js", ""),
+        (
+            "/app/node_modules/@scope/multi/package.json",
+            r#"{ "name": "@scope/multi", "bin": { "multi": "bin/multi.js", "other": "./bin/other.js", "missing": "./bin/missing.js" } }"#,
+        ),
+        ("/app/node_modules/@scope/multi/bin/multi.js", ""),
+        ("/app/node_modules/@scope/multi/bin/other.js", ""),
+        ("/app/node_modules/no-bin/package.json", r#"{ "name": "no-bin" }"#),
+    ]);
+
+    let resolver =
+        ResolverGeneric::<MemoryFS>::new_with_file_system(file_system, ResolveOptions::default());
+
+    #[rustfmt::skip]
+    let pass = [
+        ("string form", "single", None, "/app/node_modules/single/cli.js"),
+        ("string form with bin name", "single", Some("single"), "/app/node_modules/single/cli.js"),
+        ("map form defaults to the unscoped name", "@scope/multi", None, "/app/node_modules/@scope/multi/bin/multi.js"),
+        ("map form with bin name", "@scope/multi", Some("other"), "/app/node_modules/@scope/multi/bin/other.js"),
+    ];
+
+    for (comment, package_name, bin_name, expected) in pass {
+        let resolved_path = resolver.resolve_bin(f, package_name, bin_name);
+        assert_eq!(resolved_path, Ok(PathBuf::from(expected)), "{comment} {package_name}");
+    }
+
+    #[rustfmt::skip]
+    let fail = [
+        ("package not found", "missing", None, ResolveError::NotFound("missing".into())),
+        ("no bin field", "no-bin", None, ResolveError::BinNotFound("no-bin".into(), "/app/node_modules/no-bin/package.json".into())),
+        ("string form with another name", "single", Some("other"), ResolveError::BinNotFound("other".into(), "/app/node_modules/single/package.json".into())),
+        ("bin file not found", "@scope/multi", Some("missing"), ResolveError::NotFound("/app/node_modules/@scope/multi/bin/missing.js".into())),
+    ];
+
+    for (comment, package_name, bin_name, expected) in fail {
+        let resolution = resolver.resolve_bin(f, package_name, bin_name);
+        assert_eq!(resolution, Err(expected), "{comment} {package_name}");
+    }
+}
@@ -1,6 +1,9 @@
 //! <https://github.com/webpack/enhanced-resolve/blob/main/test/browserField.test.js>
 
-use crate::{AliasValue, ResolveError, ResolveOptions, Resolver};
+use crate::{
+    AliasValue, ResolveAttempt, ResolveAttemptReason, ResolveContext, ResolveError, ResolveOptions,
+    ResolveOutcome, Resolver,
+};
 
 #[test]
 fn ignore() {
@@ -150,6 +153,17 @@ fn crypto_js() {
 
     let resolved_path = resolver.resolve(f.join("crypto-js"), "crypto").map(|r| r.full_path());
     assert_eq!(resolved_path, Err(ResolveError::Ignored(f.join("crypto-js"))));
+
+    let outcome = resolver.resolve_outcome(f.join("crypto-js"), "crypto");
+    assert_eq!(outcome, Ok(ResolveOutcome::Ignored(f.join("crypto-js"))));
+
+    let mut ctx = ResolveContext { attempts: Some(vec![]), ..ResolveContext::default() };
+    let outcome = resolver.resolve_outcome_with_context(f.join("crypto-js"), "crypto", &mut ctx);
+    assert_eq!(outcome, Ok(ResolveOutcome::Ignored(f.join("crypto-js"))));
+    assert!(ctx.file_dependencies.contains(&f.join("crypto-js/package.json")));
+    let attempt =
+        ResolveAttempt { path: f.join("crypto-js"), reason: ResolveAttemptReason::Ignored };
+    assert_eq!(ctx.attempts, Some(vec![attempt]));
 }
 
 // https://github.com/webpack/webpack/blob/87660921808566ef3b8796f8df61bd79fc026108/test/cases/resolving/browser-field/index.js#L40-L43
@@ -187,3 +201,93 @@ fn with_query() {
     let resolved_path = resolver.resolve(&f, "./foo").map(|r| r.full_path());
     assert_eq!(resolved_path, Ok(f.join("lib").join("browser.js?query")));
 }
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn exports_field_result() {
+    use super::memory_fs::MemoryFS;
+    use crate::ResolverGeneric;
+    use std::path::PathBuf;
+
+    let file_system = MemoryFS::new(&[
+        (
+            "/node_modules/pkg/package.json",
+            r#"{
+                "exports": { ".": "./dist/node.js", "./sub": "./dist/sub.js" },
+                "browser": { "./dist/node.js": "./dist/browser.js", "./dist/sub.js": false }
+            }"#,
+        ),
+        ("/node_modules/pkg/dist/node.js", ""),
+        ("/node_modules/pkg/dist/browser.js", ""),
+        ("/node_modules/pkg/dist/sub.js", ""),
+    ]);
+    let resolver = ResolverGeneric::<MemoryFS>::new_with_file_system(
+        file_system,
+        ResolveOptions { alias_fields: vec![vec!["browser".into()]], ..ResolveOptions::default() },
+    );
+
+    // The alias field of the package is applied to the file selected by the exports field, as in enhanced-resolve.
+    let resolved_path = resolver.resolve("/", "pkg").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(PathBuf::from("/node_modules/pkg/dist/browser.js")));
+    assert_eq!(
+        resolver.resolve("/", "pkg/sub"),
+        Err(ResolveError::Ignored(PathBuf::from("/node_modules/pkg/dist/sub.js")))
+    );
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn alias_field_overrides() {
+    use super::memory_fs::MemoryFS;
+    use crate::{AliasFieldOverride, ResolverGeneric};
+    use std::path::{Path, PathBuf};
+
+    let f = Path::new("/app");
+    let resolve = |alias_field_overrides| {
+        let file_system = MemoryFS::new(&[
+            (
+                "/app/node_modules/broken/package.json",
+                r#"{ "name": "broken", "browser": { "./index.js": "./browser.js" } }"#,
+            ),
+            ("/app/node_modules/broken/index.js", ""),
+            ("/app/node_modules/broken/browser.js", ""),
+            ("/app/node_modules/broken/node.js", ""),
+        ]);
+        let resolver = ResolverGeneric::<MemoryFS>::new_with_file_system(
+            file_system,
+            ResolveOptions {
+                alias_fields: vec![vec!["browser".into()]],
+                alias_field_overrides,
+                ..ResolveOptions::default()
+            },
+        );
+        resolver.resolve(f, "broken").map(|r| r.full_path())
+    };
+
+    assert_eq!(resolve(vec![]), Ok(PathBuf::from("/app/node_modules/broken/browser.js")));
+    assert_eq!(
+        resolve(vec![("other".into(), AliasFieldOverride::Ignore)]),
+        Ok(PathBuf::from("/app/node_modules/broken/browser.js"))
+    );
+    assert_eq!(
+        resolve(vec![("broken".into(), AliasFieldOverride::Ignore)]),
+        Ok(PathBuf::from("/app/node_modules/broken/index.js"))
+    );
+    assert_eq!(
+        resolve(vec![(
+            "broken".into(),
+            AliasFieldOverride::Replace(vec![(
+                "./index.js".into(),
+                AliasValue::Path("./node.js".into())
+            )])
+        )]),
+        Ok(PathBuf::from("/app/node_modules/broken/node.js"))
+    );
+    assert_eq!(
+        resolve(vec![(
+            "broken".into(),
+            AliasFieldOverride::Replace(vec![("./index.js".into(), AliasValue::Ignore)])
+        )]),
+        Err(ResolveError::Ignored(PathBuf::from("/app/node_modules/broken/index.js")))
+    );
+}
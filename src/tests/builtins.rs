@@ -1,6 +1,8 @@
 use std::path::Path;
 
-use crate::{ResolveError, ResolveOptions, Resolver};
+use crate::{
+    is_nodejs_builtin, is_nodejs_builtin_in, NodeVersion, ResolveError, ResolveOptions, Resolver,
+};
 
 #[test]
 fn builtins_off() {
@@ -119,3 +121,59 @@ fn imports() {
         assert_eq!(resolved_path, Err(err));
     }
 }
+
+#[test]
+fn is_builtin() {
+    let resolver = Resolver::default();
+    assert!(!resolver.is_builtin("fs"));
+    assert!(!resolver.is_builtin("node:fs"));
+
+    let resolver = Resolver::new(ResolveOptions::default().with_builtin_modules(true));
+    assert!(resolver.is_builtin("fs"));
+    assert!(resolver.is_builtin("node:fs"));
+    assert!(resolver.is_builtin("fs/promises"));
+    assert!(!resolver.is_builtin("xxx"));
+
+    assert!(is_nodejs_builtin("fs"));
+    assert!(is_nodejs_builtin("node:fs"));
+    assert!(!is_nodejs_builtin("node:xxx"));
+    assert!(!is_nodejs_builtin("xxx"));
+}
+
+#[test]
+fn node_version() {
+    let options = |version: &str| ResolveOptions {
+        builtin_modules: true,
+        builtin_modules_node_version: Some(version.parse().unwrap()),
+        ..ResolveOptions::default()
+    };
+
+    let resolver = Resolver::new(options("22.5.0"));
+    assert!(resolver.is_builtin("node:sqlite"));
+    assert!(resolver.is_builtin("node:test"));
+    assert!(resolver.is_builtin("inspector/promises"));
+    assert!(resolver.is_builtin("fs"));
+    // Only available with the `node:` prefix.
+    assert!(!resolver.is_builtin("test"));
+    assert!(!resolver.is_builtin("node:xxx"));
+
+    let resolver = Resolver::new(options("v18"));
+    assert!(!resolver.is_builtin("node:sqlite"));
+    assert!(!resolver.is_builtin("inspector/promises"));
+    assert!(resolver.is_builtin("node:test"));
+    assert_eq!(
+        resolver.resolve(Path::new("/"), "node:test"),
+        Err(ResolveError::Builtin("node:test".into()))
+    );
+
+    assert!(is_nodejs_builtin_in("readline/promises", NodeVersion::new(17, 0, 0)));
+    assert!(!is_nodejs_builtin_in("readline/promises", NodeVersion::new(16, 20, 0)));
+
+    assert_eq!("22.5".parse(), Ok(NodeVersion::new(22, 5, 0)));
+    assert!("22.x".parse::<NodeVersion>().is_err());
+    assert!("".parse::<NodeVersion>().is_err());
+    assert!("1.2.3.4".parse::<NodeVersion>().is_err());
+    let options: ResolveOptions =
+        serde_json::from_str(r#"{ "builtin_modules_node_version": "20.12.1" }"#).unwrap();
+    assert_eq!(options.builtin_modules_node_version, Some(NodeVersion::new(20, 12, 1)));
+}
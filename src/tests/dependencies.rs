@@ -105,6 +105,36 @@ mod windows {
                 FxHashSet::from_iter(missing_dependencies.iter().map(PathBuf::from));
             assert_eq!(ctx.file_dependencies, file_dependencies, "{name}");
             assert_eq!(ctx.missing_dependencies, missing_dependencies, "{name}");
+            let description_dependencies = file_dependencies
+                .into_iter()
+                .filter(|path| path.ends_with("package.json"))
+                .collect::<FxHashSet<_>>();
+            assert_eq!(ctx.description_dependencies, description_dependencies, "{name}");
         }
     }
 }
+
+#[test]
+fn tsconfig_description_dependencies() {
+    use crate::{ResolveContext, ResolveOptions, Resolver, TsconfigOptions, TsconfigReferences};
+
+    let f = super::fixture_root().join("tsconfig/cases/extends-paths");
+    let resolver = Resolver::new(ResolveOptions {
+        tsconfig: Some(TsconfigOptions {
+            config_file: f.join("tsconfig.json"),
+            references: TsconfigReferences::Auto,
+            content: None,
+        }),
+        ..ResolveOptions::default()
+    });
+
+    let mut ctx = ResolveContext::default();
+    let resolved_path =
+        resolver.resolve_with_context(f.join("src"), "@/index", &mut ctx).map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(f.join("src/index.js")));
+    for tsconfig in [f.join("tsconfig.json"), f.join("tsconfig.base.json")] {
+        assert!(ctx.description_dependencies.contains(&tsconfig), "{tsconfig:?}");
+        assert!(ctx.file_dependencies.contains(&tsconfig), "{tsconfig:?}");
+    }
+    assert!(!ctx.description_dependencies.contains(&f.join("src/index.js")));
+}
@@ -2,7 +2,7 @@
 //!
 //! The huge exports field test cases are at the bottom of this file.
 
-use crate::{Ctx, PathUtil, ResolveError, ResolveOptions, Resolver};
+use crate::{Ctx, ExportsField, PathUtil, ResolveError, ResolveOptions, Resolver};
 use serde_json::json;
 use std::path::Path;
 
@@ -72,7 +72,7 @@ fn test_simple() {
         ("request ending with slash #3", f.clone(), "exports-field/lib/", ResolveError::PackagePathNotExported("./lib/".to_string(), p)),
         ("should throw error if target is invalid", f4, "exports-field", ResolveError::InvalidPackageTarget("./a/../b/../../pack1/index.js".to_string(), ".".to_string(), p4)),
         ("throw error if exports field is invalid", f.clone(), "invalid-exports-field", ResolveError::InvalidPackageConfig(f.join("node_modules/invalid-exports-field/package.json"))),
-        ("should throw error if target is 'null'", f5, "m/features/internal/file.js", ResolveError::PackagePathNotExported("./features/internal/file.js".to_string(), p5)),
+        ("should throw error if target is 'null'", f5, "m/features/internal/file.js", ResolveError::PackagePathBlocked("./features/internal/file.js".to_string(), "./features/internal/*".to_string(), p5)),
     ];
 
     for (comment, path, request, error) in fail {
@@ -280,6 +280,45 @@ fn extension_alias_throw_error() {
     }
 }
 
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn warnings() {
+    use super::memory_fs::MemoryFS;
+    use crate::{ResolveContext, ResolverGeneric};
+    use std::path::PathBuf;
+
+    let file_system = MemoryFS::new(&[
+        (
+            "/node_modules/pkg/package.json",
+            r#"{ "exports": { "./dist/": "./lib/", "./main": ["../outside.js", "./main.js"] } }"#,
+        ),
+        ("/node_modules/pkg/lib/a.js", ""),
+        ("/node_modules/pkg/main.js", ""),
+    ]);
+    let resolver = ResolverGeneric::new_with_file_system(file_system, ResolveOptions::default());
+
+    let mut ctx = ResolveContext::default();
+    let resolution = resolver.resolve_with_context("/", "pkg/dist/a.js", &mut ctx);
+    assert_eq!(resolution.map(|r| r.full_path()), Ok(PathBuf::from("/node_modules/pkg/lib/a.js")));
+    assert_eq!(ctx.warnings, None);
+
+    let mut ctx = ResolveContext { warnings: Some(vec![]), ..ResolveContext::default() };
+    let resolution = resolver.resolve_with_context("/", "pkg/dist/a.js", &mut ctx);
+    assert_eq!(resolution.map(|r| r.full_path()), Ok(PathBuf::from("/node_modules/pkg/lib/a.js")));
+    assert_eq!(
+        ctx.warnings,
+        Some(vec![
+            r#"[DEP0148] Use of deprecated folder mapping "./dist/" in the "exports" field module resolution of the package at /node_modules/pkg/package.json"#.to_string()
+        ])
+    );
+
+    // Skipped array targets are traced, not reported as warnings.
+    let mut ctx = ResolveContext { warnings: Some(vec![]), ..ResolveContext::default() };
+    let resolution = resolver.resolve_with_context("/", "pkg/main", &mut ctx);
+    assert_eq!(resolution.map(|r| r.full_path()), Ok(PathBuf::from("/node_modules/pkg/main.js")));
+    assert_eq!(ctx.warnings, Some(vec![]));
+}
+
 // Small script for generating the test cases from enhanced-resolve
 // for (c of testCases) {
 //  console.log("TestCase {")
@@ -2522,7 +2561,7 @@ fn test_cases() {
         .package_exports_resolve(
             Path::new(""),
             case.request,
-            &case.exports_field,
+            &ExportsField::new(&case.exports_field),
             &mut Ctx::default(),
         )
         .map(|p| p.map(|p| p.to_path_buf()));
@@ -2544,3 +2583,44 @@ fn test_cases() {
         }
     }
 }
+
+#[test]
+fn identity_wildcard() {
+    let exports = ExportsField::new(&json!({
+        ".": "./index.js",
+        "./foo": "./bar.js",
+        "./*": "./*"
+    }));
+    assert!(matches!(&exports, ExportsField::Map(map) if map.is_identity_wildcard()));
+    let resolver = Resolver::default();
+    for (request, expected) in [
+        (".", Some("./index.js")),
+        ("./foo", Some("./bar.js")),
+        ("./x/y.js", Some("./x/y.js")),
+        ("./a*b", Some("./a*b")),
+        ("./dir/", None),
+    ] {
+        let path = resolver
+            .package_exports_resolve(Path::new(""), request, &exports, &mut Ctx::default())
+            .map(|p| p.map(|p| p.to_path_buf()));
+        match expected {
+            Some(expected) => {
+                assert_eq!(path, Ok(Some(Path::new(expected).normalize())), "{request}");
+            }
+            None => assert!(path.is_err(), "{request} {path:?}"),
+        }
+    }
+
+    for json in [
+        json!({"./*": "./*", "./lib/*": "./lib/*.js"}),
+        json!({"./*": "./dist/*"}),
+        json!({"./*": "./*", "./dir/": "./dir/"}),
+        json!("./index.js"),
+    ] {
+        let exports = ExportsField::new(&json);
+        assert!(
+            !matches!(&exports, ExportsField::Map(map) if map.is_identity_wildcard()),
+            "{json}"
+        );
+    }
+}
@@ -1,6 +1,8 @@
 //! <https://github.com/webpack/enhanced-resolve/blob/main/test/extension-alias.test.js>
 
-use crate::{ResolveError, ResolveOptions, Resolver};
+use crate::{
+    ResolveAttempt, ResolveAttemptReason, ResolveContext, ResolveError, ResolveOptions, Resolver,
+};
 
 #[test]
 fn extension_alias() {
@@ -59,3 +61,27 @@ fn not_apply_to_extension_nor_main_files() {
         assert_eq!(resolved_path, Ok(expected), "{comment} {path:?} {request}");
     }
 }
+
+#[test]
+fn extension_aliased_attempt() {
+    let f = super::fixture().join("extension-alias");
+
+    let resolver = Resolver::new(ResolveOptions {
+        extension_alias: vec![(".mjs".into(), vec![".mts".into()])],
+        ..ResolveOptions::default()
+    });
+
+    // `index.mts` is not found, and `index.mjs` exists but is not tried in its place.
+    let mut ctx = ResolveContext { attempts: Some(vec![]), ..ResolveContext::default() };
+    let resolution = resolver.resolve_with_context(&f, "./index.mjs", &mut ctx);
+    assert!(matches!(resolution, Err(ResolveError::ExtensionAlias(..))));
+    let attempts = ctx.attempts.unwrap();
+    let not_found =
+        ResolveAttempt { path: f.join("index.mts"), reason: ResolveAttemptReason::NotFound };
+    assert!(attempts.contains(&not_found));
+    let aliased = ResolveAttempt {
+        path: f.join("index.mjs"),
+        reason: ResolveAttemptReason::ExtensionAliased,
+    };
+    assert_eq!(attempts.last(), Some(&aliased));
+}
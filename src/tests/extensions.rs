@@ -60,6 +60,28 @@ fn default_enforce_extension() {
     assert!(ctx.missing_dependencies.is_empty());
 }
 
+#[test]
+fn spread_extensions() {
+    let f = super::fixture().join("extensions");
+
+    let resolver = Resolver::new(ResolveOptions {
+        extensions: vec![".ts".into(), "...".into()],
+        condition_names: vec!["...".into(), "node".into()],
+        main_fields: vec!["module".into(), "...".into()],
+        ..ResolveOptions::default()
+    });
+
+    let options = resolver.options();
+    assert_eq!(options.extensions, vec![".ts", ".js", ".json", ".node"]);
+    assert_eq!(options.condition_names, vec!["node"]);
+    assert_eq!(options.main_fields, vec!["module", "main"]);
+
+    let resolved_path = resolver.resolve(&f, "./foo").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(f.join("foo.ts")));
+    let resolved_path = resolver.resolve(&f, "./app.module").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(f.join("app.module.js")));
+}
+
 // should respect enforceExtension when extensions includes an empty string
 #[test]
 fn respect_enforce_extension() {
@@ -123,3 +145,265 @@ fn without_leading_dot() {
         ..ResolveOptions::default()
     });
 }
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn extension_infixes() {
+    use std::path::PathBuf;
+
+    use super::memory_fs::MemoryFS;
+    use crate::ResolverGeneric;
+
+    let file_system = MemoryFS::new(&[
+        ("/Button.ios.tsx", ""),
+        ("/Button.native.tsx", ""),
+        ("/Button.tsx", ""),
+        ("/Text.native.ts", ""),
+        ("/Text.tsx", ""),
+        ("/View.ts", ""),
+        ("/Image/index.native.ts", ""),
+    ]);
+    let resolver = ResolverGeneric::<MemoryFS>::new_with_file_system(
+        file_system,
+        ResolveOptions {
+            extensions: vec![".ts".into(), ".tsx".into()],
+            extension_infixes: vec!["ios".into(), "native".into()],
+            ..ResolveOptions::default()
+        },
+    );
+
+    #[rustfmt::skip]
+    let pass = [
+        ("infix before the plain extension", "./Button", "/Button.ios.tsx"),
+        ("infixes are tried per extension", "./Text", "/Text.native.ts"),
+        ("no infixed file", "./View", "/View.ts"),
+        ("index file", "./Image", "/Image/index.native.ts"),
+    ];
+
+    for (comment, request, expected) in pass {
+        let resolved_path = resolver.resolve("/", request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(PathBuf::from(expected)), "{comment} {request}");
+    }
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn native_addon_suffixes() {
+    use std::path::PathBuf;
+
+    use super::memory_fs::MemoryFS;
+    use crate::ResolverGeneric;
+
+    let file_system = MemoryFS::new(&[
+        ("/napi/binding.linux-x64-gnu.node", ""),
+        ("/napi/binding.darwin-arm64.node", ""),
+        ("/prebuildify/prebuilds/linux-x64/addon.node", ""),
+        ("/generic/addon.node", ""),
+        ("/generic/addon.linux-x64-gnu.node", ""),
+    ]);
+    let resolver = ResolverGeneric::<MemoryFS>::new_with_file_system(
+        file_system,
+        ResolveOptions {
+            native_addon_suffixes: vec!["linux-x64-gnu".into(), "linux-x64".into()],
+            ..ResolveOptions::default()
+        },
+    );
+
+    #[rustfmt::skip]
+    let pass = [
+        ("napi-rs", "./napi/binding.node", "/napi/binding.linux-x64-gnu.node"),
+        ("napi-rs without extension", "./napi/binding", "/napi/binding.linux-x64-gnu.node"),
+        ("prebuildify", "./prebuildify/addon.node", "/prebuildify/prebuilds/linux-x64/addon.node"),
+        ("generic name first", "./generic/addon.node", "/generic/addon.node"),
+    ];
+
+    for (comment, request, expected) in pass {
+        let resolved_path = resolver.resolve("/", request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(PathBuf::from(expected)), "{comment} {request}");
+    }
+
+    let resolver = ResolverGeneric::<MemoryFS>::new_with_file_system(
+        MemoryFS::new(&[("/napi/binding.linux-x64-gnu.node", "")]),
+        ResolveOptions::default(),
+    );
+    let resolved_path = resolver.resolve("/", "./napi/binding.node");
+    assert_eq!(resolved_path, Err(ResolveError::NotFound("./napi/binding.node".into())));
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn adaptive_extensions() {
+    use super::memory_fs::MemoryFS;
+    use crate::{cache::EXTENSION_ORDER_INTERVAL, ResolverGeneric};
+    use std::path::{Path, PathBuf};
+
+    let f = Path::new("/app");
+    let file_system = MemoryFS::new(&[
+        ("/app/foo.js", ""),
+        ("/app/foo.ts", ""),
+        ("/app/a.ts", ""),
+        ("/app/b.ts", ""),
+        ("/app/c.js", ""),
+    ]);
+    let resolver = ResolverGeneric::<MemoryFS>::new_with_file_system(
+        file_system,
+        ResolveOptions {
+            extensions: vec![".js".into(), ".ts".into()],
+            adaptive_extensions: true,
+            ..ResolveOptions::default()
+        },
+    );
+
+    // `.js` is tried first without hits.
+    let resolution = resolver.resolve(f, "./foo").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(PathBuf::from("/app/foo.js")));
+
+    for specifier in ["./a", "./b", "./a"] {
+        assert!(resolver.resolve(f, specifier).is_ok());
+    }
+    assert_eq!(
+        resolver.cache_stats().extension_hits,
+        vec![(".ts".to_string(), 3), (".js".to_string(), 1)]
+    );
+
+    // The order is only sorted again after `EXTENSION_ORDER_INTERVAL` hits.
+    let resolution = resolver.resolve(f, "./foo").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(PathBuf::from("/app/foo.js")));
+    for _ in 5..EXTENSION_ORDER_INTERVAL {
+        assert!(resolver.resolve(f, "./a").is_ok());
+    }
+
+    // `.ts` resolved more files, so it is tried first.
+    let resolution = resolver.resolve(f, "./foo").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(PathBuf::from("/app/foo.ts")));
+
+    resolver.clear_cache();
+    assert!(resolver.cache_stats().extension_hits.is_empty());
+
+    // Ties are broken alphabetically, for the stats and the probing order alike.
+    let resolver = resolver.clone_with_options(ResolveOptions {
+        extensions: vec![".ts".into(), ".js".into()],
+        adaptive_extensions: true,
+        ..ResolveOptions::default()
+    });
+    for _ in 0..EXTENSION_ORDER_INTERVAL / 2 {
+        assert!(resolver.resolve(f, "./a").is_ok());
+        assert!(resolver.resolve(f, "./c").is_ok());
+    }
+    let hits = EXTENSION_ORDER_INTERVAL / 2;
+    assert_eq!(
+        resolver.cache_stats().extension_hits,
+        vec![(".js".to_string(), hits), (".ts".to_string(), hits)]
+    );
+    let resolution = resolver.resolve(f, "./foo").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(PathBuf::from("/app/foo.js")));
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn directory_listing_threshold() {
+    use super::memory_fs::MemoryFS;
+    use crate::{FileMetadata, FileSystem, ResolverGeneric};
+    use std::{
+        io,
+        path::{Path, PathBuf},
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    struct CountingFS {
+        fs: MemoryFS,
+        metadata: AtomicUsize,
+    }
+
+    impl FileSystem for CountingFS {
+        fn read_to_string(&self, path: &Path) -> io::Result<String> {
+            self.fs.read_to_string(path)
+        }
+        fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+            self.fs.read(path)
+        }
+        fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+            self.metadata.fetch_add(1, Ordering::Relaxed);
+            self.fs.metadata(path)
+        }
+        fn symlink_metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+            self.fs.symlink_metadata(path)
+        }
+        fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+            self.fs.canonicalize(path)
+        }
+        fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+            self.fs.read_dir(path)
+        }
+    }
+
+    let f = Path::new("/app");
+    let resolve = |file: &'static str, request: &str, directory_listing_threshold| {
+        let file_system =
+            CountingFS { fs: MemoryFS::new(&[(file, "")]), metadata: AtomicUsize::new(0) };
+        let resolver = ResolverGeneric::<CountingFS>::new_with_file_system(
+            file_system,
+            ResolveOptions {
+                extensions: vec![".js".into(), ".jsx".into(), ".ts".into(), ".tsx".into()],
+                directory_listing_threshold,
+                ..ResolveOptions::default()
+            },
+        );
+        let mut ctx = ResolveContext::default();
+        let resolution = resolver.resolve_with_context(f, request, &mut ctx).map(|r| r.full_path());
+        (
+            resolution,
+            resolver.file_system().metadata.load(Ordering::Relaxed),
+            ctx.missing_dependencies,
+        )
+    };
+
+    let (resolution, metadata, missing_dependencies) = resolve("/app/foo.tsx", "./foo", None);
+    assert_eq!(resolution, Ok(PathBuf::from("/app/foo.tsx")));
+    // Fewer candidates than the threshold are looked up one by one.
+    assert_eq!(
+        resolve("/app/foo.tsx", "./foo", Some(5)),
+        (resolution.clone(), metadata, missing_dependencies.clone())
+    );
+
+    // `foo.js`, `foo.jsx` and `foo.ts` are not listed in `/app`.
+    let (listed_resolution, listed_metadata, listed_missing_dependencies) =
+        resolve("/app/foo.tsx", "./foo", Some(4));
+    assert_eq!(listed_resolution, resolution);
+    assert_eq!(listed_metadata, metadata - 3);
+    assert_eq!(listed_missing_dependencies, missing_dependencies);
+
+    // `Foo.tsx` is listed case-insensitively, so `foo.tsx` is looked up for case-insensitive file systems.
+    let (_, metadata, _) = resolve("/app/Foo.tsx", "./foo", None);
+    let (_, listed_metadata, _) = resolve("/app/Foo.tsx", "./foo", Some(4));
+    assert_eq!(listed_metadata, metadata - 3);
+
+    // Names with non-ASCII characters are always looked up.
+    let (_, metadata, _) = resolve("/app/föo.tsx", "./föo", None);
+    let (_, listed_metadata, _) = resolve("/app/föo.tsx", "./föo", Some(4));
+    assert_eq!(listed_metadata, metadata);
+}
+
+#[test]
+fn directory_listing_invalidate() {
+    let dir = dunce::canonicalize(std::env::temp_dir())
+        .unwrap()
+        .join("rspack_resolver_directory_listing_invalidate");
+    _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("x.js"), "").unwrap();
+
+    let resolver = Resolver::new(ResolveOptions {
+        directory_listing_threshold: Some(1),
+        ..ResolveOptions::default()
+    });
+    assert_eq!(resolver.resolve(&dir, "./x").map(|r| r.full_path()), Ok(dir.join("x.js")));
+    assert!(resolver.resolve(&dir, "./y").is_err());
+
+    // The created file is listed after it is invalidated.
+    std::fs::write(dir.join("y.js"), "").unwrap();
+    resolver.invalidate(&dir.join("y.js"));
+    assert_eq!(resolver.resolve(&dir, "./y").map(|r| r.full_path()), Ok(dir.join("y.js")));
+
+    _ = std::fs::remove_dir_all(&dir);
+}
@@ -98,3 +98,43 @@ fn fallback() {
         assert_eq!(resolution, Err(expected), "{comment} {request}");
     }
 }
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn fallback_bare_only() {
+    use super::memory_fs::MemoryFS;
+    use crate::{AliasValue, ResolveError, ResolveOptions, ResolverGeneric};
+    use std::path::{Path, PathBuf};
+
+    let f = Path::new("/app");
+    let polyfill = || vec![AliasValue::Path("/polyfills/path.js".into())];
+
+    for fallback_bare_only in [false, true] {
+        let file_system = MemoryFS::new(&[("/app/index.js", ""), ("/polyfills/path.js", "")]);
+        let resolver = ResolverGeneric::new_with_file_system(
+            file_system,
+            ResolveOptions {
+                fallback: vec![
+                    ("path".into(), polyfill()),
+                    ("./missing".into(), polyfill()),
+                    ("/missing".into(), polyfill()),
+                ],
+                fallback_bare_only,
+                ..ResolveOptions::default()
+            },
+        );
+
+        assert_eq!(
+            resolver.resolve(f, "path").map(|r| r.full_path()),
+            Ok(PathBuf::from("/polyfills/path.js"))
+        );
+        for specifier in ["./missing", "/missing"] {
+            let resolved_path = resolver.resolve(f, specifier).map(|r| r.full_path());
+            if fallback_bare_only {
+                assert_eq!(resolved_path, Err(ResolveError::NotFound(specifier.into())));
+            } else {
+                assert_eq!(resolved_path, Ok(PathBuf::from("/polyfills/path.js")));
+            }
+        }
+    }
+}
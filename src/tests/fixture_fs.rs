@@ -0,0 +1,70 @@
+//! Tests for [crate::FixtureFileSystem]
+
+use std::path::{Path, PathBuf};
+
+use crate::{FileSystem, FixtureFileSystem, ResolveOptions, ResolverGeneric};
+
+#[cfg(not(target_os = "windows"))] // The fixtures use `/` paths so the test will not pass in windows.
+#[test]
+fn fixture_fs() {
+    let fs = FixtureFileSystem::from_json(
+        "/app",
+        r#"{
+            "package.json": { "name": "app", "main": "lib/index.js" },
+            "lib": { "index.js": "module.exports = 1", "nested/a.js": "" },
+            "node_modules": { "app": { "$symlink": ".." }, "abs": { "$symlink": "/app/lib" } },
+            "empty": {}
+        }"#,
+    )
+    .unwrap();
+
+    let root = Path::new("/app");
+    assert!(fs.metadata(Path::new("/")).unwrap().is_dir);
+    assert!(fs.metadata(&root.join("empty")).unwrap().is_dir);
+    assert!(fs.metadata(&root.join("lib/nested")).unwrap().is_dir);
+    assert_eq!(fs.metadata(&root.join("lib/index.js")).unwrap().size, Some(18));
+    assert!(fs.metadata(&root.join("missing.js")).is_err());
+    assert!(fs.metadata(&root.join("lib/index.js/a.js")).is_err());
+    assert!(fs.read_to_string(&root.join("package.json")).unwrap().contains(r#""name": "app""#));
+
+    let link = root.join("node_modules/app");
+    assert!(fs.symlink_metadata(&link).unwrap().is_symlink);
+    assert!(fs.metadata(&link).unwrap().is_dir);
+    assert_eq!(fs.read_link(&link).unwrap(), PathBuf::from(".."));
+    assert_eq!(fs.canonicalize(&link.join("lib/index.js")).unwrap(), root.join("lib/index.js"));
+    assert_eq!(fs.canonicalize(&root.join("node_modules/abs")).unwrap(), root.join("lib"));
+    assert_eq!(
+        fs.read_dir(&link.join("lib")).unwrap(),
+        vec![link.join("lib/index.js"), link.join("lib/nested")]
+    );
+
+    let resolver = ResolverGeneric::new_with_file_system(fs, ResolveOptions::default());
+    let resolution = resolver.resolve(root, "app").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(root.join("lib/index.js")));
+
+    assert!(FixtureFileSystem::from_json("/app", "[]").is_err());
+    assert!(FixtureFileSystem::from_json("/app", r#"{ "a.js": 1 }"#).is_err());
+    assert!(FixtureFileSystem::from_json("/app", r#"{ "a": { "$symlink": 1 } }"#).is_err());
+}
+
+#[cfg(not(target_os = "windows"))] // The fixtures use `/` paths so the test will not pass in windows.
+#[test]
+fn conflicting_entries() {
+    let conflict = |tree| FixtureFileSystem::from_json("/app", tree).unwrap_err().to_string();
+    assert_eq!(
+        conflict(r#"{ "a/b.js": "", "a": "x" }"#),
+        "/app/a: described by more than one entry"
+    );
+    assert_eq!(
+        conflict(r#"{ "a": "x", "a/b.js": "" }"#),
+        "/app/a: described by more than one entry"
+    );
+    assert_eq!(
+        conflict(r#"{ "a": { "$symlink": "b" }, "a/c.js": "" }"#),
+        "/app/a: described by more than one entry"
+    );
+    // Directories are merged.
+    let fs =
+        FixtureFileSystem::from_json("/app", r#"{ "a/b.js": "", "a": { "c.js": "" } }"#).unwrap();
+    assert_eq!(fs.read_dir(Path::new("/app/a")).unwrap().len(), 2);
+}
@@ -0,0 +1,70 @@
+//! Tests for [crate::ResolverGeneric::resolution_graph]
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn resolution_graph() {
+    use super::memory_fs::MemoryFS;
+    use crate::{AliasValue, GraphEdge, GraphEdgeKind, GraphNode, ResolveOptions, ResolverGeneric};
+    use std::path::PathBuf;
+
+    let file_system = MemoryFS::new(&[
+        ("/app/node_modules/lib/package.json", r#"{ "exports": { "./sub": "./dist/sub.js" } }"#),
+        ("/app/node_modules/lib/dist/sub.js", ""),
+        ("/app/src/index.js", ""),
+    ]);
+    let resolver = ResolverGeneric::<MemoryFS>::new_with_file_system(
+        file_system,
+        ResolveOptions {
+            alias: vec![("@sub".into(), vec![AliasValue::Path("lib/sub".into())])],
+            ..ResolveOptions::default()
+        },
+    );
+
+    let graph = resolver.resolution_graph(&[
+        ("/app/src", "@sub"),
+        ("/app", "lib/sub"),
+        ("/app", "./src"),
+        ("/app", "missing"),
+    ]);
+
+    let request = |directory: &str, specifier: &str| GraphNode::Request {
+        directory: PathBuf::from(directory),
+        specifier: specifier.into(),
+    };
+    assert_eq!(
+        graph.nodes,
+        vec![
+            request("/app/src", "@sub"),
+            GraphNode::Specifier("lib/sub".into()),
+            GraphNode::File("/app/node_modules/lib/dist/sub.js".into()),
+            request("/app", "lib/sub"),
+            request("/app", "./src"),
+            GraphNode::File("/app/src/index.js".into()),
+            request("/app", "missing"),
+            GraphNode::Error("Cannot find module 'missing'".into()),
+        ]
+    );
+    let edge = |from, to, kind| GraphEdge { from, to, kind };
+    assert_eq!(
+        graph.edges,
+        vec![
+            edge(0, 1, GraphEdgeKind::Alias("@sub".into())),
+            edge(1, 2, GraphEdgeKind::Exports),
+            edge(3, 2, GraphEdgeKind::Exports),
+            edge(4, 5, GraphEdgeKind::Resolved),
+            edge(6, 7, GraphEdgeKind::Failed),
+        ]
+    );
+
+    let dot = graph.to_dot();
+    assert!(dot.starts_with("digraph resolutions {\n"));
+    assert!(dot.contains("  0 -> 1 [label=\"alias @sub\"];\n"));
+    assert!(dot.contains("  7 [label=\"Cannot find module 'missing'\", shape=octagon];\n"));
+
+    let json: serde_json::Value = serde_json::from_str(&graph.to_json()).unwrap();
+    assert_eq!(
+        json["nodes"][0],
+        serde_json::json!({ "kind": "request", "label": "@sub from /app/src" })
+    );
+    assert_eq!(json["edges"][1], serde_json::json!({ "from": 1, "to": 2, "kind": "exports" }));
+}
@@ -4,7 +4,9 @@
 
 use serde_json::json;
 
-use crate::{Ctx, JSONMap, PathUtil, ResolveError, ResolveOptions, Resolver};
+use crate::{
+    package_json::JSONMap, Ctx, ImportsExportsMap, PathUtil, ResolveError, ResolveOptions, Resolver,
+};
 use std::path::Path;
 
 #[test]
@@ -1298,7 +1300,7 @@ fn test_cases() {
         let resolved = Resolver::default()
             .package_imports_exports_resolve(
                 case.request,
-                &case.imports_field,
+                &ImportsExportsMap::new(&case.imports_field),
                 Path::new(""),
                 true,
                 &case.condition_names.iter().map(ToString::to_string).collect::<Vec<_>>(),
@@ -66,3 +66,72 @@ fn no_description_file() {
         Resolver::new(ResolveOptions { description_files: vec![], ..ResolveOptions::default() });
     assert_eq!(resolver.resolve(&f, "."), Err(ResolveError::NotFound(".".into())));
 }
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn jsonc_description_files() {
+    use super::memory_fs::MemoryFS;
+    use crate::ResolverGeneric;
+    use std::path::{Path, PathBuf};
+
+    let resolve = |jsonc_description_files: Vec<String>| {
+        let file_system = MemoryFS::new(&[
+            ("/app/jsonc/deno.jsonc", "{\n  // entry\n  \"main\": \"./main.js\",\n}"),
+            ("/app/jsonc/main.js", ""),
+            ("/app/strict/package.json", "{ /* entry */ \"main\": \"./main.js\" }"),
+            ("/app/strict/main.js", ""),
+        ]);
+        let resolver = ResolverGeneric::<MemoryFS>::new_with_file_system(
+            file_system,
+            ResolveOptions {
+                description_files: vec!["deno.jsonc".into(), "package.json".into()],
+                jsonc_description_files,
+                ..ResolveOptions::default()
+            },
+        );
+        let jsonc = resolver.resolve(Path::new("/app/jsonc"), ".").map(|r| r.full_path());
+        let strict = resolver.resolve(Path::new("/app/strict"), ".").map(|r| r.full_path());
+        (jsonc, strict)
+    };
+
+    let (jsonc, strict) = resolve(vec![]);
+    assert!(
+        matches!(jsonc, Err(ResolveError::JSON(error)) if error.path == Path::new("/app/jsonc/deno.jsonc"))
+    );
+    assert!(matches!(strict, Err(ResolveError::JSON(_))));
+
+    let (jsonc, strict) = resolve(vec!["deno.jsonc".into()]);
+    assert_eq!(jsonc, Ok(PathBuf::from("/app/jsonc/main.js")));
+    assert!(matches!(strict, Err(ResolveError::JSON(_))));
+}
+
+// Parse errors are located on the first read of the description file.
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn parse_error_location() {
+    use super::memory_fs::MemoryFS;
+    use crate::{FsOperation, ResolverGeneric, TraceFileSystem};
+    use std::path::Path;
+
+    let file_system = TraceFileSystem::new(MemoryFS::new(&[(
+        "/app/package.json",
+        "{\n  \"name\": \"app\",\n  \"main\": ./main.js\n}",
+    )]));
+    let resolver = ResolverGeneric::new_with_file_system(file_system, ResolveOptions::default());
+
+    let resolution = resolver.resolve(Path::new("/app"), ".");
+    let Err(ResolveError::JSON(error)) = resolution else {
+        panic!("expected a JSON error, got {resolution:?}");
+    };
+    assert_eq!((error.line, error.column), (3, 11));
+    assert_eq!(error.path, Path::new("/app/package.json"));
+
+    let summary = resolver.file_system().summary();
+    let reads = summary
+        .by_operation
+        .iter()
+        .filter(|(operation, _)| matches!(operation, FsOperation::Read | FsOperation::ReadToString))
+        .map(|(_, stats)| stats.calls)
+        .sum::<u64>();
+    assert_eq!(reads, 1);
+}
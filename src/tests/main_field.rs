@@ -36,3 +36,55 @@ fn test_fallback() {
     let resolution = resolver1.resolve(&f, "main_field_fallback").map(|r| r.full_path());
     assert_eq!(resolution, Ok(f.join("node_modules/main_field_fallback/exist.js")));
 }
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn nested_path() {
+    use super::memory_fs::MemoryFS;
+    use crate::ResolverGeneric;
+    use std::path::{Path, PathBuf};
+
+    let f = Path::new("/app");
+
+    let file_system = MemoryFS::new(&[
+        (
+            "/app/node_modules/pck/package.json",
+            r#"{ "main": "./main.js", "spm": { "main": "./spm.js" }, "sass": { "main": 1 } }"#,
+        ),
+        ("/app/node_modules/pck/main.js", ""),
+        ("/app/node_modules/pck/spm.js", ""),
+    ]);
+
+    let resolver = ResolverGeneric::<MemoryFS>::new_with_file_system(
+        file_system,
+        ResolveOptions {
+            main_fields: vec!["sass.main".into(), "spm.main".into(), "main".into()],
+            ..ResolveOptions::default()
+        },
+    );
+    let resolution = resolver.resolve(f, "pck").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(PathBuf::from("/app/node_modules/pck/spm.js")));
+
+    let resolver = resolver.clone_with_options(ResolveOptions {
+        main_fields: vec!["spm".into(), "main".into()],
+        ..ResolveOptions::default()
+    });
+    let resolution = resolver.resolve(f, "pck").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(PathBuf::from("/app/node_modules/pck/main.js")));
+
+    // A field named with dots takes precedence over the path.
+    let file_system = MemoryFS::new(&[
+        (
+            "/app/node_modules/pck/package.json",
+            r#"{ "spm.main": "./literal.js", "spm": { "main": "./spm.js" } }"#,
+        ),
+        ("/app/node_modules/pck/literal.js", ""),
+        ("/app/node_modules/pck/spm.js", ""),
+    ]);
+    let resolver = ResolverGeneric::<MemoryFS>::new_with_file_system(
+        file_system,
+        ResolveOptions { main_fields: vec!["spm.main".into()], ..ResolveOptions::default() },
+    );
+    let resolution = resolver.resolve(f, "pck").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(PathBuf::from("/app/node_modules/pck/literal.js")));
+}
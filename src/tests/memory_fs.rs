@@ -75,4 +75,13 @@ impl FileSystem for MemoryFS {
     fn canonicalize(&self, _path: &Path) -> io::Result<PathBuf> {
         Err(io::Error::new(io::ErrorKind::NotFound, "not a symlink"))
     }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        use vfs::FileSystem;
+        let entries = self
+            .fs
+            .read_dir(path.to_string_lossy().as_ref())
+            .map_err(|err| io::Error::new(io::ErrorKind::NotFound, err))?;
+        Ok(entries.map(|name| path.join(name)).collect())
+    }
 }
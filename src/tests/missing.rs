@@ -2,7 +2,10 @@
 
 use normalize_path::NormalizePath;
 
-use crate::{AliasValue, ResolveContext, ResolveOptions, Resolver};
+use crate::{
+    AliasValue, ResolveAttempt, ResolveAttemptReason, ResolveContext, ResolveError, ResolveOptions,
+    Resolver,
+};
 
 #[test]
 fn test() {
@@ -104,3 +107,166 @@ fn alias_and_extensions() {
         }
     }
 }
+
+#[test]
+fn attempts() {
+    let f = super::fixture();
+    let resolver = Resolver::default();
+
+    let mut ctx = ResolveContext::default();
+    let _ = resolver.resolve_with_context(&f, "./missing-file", &mut ctx);
+    assert!(ctx.attempts.is_none());
+
+    let mut ctx = ResolveContext { attempts: Some(vec![]), ..ResolveContext::default() };
+    let _ = resolver.resolve_with_context(&f, "./missing-file", &mut ctx);
+    let attempts = ctx.attempts.unwrap();
+    let expected = [f.join("missing-file"), f.join("missing-file.js"), f.join("missing-file.json")]
+        .map(|path| ResolveAttempt { path, reason: ResolveAttemptReason::NotFound });
+    assert_eq!(attempts[..expected.len()], expected);
+
+    // `dirOrFile` is a directory, so it is rejected as a file before `dirOrFile.js` is found.
+    let mut ctx = ResolveContext { attempts: Some(vec![]), ..ResolveContext::default() };
+    let resolution = resolver.resolve_with_context(&f, "./dirOrFile", &mut ctx).unwrap();
+    assert_eq!(resolution.path(), f.join("dirOrFile.js"));
+    assert_eq!(
+        ctx.attempts.unwrap(),
+        vec![ResolveAttempt { path: f.join("dirOrFile"), reason: ResolveAttemptReason::NotAFile }]
+    );
+}
+
+#[test]
+fn searched_directories() {
+    let f = super::fixture();
+    let resolver = Resolver::default();
+
+    let mut ctx = ResolveContext::default();
+    let resolution = resolver.resolve_with_context(&f, "missing-package", &mut ctx);
+    assert_eq!(resolution, Err(ResolveError::NotFound("missing-package".into())));
+    assert!(ctx.searched_directories.is_empty());
+
+    let mut ctx = ResolveContext { attempts: Some(vec![]), ..ResolveContext::default() };
+    let resolution = resolver.resolve_with_context(&f, "missing-package", &mut ctx);
+    assert_eq!(resolution, Err(ResolveError::NotFound("missing-package".into())));
+    let directories = ctx.searched_directories;
+    assert_eq!(directories.first(), Some(&f.join("node_modules")));
+    assert!(directories.iter().all(|directory| directory.ends_with("node_modules")));
+}
+
+#[test]
+fn reuse_context() {
+    let f = super::fixture();
+    let resolver = Resolver::default();
+
+    let mut ctx = ResolveContext { attempts: Some(vec![]), ..ResolveContext::default() };
+    let _ = resolver.resolve_with_context(&f, "./missing-file", &mut ctx);
+    let missing_dependencies = ctx.missing_dependencies.clone();
+    assert!(!missing_dependencies.is_empty());
+
+    ctx.clear();
+    assert!(ctx.file_dependencies.is_empty());
+    assert!(ctx.missing_dependencies.is_empty());
+    assert_eq!(ctx.attempts, Some(vec![]));
+
+    let _ = resolver.resolve_with_context(&f, "./missing-file", &mut ctx);
+    assert_eq!(ctx.missing_dependencies, missing_dependencies);
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn permission_denied() {
+    use super::memory_fs::MemoryFS;
+    use crate::{FileMetadata, FileSystem, ResolverGeneric};
+    use std::{
+        io,
+        path::{Path, PathBuf},
+    };
+
+    /// Fails with the given error for the failing paths and everything under them.
+    struct FailingFileSystem(MemoryFS, Vec<(&'static str, io::ErrorKind, &'static str)>);
+
+    impl FailingFileSystem {
+        fn check(&self, path: &Path) -> io::Result<()> {
+            match self.1.iter().find(|(failing, _, _)| path.starts_with(failing)) {
+                Some((_, kind, message)) => Err(io::Error::new(*kind, *message)),
+                None => Ok(()),
+            }
+        }
+    }
+
+    impl FileSystem for FailingFileSystem {
+        fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+            self.check(path)?;
+            self.0.read(path)
+        }
+
+        fn read_to_string(&self, path: &Path) -> io::Result<String> {
+            self.check(path)?;
+            self.0.read_to_string(path)
+        }
+
+        fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+            self.check(path)?;
+            self.0.metadata(path)
+        }
+
+        fn symlink_metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+            self.check(path)?;
+            self.0.symlink_metadata(path)
+        }
+
+        fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+            self.check(path)?;
+            self.0.canonicalize(path)
+        }
+    }
+
+    let denied = io::ErrorKind::PermissionDenied;
+    let file_system = FailingFileSystem(
+        MemoryFS::new(&[
+            ("/app/index.js", ""),
+            ("/app/src/index.js", ""),
+            ("/app/node_modules/denied/index.js", ""),
+            ("/app/node_modules/private/package.json", "{}"),
+            ("/app/node_modules/private/index.js", ""),
+            ("/app/node_modules/broken/index.js", ""),
+            ("/app/node_modules/looped/index.js", ""),
+        ]),
+        vec![
+            ("/app/node_modules/denied", denied, "permission denied"),
+            ("/app/node_modules/private/package.json", denied, "permission denied"),
+            ("/app/lib.js", denied, "permission denied"),
+            ("/app/src/node_modules", denied, "permission denied"),
+            ("/app/node_modules/broken", io::ErrorKind::Other, "input/output error"),
+            ("/app/node_modules/looped", io::ErrorKind::Other, "too many levels of symbolic links"),
+        ],
+    );
+    let resolver = ResolverGeneric::new_with_file_system(file_system, ResolveOptions::default());
+
+    for (specifier, message) in [
+        ("denied", "/app/node_modules/denied: permission denied"),
+        ("private", "/app/node_modules/private/package.json: permission denied"),
+        ("./lib", "/app/lib.js: permission denied"),
+        ("broken", "/app/node_modules/broken: input/output error"),
+        ("looped", "/app/node_modules/looped: too many levels of symbolic links"),
+    ] {
+        let error = resolver.resolve("/app", specifier).unwrap_err();
+        assert!(matches!(error, ResolveError::IOError(_)), "{specifier} {error:?}");
+        assert_eq!(error.to_string(), message);
+    }
+
+    // The error of the path the specifier points to is reported,
+    // not the one of an inaccessible directory searched before it.
+    let error = resolver.resolve("/app/src", "denied").unwrap_err();
+    assert_eq!(error.to_string(), "/app/node_modules/denied: permission denied");
+
+    assert_eq!(
+        resolver.resolve("/app", "./missing"),
+        Err(ResolveError::NotFound("./missing".into()))
+    );
+    // An inaccessible directory searched along the way is not where the module would have been.
+    assert_eq!(
+        resolver.resolve("/app/src", "missing"),
+        Err(ResolveError::NotFound("missing".into()))
+    );
+    assert!(resolver.resolve("/app", "./index.js").is_ok());
+}
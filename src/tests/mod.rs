@@ -1,4 +1,5 @@
 mod alias;
+mod bin;
 mod browser_field;
 mod builtins;
 mod dependencies;
@@ -6,7 +7,10 @@ mod exports_field;
 mod extension_alias;
 mod extensions;
 mod fallback;
+#[cfg(feature = "fixture_fs")]
+mod fixture_fs;
 mod full_specified;
+mod graph;
 mod imports_field;
 mod incorrect_description_file;
 mod main_field;
@@ -22,6 +26,7 @@ mod simple;
 mod symlink;
 mod tsconfig_paths;
 mod tsconfig_project_references;
+mod workspaces;
 
 use crate::Resolver;
 use std::{env, path::PathBuf, sync::Arc, thread};
@@ -49,3 +54,184 @@ fn threaded_environment() {
         .join();
     }
 }
+
+#[test]
+fn cache_stats() {
+    let f = fixture();
+    let resolver = Resolver::default();
+    assert_eq!(resolver.cache_stats(), crate::CacheStats::default());
+
+    resolver.resolve(&f, "m1/a").unwrap();
+    let stats = resolver.cache_stats();
+    assert!(stats.paths > 0);
+    assert!(stats.package_jsons > 0);
+    assert_eq!(stats.tsconfigs, 0);
+
+    resolver.clear_cache();
+    assert_eq!(resolver.cache_stats(), crate::CacheStats::default());
+}
+
+#[test]
+fn approximate_memory_usage() {
+    use crate::{MemoryUsage, ResolveOptions, TsconfigOptions, TsconfigReferences};
+
+    let f = fixture();
+    let resolver = Resolver::default();
+    assert_eq!(resolver.approximate_memory_usage(), MemoryUsage::default());
+
+    resolver.resolve(&f, "m1/a").unwrap();
+    let usage = resolver.approximate_memory_usage();
+    assert!(usage.paths > 0);
+    assert!(usage.package_jsons > 0);
+    assert_eq!(usage.tsconfigs, 0);
+    assert_eq!(usage.total(), usage.paths + usage.package_jsons + usage.pnp_manifests);
+
+    // Resolving again reuses the cache.
+    resolver.resolve(&f, "m1/a").unwrap();
+    assert_eq!(resolver.approximate_memory_usage(), usage);
+
+    let tsconfig_fixture = fixture_root().join("tsconfig/cases/paths_template_variable");
+    let resolver = Resolver::new(ResolveOptions {
+        tsconfig: Some(TsconfigOptions {
+            config_file: tsconfig_fixture.join("tsconfig1.json"),
+            references: TsconfigReferences::Auto,
+            content: None,
+        }),
+        ..ResolveOptions::default()
+    });
+    _ = resolver.resolve(&tsconfig_fixture, "foo");
+    assert!(resolver.approximate_memory_usage().tsconfigs > 0);
+
+    resolver.clear_cache();
+    assert_eq!(resolver.approximate_memory_usage(), MemoryUsage::default());
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn take_invalidated() {
+    use crate::{ResolveOptions, ResolverGeneric};
+    use memory_fs::MemoryFS;
+    use std::path::Path;
+
+    let file_system = MemoryFS::new(&[
+        ("/app/src/index.js", ""),
+        ("/app/node_modules/pkg/package.json", r#"{"main":"lib/main.js"}"#),
+        ("/app/node_modules/pkg/lib/main.js", ""),
+    ]);
+    let resolver = ResolverGeneric::new_with_file_system(file_system, ResolveOptions::default());
+    assert!(resolver.resolve("/app/src", "./index.js").is_ok());
+    assert!(resolver.resolve("/app/src", "pkg").is_ok());
+    assert!(resolver.take_invalidated().is_empty());
+
+    let pkg = Path::new("/app/node_modules/pkg");
+    resolver.invalidate(pkg);
+    let invalidated = resolver.take_invalidated();
+    assert!(invalidated.iter().all(|path| path.starts_with(pkg)));
+    for path in ["", "lib", "lib/main.js"] {
+        assert!(invalidated.contains(&pkg.join(path)), "{path}");
+    }
+    assert!(invalidated.windows(2).all(|paths| paths[0] < paths[1]));
+    assert!(resolver.take_invalidated().is_empty());
+}
+
+#[test]
+fn invalidate_package_json() {
+    let dir = dunce::canonicalize(std::env::temp_dir())
+        .unwrap()
+        .join("rspack_resolver_invalidate_package_json");
+    _ = std::fs::remove_dir_all(&dir);
+    let pkg = dir.join("node_modules/pkg");
+    std::fs::create_dir_all(&pkg).unwrap();
+    std::fs::write(pkg.join("a.js"), "").unwrap();
+    std::fs::write(pkg.join("b.js"), "").unwrap();
+    std::fs::write(pkg.join("package.json"), r#"{"main":"a.js"}"#).unwrap();
+
+    let resolver = Resolver::default();
+    let resolve = || resolver.resolve(&dir, "pkg").map(|r| r.full_path());
+    assert_eq!(resolve(), Ok(pkg.join("a.js")));
+
+    // Invalidating only the package.json reads it again, not the one cached by its directory.
+    std::fs::write(pkg.join("package.json"), r#"{"main":"b.js"}"#).unwrap();
+    resolver.invalidate(&pkg.join("package.json"));
+    assert_eq!(resolve(), Ok(pkg.join("b.js")));
+
+    _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn disable_cache() {
+    use crate::{FileSystemOs, ResolveOptions, ResolverGeneric, TraceFileSystem};
+
+    let f = fixture();
+    let calls = |disable_cache| {
+        let options = ResolveOptions { disable_cache, ..ResolveOptions::default() };
+        let file_system = TraceFileSystem::new(FileSystemOs::default());
+        let resolver = ResolverGeneric::new_with_file_system(file_system, options);
+        let first = resolver.resolve(&f, "m1/a").map(|r| r.full_path());
+        let first_calls = resolver.file_system().summary().total.calls;
+        resolver.file_system().reset();
+        let second = resolver.resolve(&f, "m1/a").map(|r| r.full_path());
+        assert_eq!(first, second);
+        assert_eq!(resolver.cache_stats().paths == 0, disable_cache);
+        (first_calls, resolver.file_system().summary().total.calls)
+    };
+
+    let (cached_first_calls, second_calls) = calls(false);
+    assert!(second_calls < cached_first_calls);
+    let (first_calls, second_calls) = calls(true);
+    assert_eq!(second_calls, first_calls);
+    // Paths are still shared within a resolution.
+    assert_eq!(first_calls, cached_first_calls);
+}
+
+#[test]
+fn resolve_all() {
+    let f = fixture();
+    let resolver = Resolver::default();
+    let entries = [(&f, "./a"), (&f, "m1/a"), (&f, "./missing-file"), (&f, "./main1.js")];
+
+    let expected = entries
+        .iter()
+        .map(|(directory, specifier)| resolver.resolve(directory, specifier))
+        .collect::<Vec<_>>();
+    assert!(expected[2].is_err());
+
+    for concurrency in [0, 1, 3, 16] {
+        let mut ctx = crate::ResolveContext::default();
+        assert_eq!(resolver.resolve_all(&entries, concurrency, &mut ctx), expected);
+        assert!(!ctx.file_dependencies.is_empty());
+        assert!(!ctx.missing_dependencies.is_empty());
+    }
+}
+
+#[test]
+fn not_found_report() {
+    let f = fixture();
+    let g = f.join("node_modules");
+    let resolver = Resolver::default();
+    let entries = [
+        (&f, "./a"),
+        (&f, "lodash/get"),
+        (&f, "lodash"),
+        (&g, "@scope/missing/sub"),
+        (&g, "lodash/set"),
+        (&g, "./missing-file"),
+    ];
+    let results = resolver.resolve_all(&entries, 1, &mut crate::ResolveContext::default());
+
+    let report = resolver.not_found_report(&entries, &results);
+    assert_eq!(report.total, 5);
+    assert_eq!(
+        report.by_package,
+        vec![("lodash".to_string(), 3), ("@scope/missing".to_string(), 1)]
+    );
+    assert_eq!(report.by_directory, vec![(g.clone(), 3), (f.clone(), 2)]);
+    assert_eq!(
+        report.to_string(),
+        format!(
+            "5 not found\n  3 not found in package 'lodash'\n  1 not found in package '@scope/missing'\n  3 not found from '{}'\n  2 not found from '{}'\n",
+            g.display(),
+            f.display()
+        )
+    );
+}
@@ -3,7 +3,10 @@
 //! enhanced_resolve's test <https://github.com/webpack/enhanced-resolve/blob/main/test/pnp.test.js>
 //! cannot be ported over because it uses mocks on `pnpApi` provided by the runtime.
 
-use crate::{ResolveOptions, Resolver};
+use crate::{
+    FileSystem, FileSystemOptions, FileSystemOs, ResolveContext, ResolveError, ResolveOptions,
+    Resolver,
+};
 
 #[test]
 fn pnp1() {
@@ -64,3 +67,161 @@ fn pnp1() {
         )),
     );
 }
+
+#[test]
+fn pnp_loose() {
+    let fixture = super::fixture_root().join("pnp");
+
+    let resolver = Resolver::default();
+    let mut ctx = ResolveContext { warnings: Some(vec![]), ..ResolveContext::default() };
+    assert_eq!(
+        resolver.resolve_with_context(&fixture, "undeclared", &mut ctx).map(|r| r.full_path()),
+        Err(ResolveError::NotFound("undeclared".into()))
+    );
+    assert_eq!(ctx.warnings, Some(vec![]));
+
+    let resolver = Resolver::new(ResolveOptions { pnp_loose: true, ..ResolveOptions::default() });
+    let mut ctx = ResolveContext { warnings: Some(vec![]), ..ResolveContext::default() };
+    assert_eq!(
+        resolver.resolve_with_context(&fixture, "undeclared", &mut ctx).map(|r| r.full_path()),
+        Ok(fixture.join("node_modules/undeclared/index.js"))
+    );
+    let warnings = ctx.warnings.unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].contains("undeclared"), "{warnings:?}");
+
+    // Declared dependencies are still resolved by the manifest.
+    assert_eq!(
+        resolver.resolve(&fixture, "lodash.zip").map(|r| r.full_path()),
+        Ok(fixture.join(
+            ".yarn/cache/lodash.zip-npm-4.2.0-5299417ec8-e596da80a6.zip/node_modules/lodash.zip/index.js"
+        ))
+    );
+}
+
+#[test]
+fn pnp_manifest_changed() {
+    let fixture = super::fixture_root().join("pnp");
+    // Canonicalized for comparing with resolved real paths, the temp directory is a symlink on macOS.
+    let dir = dunce::canonicalize(std::env::temp_dir())
+        .unwrap()
+        .join("rspack_resolver_pnp_manifest_changed");
+    _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("node_modules/undeclared")).unwrap();
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    std::fs::write(dir.join("node_modules/undeclared/index.js"), "").unwrap();
+    let manifest = dir.join(".pnp.cjs");
+    std::fs::copy(fixture.join(".pnp.cjs"), &manifest).unwrap();
+
+    let resolver = Resolver::default();
+    let not_found = Err(ResolveError::NotFound("undeclared".into()));
+    let found = Ok(dir.join("node_modules/undeclared/index.js"));
+    assert_eq!(resolver.resolve(&dir, "undeclared").map(|r| r.full_path()), not_found);
+
+    // The manifest is kept until `.pnp.cjs` is invalidated, then found again without clearing the cache.
+    std::fs::remove_file(&manifest).unwrap();
+    assert_eq!(resolver.resolve(&dir, "undeclared").map(|r| r.full_path()), not_found);
+    resolver.invalidate(&manifest);
+    assert_eq!(resolver.resolve(&dir, "undeclared").map(|r| r.full_path()), found);
+    assert_eq!(resolver.resolve(dir.join("src"), "undeclared").map(|r| r.full_path()), found);
+
+    // A created `.pnp.cjs` replaces the cached absence of a manifest for the directories under it.
+    std::fs::copy(fixture.join(".pnp.cjs"), &manifest).unwrap();
+    resolver.invalidate(&manifest);
+    assert_eq!(resolver.resolve(&dir, "undeclared").map(|r| r.full_path()), not_found);
+    assert_eq!(resolver.resolve(dir.join("src"), "undeclared").map(|r| r.full_path()), not_found);
+
+    _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn unplugged() {
+    let fixture = super::fixture_root().join("pnp-unplugged");
+    let unplugged =
+        fixture.join(".yarn/unplugged/is-buffer-npm-1.1.6-08199d9ccc/node_modules/is-buffer");
+    let archive = fixture
+        .join(".yarn/cache/is-buffer-npm-1.1.6-08199d9ccc-ae18aa0b6e.zip/node_modules/is-buffer");
+    let kind_of = fixture
+        .join(".yarn/cache/kind-of-npm-3.2.2-7deaffa5f9-7e34bc29d4.zip/node_modules/kind-of");
+    let archive_index = archive.join("index.js");
+
+    let resolver = Resolver::default();
+
+    #[rustfmt::skip]
+    let pass = [
+        ("dependency", &fixture, "is-buffer"),
+        ("nested dependency of a zipped package", &kind_of, "is-buffer"),
+        ("relative from the unplugged package", &unplugged, "./index.js"),
+        ("relative from the archive", &archive, "./index.js"),
+        ("absolute path into the archive", &fixture, archive_index.to_str().unwrap()),
+    ];
+
+    for (comment, directory, specifier) in pass {
+        let resolution = resolver.resolve(directory, specifier);
+        let resolution = resolution.unwrap_or_else(|e| panic!("{comment} {e}"));
+        assert_eq!(resolution.full_path(), unplugged.join("index.js"), "{comment}");
+        assert_eq!(
+            resolution.package_json().map(|p| p.path.clone()),
+            Some(unplugged.join("package.json")),
+            "{comment}"
+        );
+    }
+
+    assert_eq!(
+        resolver.resolve(&fixture, "kind-of").map(|r| r.full_path()),
+        Ok(kind_of.join("index.js"))
+    );
+    assert!(resolver.approximate_memory_usage().pnp_manifests > 0);
+}
+
+#[test]
+fn file_system_options() {
+    let path = super::fixture_root().join(
+        "pnp/.yarn/cache/is-even-npm-1.0.0-9f726520dc-2728cc2f39.zip/node_modules/is-even/index.js",
+    );
+
+    let fs =
+        FileSystemOs::new(FileSystemOptions { zip_cache_size: 1, ..FileSystemOptions::default() });
+    assert!(fs.metadata(&path).is_ok_and(|meta| meta.is_file));
+    assert!(fs.read_to_string(&path).is_ok());
+    assert!(fs.metadata(path.parent().unwrap()).is_ok_and(|meta| meta.is_dir));
+
+    let fs =
+        FileSystemOs::new(FileSystemOptions { enable_pnp: false, ..FileSystemOptions::default() });
+    assert!(fs.metadata(&path).is_err());
+    assert!(fs.read_to_string(&path).is_err());
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn virtual_directory() {
+    use super::memory_fs::MemoryFS;
+    use crate::ResolverGeneric;
+    use std::path::Path;
+
+    let file_system = MemoryFS::new(&[
+        ("/project/.yarn/cache/pkg/package.json", r#"{"name":"pkg"}"#),
+        ("/project/.yarn/cache/pkg/lib/a.js", ""),
+    ]);
+    let resolver = ResolverGeneric::new_with_file_system(file_system, ResolveOptions::default());
+    let virtual_directory = Path::new("/project/.yarn/__virtual__/pkg-virtual-0123/0/cache/pkg");
+
+    let resolution = resolver.resolve(virtual_directory, "./lib/a.js").unwrap();
+    assert_eq!(resolution.full_path(), Path::new("/project/.yarn/cache/pkg/lib/a.js"));
+    assert_eq!(
+        resolution.package_json().map(|p| p.path.clone()),
+        Some(Path::new("/project/.yarn/cache/pkg/package.json").to_path_buf())
+    );
+
+    let specifier = "/project/.yarn/__virtual__/pkg-virtual-0123/1/.yarn/cache/pkg/lib/a.js";
+    assert_eq!(
+        resolver.resolve("/", specifier).map(|r| r.full_path()),
+        Ok(Path::new("/project/.yarn/cache/pkg/lib/a.js").to_path_buf())
+    );
+
+    let resolver = ResolverGeneric::new_with_file_system(
+        MemoryFS::new(&[("/project/.yarn/cache/pkg/lib/a.js", "")]),
+        ResolveOptions { enable_pnp: false, ..ResolveOptions::default() },
+    );
+    assert!(resolver.resolve(virtual_directory, "./lib/a.js").is_err());
+}
@@ -1,6 +1,6 @@
 //! <https://github.com/webpack/enhanced-resolve/blob/main/test/resolve.test.js>
 
-use crate::{ResolveError, ResolveOptions, Resolver};
+use crate::{Resolution, ResolveError, ResolveOptions, Resolver};
 
 #[test]
 fn resolve() {
@@ -58,6 +58,25 @@ fn resolve() {
     }
 }
 
+#[test]
+fn fragment_as_path() {
+    let f = super::fixture();
+    let resolver =
+        Resolver::new(ResolveOptions { fragment_as_path: false, ..ResolveOptions::default() });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("fragment", "./no#fragment/#/#", f.join("no.js#fragment/#/#")),
+        ("escaped fragment", "./no\0#fragment/\0#/\0##fragment", f.join("no#fragment/#/#.js#fragment")),
+        ("file with fragment", "./main1.js#fragment", f.join("main1.js#fragment")),
+    ];
+
+    for (comment, request, expected) in pass {
+        let resolved_path = resolver.resolve(&f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{comment} {request}");
+    }
+}
+
 #[test]
 fn issue238_resolve() {
     let f = super::fixture().join("issue-238");
@@ -117,3 +136,738 @@ fn resolve_hash_as_module() {
     let resolution = resolver.resolve(f, "#a");
     assert_eq!(resolution, Err(ResolveError::NotFound("#a".into())));
 }
+
+#[test]
+fn resolve_package_dir() {
+    let f = super::fixture();
+    let resolver = Resolver::default();
+
+    #[rustfmt::skip]
+    let pass = [
+        ("package name", f.clone(), "dash", f.join("node_modules/dash")),
+        ("package subpath", f.clone(), "dash-name/some/file.js", f.join("node_modules/dash-name")),
+        ("from nested directory", f.join("node_modules/complexm"), "dash", f.join("node_modules/dash")),
+    ];
+
+    for (comment, path, request, expected) in pass {
+        let resolved_path = resolver.resolve_package_dir(&path, request);
+        assert_eq!(resolved_path, Ok(expected), "{comment} {path:?} {request}");
+    }
+
+    #[rustfmt::skip]
+    let fail = [
+        ("without package.json", "m1"),
+        ("not found", "missing"),
+        ("relative path", "./dash"),
+    ];
+
+    for (comment, request) in fail {
+        let resolution = resolver.resolve_package_dir(&f, request);
+        assert_eq!(resolution, Err(ResolveError::NotFound(request.into())), "{comment} {request}");
+    }
+}
+
+#[test]
+fn path_hash() {
+    let f = super::fixture();
+    let resolver = Resolver::default();
+
+    let main1 = resolver.resolve(&f, "./main1").unwrap();
+    let main1_with_query = resolver.resolve(&f, "./main1.js?query").unwrap();
+    let a = resolver.resolve(&f, "./a").unwrap();
+    assert_eq!(main1.path_hash(), main1_with_query.path_hash());
+    assert_ne!(main1.path_hash(), a.path_hash());
+    assert_eq!(main1.path_hash(), crate::cache::hash_path(main1.path()));
+}
+
+#[test]
+fn cancellation() {
+    use std::time::Duration;
+
+    use crate::{CancellationToken, ResolveContext};
+
+    let f = super::fixture();
+    let resolver = Resolver::default();
+
+    let token = CancellationToken::new();
+    let mut ctx = ResolveContext { cancellation: Some(token.clone()), ..ResolveContext::default() };
+    let resolution = resolver.resolve_with_context(&f, "m1/a", &mut ctx).map(|r| r.full_path());
+    assert_eq!(resolution, Ok(f.join("node_modules/m1/a.js")));
+
+    token.cancel();
+    assert!(token.is_cancelled());
+    let resolution = resolver.resolve_with_context(&f, "m1/a", &mut ctx);
+    assert_eq!(resolution, Err(ResolveError::Cancelled));
+
+    let mut ctx = ResolveContext {
+        cancellation: Some(CancellationToken::with_timeout(Duration::ZERO)),
+        ..ResolveContext::default()
+    };
+    let results = resolver.resolve_all(&[(&f, "./main1"), (&f, "m1/a")], 2, &mut ctx);
+    assert_eq!(results, vec![Err(ResolveError::Cancelled), Err(ResolveError::Cancelled)]);
+}
+
+#[test]
+fn fs_operation_budget() {
+    let f = super::fixture();
+
+    let resolver = Resolver::new(ResolveOptions {
+        fs_operation_budget: Some(100),
+        ..ResolveOptions::default()
+    });
+    let resolution = resolver.resolve(&f, "m1/a").map(|r| r.full_path());
+    assert_eq!(resolution, Ok(f.join("node_modules/m1/a.js")));
+
+    let resolver =
+        Resolver::new(ResolveOptions { fs_operation_budget: Some(1), ..ResolveOptions::default() });
+    let Err(ResolveError::FsOperationBudgetExceeded(budget, directories)) =
+        resolver.resolve(&f, "m1/a")
+    else {
+        panic!("expected the budget to be exceeded");
+    };
+    assert_eq!(budget, 1);
+    assert!(!directories.is_empty());
+    assert!(directories.windows(2).all(|w| w[0].1 >= w[1].1));
+}
+
+#[test]
+fn resolve_all_candidates() {
+    let f = super::fixture();
+
+    let resolver = Resolver::new(ResolveOptions {
+        extensions: vec![".ts".into(), ".js".into()],
+        ..ResolveOptions::default()
+    });
+    let candidates = resolver
+        .resolve_all_candidates(f.join("extensions"), "./foo")
+        .map(|candidates| candidates.iter().map(Resolution::full_path).collect::<Vec<_>>());
+    assert_eq!(candidates, Ok(vec![f.join("extensions/foo.ts"), f.join("extensions/foo.js")]));
+
+    let resolver = Resolver::default();
+    let candidates = resolver
+        .resolve_all_candidates(&f, "./dirOrFile")
+        .map(|candidates| candidates.iter().map(Resolution::full_path).collect::<Vec<_>>());
+    assert_eq!(candidates, Ok(vec![f.join("dirOrFile.js"), f.join("dirOrFile/index.js")]));
+
+    let candidates = resolver.resolve_all_candidates(&f, "./missing");
+    assert_eq!(candidates, Err(ResolveError::NotFound("./missing".into())));
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn resolve_condition_sets() {
+    use super::memory_fs::MemoryFS;
+    use crate::{ResolverGeneric, TraceFileSystem};
+    use std::path::{Path, PathBuf};
+
+    let file_system = TraceFileSystem::new(MemoryFS::new(&[
+        (
+            "/node_modules/dual/package.json",
+            r#"{ "exports": { "import": "./index.mjs", "require": "./index.cjs" } }"#,
+        ),
+        ("/node_modules/dual/index.mjs", ""),
+        ("/node_modules/dual/index.cjs", ""),
+        ("/src/a.js", ""),
+    ]));
+    let resolver = ResolverGeneric::new_with_file_system(
+        file_system,
+        ResolveOptions { disable_cache: true, ..ResolveOptions::default() },
+    );
+    let condition_sets = [vec!["import".into()], vec!["require".into()], vec![]];
+    let resolve = |specifier| {
+        resolver
+            .resolve_condition_sets(Path::new("/src"), specifier, &condition_sets)
+            .into_iter()
+            .map(|result| result.map(|r| r.full_path()))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        resolve("dual"),
+        vec![
+            Ok(PathBuf::from("/node_modules/dual/index.mjs")),
+            Ok(PathBuf::from("/node_modules/dual/index.cjs")),
+            Err(ResolveError::PackagePathNotExported(
+                ".".into(),
+                PathBuf::from("/node_modules/dual/package.json")
+            )),
+        ]
+    );
+
+    // Resolved once when the conditions are not used.
+    resolver.file_system().reset();
+    assert_eq!(resolve("./a"), vec![Ok(PathBuf::from("/src/a.js")); 3]);
+    let calls = resolver.file_system().summary().total.calls;
+    resolver.file_system().reset();
+    let _ = resolver.resolve("/src", "./a");
+    assert_eq!(resolver.file_system().summary().total.calls, calls);
+}
+
+#[test]
+fn specifier_rewrites() {
+    use std::sync::Arc;
+
+    use crate::SpecifierRewrite;
+
+    let f = super::fixture();
+
+    let resolver = Resolver::new(ResolveOptions {
+        specifier_rewrites: vec![
+            SpecifierRewrite::Scheme("virtual".into()),
+            SpecifierRewrite::Prefix("~/".into(), "./".into()),
+            SpecifierRewrite::Function(Arc::new(|specifier| {
+                (specifier == "entry").then(|| "./main1".to_string())
+            })),
+        ],
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let pass = [
+        ("virtual:./main1", f.join("main1.js")),
+        ("~/a", f.join("a.js")),
+        // Rewrites apply in order.
+        ("virtual:~/a", f.join("a.js")),
+        ("entry", f.join("main1.js")),
+        ("./b", f.join("b.js")),
+    ];
+
+    for (specifier, expected) in pass {
+        let resolved_path = resolver.resolve(&f, specifier).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{specifier}");
+    }
+
+    // Errors report the rewritten specifier.
+    let resolution = resolver.resolve(&f, "virtual:./missing");
+    assert_eq!(resolution, Err(ResolveError::NotFound("./missing".into())));
+}
+
+#[test]
+fn package_version() {
+    let f = super::fixture();
+    let resolver = Resolver::default();
+
+    assert_eq!(
+        resolver.package_version(f.join("exports-field"), "exports-field"),
+        Ok(Some("1.0.0".into()))
+    );
+    assert_eq!(resolver.package_version(&f, "dash"), Ok(None));
+    assert_eq!(
+        resolver.package_version(&f, "missing"),
+        Err(ResolveError::NotFound("missing".into()))
+    );
+}
+
+#[test]
+fn externals() {
+    use crate::External;
+    use std::sync::Arc;
+
+    let f = super::fixture();
+    let resolver = Resolver::new(ResolveOptions {
+        externals: vec![
+            External::Exact("dash".into()),
+            External::Prefix("node:".into()),
+            External::Package("exports-field".into()),
+            External::Package("missing".into()),
+            External::Function(Arc::new(|specifier, _| specifier.starts_with("virtual:"))),
+        ],
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let data = [
+        ("exact", "dash", true),
+        ("exact does not match subpaths", "dash/foo", false),
+        ("prefix", "node:fs", true),
+        ("package", "exports-field", true),
+        ("package subpath", "exports-field/dist/main.js", true),
+        ("package does not match other names", "exports-field-extra", false),
+        ("function", "virtual:entry", true),
+        ("not external", "m1", false),
+    ];
+
+    for (comment, request, expected) in data {
+        assert_eq!(resolver.is_external(&f, request), expected, "{comment} {request}");
+    }
+
+    let package_json = resolver.external_package_json(&f, "dash").unwrap().unwrap();
+    assert_eq!(package_json.directory(), f.join("node_modules/dash"));
+    assert!(resolver.external_package_json(&f, "m1").unwrap().is_none());
+    assert!(resolver.external_package_json(&f, "node:fs").unwrap().is_none());
+    assert_eq!(
+        resolver.external_package_json(&f, "missing/foo").map(|p| p.is_some()),
+        Err(ResolveError::NotFound("missing/foo".into()))
+    );
+}
+
+#[test]
+fn fallback_options() {
+    use crate::ResolveContext;
+
+    let f = super::fixture();
+    let base = ResolveOptions { fully_specified: true, ..ResolveOptions::default() };
+    let resolver = Resolver::new(ResolveOptions {
+        fallback_options: vec![
+            ResolveOptions { extensions: vec![".json".into()], ..base.clone() },
+            ResolveOptions { fully_specified: false, ..base.clone() },
+        ],
+        ..base
+    });
+
+    // The first option set resolves.
+    let resolved_path = resolver.resolve(&f, "./main1.js").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(f.join("main1.js")));
+
+    // The first fallback does not help, the second one does.
+    let mut ctx = ResolveContext::default();
+    let resolved_path =
+        resolver.resolve_with_context(&f, "./main1?query", &mut ctx).map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(f.join("main1.js?query")));
+    // Dependencies of the failed attempts are kept.
+    assert!(ctx.missing_dependencies.contains(&f.join("main1")));
+
+    assert_eq!(resolver.resolve(&f, "./missing"), Err(ResolveError::NotFound("./missing".into())));
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn dedupe() {
+    use super::memory_fs::MemoryFS;
+    use crate::ResolverGeneric;
+    use std::path::PathBuf;
+
+    let file_system = MemoryFS::new(&[
+        ("/app/node_modules/react/package.json", r#"{ "name": "react" }"#),
+        ("/app/node_modules/react/index.js", ""),
+        ("/app/node_modules/react/jsx-runtime.js", ""),
+        ("/linked/lib/node_modules/react/package.json", r#"{ "name": "react" }"#),
+        ("/linked/lib/node_modules/react/index.js", ""),
+        ("/linked/lib/node_modules/react/jsx-runtime.js", ""),
+        ("/linked/lib/node_modules/react-dom/index.js", ""),
+    ]);
+    let resolver = ResolverGeneric::<MemoryFS>::new_with_file_system(
+        file_system,
+        ResolveOptions {
+            dedupe: vec!["react".into()],
+            dedupe_root: Some(PathBuf::from("/app")),
+            ..ResolveOptions::default()
+        },
+    );
+
+    #[rustfmt::skip]
+    let pass = [
+        ("dedupe", "react", "/app/node_modules/react/index.js"),
+        ("dedupe subpath", "react/jsx-runtime", "/app/node_modules/react/jsx-runtime.js"),
+        ("not dedupe", "react-dom", "/linked/lib/node_modules/react-dom/index.js"),
+    ];
+
+    for (comment, request, expected) in pass {
+        let resolved_path = resolver.resolve("/linked/lib", request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(PathBuf::from(expected)), "{comment} {request}");
+    }
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn modules_boundary() {
+    use super::memory_fs::MemoryFS;
+    use crate::{ModulesBoundary, ResolverGeneric};
+    use std::path::PathBuf;
+
+    let file_system = MemoryFS::new(&[
+        ("/home/node_modules/stray/index.js", ""),
+        ("/home/project/.git/HEAD", ""),
+        ("/home/project/node_modules/dep/index.js", ""),
+        ("/home/project/packages/app/index.js", ""),
+    ]);
+    let resolver = ResolverGeneric::<MemoryFS>::new_with_file_system(
+        file_system,
+        ResolveOptions {
+            modules_boundary: Some(ModulesBoundary::Git),
+            ..ResolveOptions::default()
+        },
+    );
+    let f = "/home/project/packages/app";
+
+    let resolved_path = resolver.resolve(f, "dep").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(PathBuf::from("/home/project/node_modules/dep/index.js")));
+    assert_eq!(resolver.resolve(f, "stray"), Err(ResolveError::NotFound("stray".into())));
+    assert_eq!(
+        resolver.resolve_package_dir(f, "stray"),
+        Err(ResolveError::NotFound("stray".into()))
+    );
+
+    let resolver = resolver.clone_with_options(ResolveOptions {
+        modules_boundary: Some(ModulesBoundary::Path(PathBuf::from("/home/project/packages"))),
+        ..ResolveOptions::default()
+    });
+    assert_eq!(resolver.resolve(f, "dep"), Err(ResolveError::NotFound("dep".into())));
+    // Importers outside of the boundary are not limited.
+    let resolved_path = resolver.resolve("/home/project", "stray").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(PathBuf::from("/home/node_modules/stray/index.js")));
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn modules_provider() {
+    use super::memory_fs::MemoryFS;
+    use crate::{ModulesProvider, ResolverGeneric};
+    use std::path::{Path, PathBuf};
+
+    let file_system = MemoryFS::new(&[
+        ("/runfiles/npm/dep/package.json", r#"{ "name": "dep", "exports": "./main.js" }"#),
+        ("/runfiles/npm/dep/main.js", ""),
+        ("/runfiles/npm/other/index.js", ""),
+        ("/workspace/app/node_modules/other/index.js", ""),
+        ("/workspace/app/package.json", r##"{ "imports": { "#dep": "dep" } }"##),
+        ("/workspace/app/index.js", ""),
+    ]);
+    let resolver = ResolverGeneric::<MemoryFS>::new_with_file_system(
+        file_system,
+        ResolveOptions {
+            modules_provider: Some(ModulesProvider::new(|directory| {
+                if directory.starts_with("/workspace") {
+                    vec![PathBuf::from("/missing"), PathBuf::from("/runfiles/npm")]
+                } else {
+                    vec![]
+                }
+            })),
+            ..ResolveOptions::default()
+        },
+    );
+    let f = Path::new("/workspace/app");
+
+    #[rustfmt::skip]
+    let pass = [
+        ("provided module directory", "dep", "/runfiles/npm/dep/main.js"),
+        ("node_modules first", "other", "/workspace/app/node_modules/other/index.js"),
+        ("imports field", "#dep", "/runfiles/npm/dep/main.js"),
+    ];
+
+    for (comment, request, expected) in pass {
+        let resolved_path = resolver.resolve(f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(PathBuf::from(expected)), "{comment} {request}");
+    }
+
+    assert_eq!(resolver.resolve_package_dir(f, "dep"), Ok(PathBuf::from("/runfiles/npm/dep")));
+    assert_eq!(resolver.resolve("/", "dep"), Err(ResolveError::NotFound("dep".into())));
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn self_reference_without_exports() {
+    use super::memory_fs::MemoryFS;
+    use crate::ResolverGeneric;
+    use std::path::PathBuf;
+
+    let file_system = MemoryFS::new(&[
+        ("/legacy/package.json", r#"{ "name": "legacy", "main": "lib/main.js" }"#),
+        ("/legacy/lib/main.js", ""),
+        ("/legacy/lib/util.js", ""),
+        ("/legacy/src/index.js", ""),
+        ("/legacy/node_modules/other/index.js", ""),
+    ]);
+    let resolver =
+        ResolverGeneric::<MemoryFS>::new_with_file_system(file_system, ResolveOptions::default());
+
+    #[rustfmt::skip]
+    let pass = [
+        ("self", "legacy", "/legacy/lib/main.js"),
+        ("self subpath", "legacy/lib/util", "/legacy/lib/util.js"),
+        ("other package", "other", "/legacy/node_modules/other/index.js"),
+    ];
+
+    for (comment, request, expected) in pass {
+        let resolved_path = resolver.resolve("/legacy/src", request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(PathBuf::from(expected)), "{comment} {request}");
+    }
+
+    assert_eq!(
+        resolver.resolve("/legacy/src", "legacy/missing"),
+        Err(ResolveError::NotFound("legacy/missing".into()))
+    );
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn package_json_get_field() {
+    use super::memory_fs::MemoryFS;
+    use crate::ResolverGeneric;
+
+    let file_system = MemoryFS::new(&[
+        (
+            "/node_modules/pkg/package.json",
+            r#"{ "unpkg": "dist/pkg.min.js", "rspack": { "entry": { "main": "./src" } } }"#,
+        ),
+        ("/node_modules/pkg/index.js", ""),
+    ]);
+    let resolver =
+        ResolverGeneric::<MemoryFS>::new_with_file_system(file_system, ResolveOptions::default());
+    let resolution = resolver.resolve("/", "pkg").unwrap();
+    let package_json = resolution.package_json().unwrap();
+
+    assert_eq!(package_json.get_field(&["unpkg"]), Some(&"dist/pkg.min.js".into()));
+    assert_eq!(package_json.get_field(&["rspack", "entry", "main"]), Some(&"./src".into()));
+    assert_eq!(package_json.get_field(&["rspack", "missing"]), None);
+    assert_eq!(package_json.get_field(&["unpkg", "nested"]), None);
+    assert_eq!(package_json.get_field(&[]), None);
+}
+
+#[test]
+#[cfg(all(feature = "package_json_raw_json_api", not(target_os = "windows")))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn package_json_removed_fields() {
+    use super::memory_fs::MemoryFS;
+    use crate::ResolverGeneric;
+
+    let package_json = r#"{ "description": "d", "scripts": { "build": "b" }, "rspack": {} }"#;
+    let file_system = || {
+        MemoryFS::new(&[
+            ("/node_modules/pkg/package.json", package_json),
+            ("/node_modules/pkg/index.js", ""),
+        ])
+    };
+
+    let resolver =
+        ResolverGeneric::<MemoryFS>::new_with_file_system(file_system(), ResolveOptions::default());
+    let resolution = resolver.resolve("/", "pkg").unwrap();
+    let raw_json = resolution.package_json().unwrap().raw_json();
+    assert!(raw_json.get("description").is_none());
+    assert!(raw_json.get("scripts").is_none());
+    assert!(raw_json.get("rspack").is_some());
+
+    let resolver = ResolverGeneric::<MemoryFS>::new_with_file_system(
+        file_system(),
+        ResolveOptions { package_json_removed_fields: vec![], ..ResolveOptions::default() },
+    );
+    let resolution = resolver.resolve("/", "pkg").unwrap();
+    let raw_json = resolution.package_json().unwrap().raw_json();
+    assert!(raw_json.get("description").is_some());
+    assert!(raw_json.get("scripts").is_some());
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn package_json_provider() {
+    use std::{
+        path::{Path, PathBuf},
+        sync::Arc,
+    };
+
+    use super::memory_fs::MemoryFS;
+    use crate::{PackageJson, ResolverGeneric};
+
+    let file_system = MemoryFS::new(&[
+        ("/node_modules/provided/package.json", r#"{ "main": "./fs.js" }"#),
+        ("/node_modules/provided/fs.js", ""),
+        ("/node_modules/provided/provided.js", ""),
+        ("/node_modules/other/package.json", r#"{ "main": "./fs.js" }"#),
+        ("/node_modules/other/fs.js", ""),
+    ]);
+    let provider = |directory: &Path| {
+        (directory == Path::new("/node_modules/provided")).then(|| {
+            let path = directory.join("package.json");
+            let value = serde_json::json!({ "name": "provided", "main": "./provided.js" });
+            Arc::new(PackageJson::from_value(path.clone(), path, value))
+        })
+    };
+    let resolver = ResolverGeneric::<MemoryFS>::new_with_file_system(
+        file_system,
+        ResolveOptions {
+            package_json_provider: Some(Arc::new(provider)),
+            ..ResolveOptions::default()
+        },
+    );
+
+    let resolution = resolver.resolve("/", "provided").unwrap();
+    assert_eq!(resolution.path(), Path::new("/node_modules/provided/provided.js"));
+    assert_eq!(resolution.package_json().unwrap().name.as_deref(), Some("provided"));
+
+    // Directories without a provided package.json are read from the file system.
+    let resolution = resolver.resolve("/", "other").unwrap();
+    assert_eq!(resolution.path(), PathBuf::from("/node_modules/other/fs.js"));
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn trailing_slash() {
+    use std::path::PathBuf;
+
+    use super::memory_fs::MemoryFS;
+    use crate::{ResolverGeneric, TrailingSlash};
+
+    let resolver = |trailing_slash| {
+        let file_system = MemoryFS::new(&[("/dir/index.js", ""), ("/file.js", "")]);
+        ResolverGeneric::<MemoryFS>::new_with_file_system(
+            file_system,
+            ResolveOptions { trailing_slash, ..ResolveOptions::default() },
+        )
+    };
+
+    #[rustfmt::skip]
+    let data = [
+        (TrailingSlash::Index, "./dir/", Ok(PathBuf::from("/dir/index.js"))),
+        (TrailingSlash::Index, "./file.js/", Err(ResolveError::NotFound("./file.js/".into()))),
+        (TrailingSlash::Context, "./dir/", Ok(PathBuf::from("/dir"))),
+        (TrailingSlash::Context, "./file.js/", Err(ResolveError::NotFound("./file.js/".into()))),
+        (TrailingSlash::Strict, "./dir/", Ok(PathBuf::from("/dir/index.js"))),
+        (TrailingSlash::Strict, "./file.js/", Err(ResolveError::NotADirectory(PathBuf::from("/file.js")))),
+        (TrailingSlash::Strict, "./missing/", Err(ResolveError::NotFound("./missing/".into()))),
+    ];
+
+    for (trailing_slash, request, expected) in data {
+        let resolved_path = resolver(trailing_slash).resolve("/", request).map(|r| r.full_path());
+        assert_eq!(resolved_path, expected, "{trailing_slash:?} {request}");
+    }
+
+    // Requests without a trailing slash are not affected.
+    let resolved_path =
+        resolver(TrailingSlash::Context).resolve("/", "./dir").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(PathBuf::from("/dir/index.js")));
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn strict_dependencies() {
+    use std::path::{Path, PathBuf};
+
+    use super::memory_fs::MemoryFS;
+    use crate::{ResolverGeneric, StrictDependencies};
+
+    let resolver = |strict_dependencies| {
+        let file_system = MemoryFS::new(&[
+            (
+                "/app/package.json",
+                r#"{ "name": "app", "dependencies": { "a": "1" }, "devDependencies": { "b": "1" } }"#,
+            ),
+            ("/app/index.js", ""),
+            ("/node_modules/a/index.js", ""),
+            ("/node_modules/b/index.js", ""),
+            ("/node_modules/phantom/index.js", ""),
+        ]);
+        ResolverGeneric::<MemoryFS>::new_with_file_system(
+            file_system,
+            ResolveOptions { strict_dependencies, ..ResolveOptions::default() },
+        )
+    };
+    let undeclared = |name: &str| {
+        Err(ResolveError::UndeclaredDependency(name.into(), PathBuf::from("/app/package.json")))
+    };
+
+    let resolver_with_dev = resolver(Some(StrictDependencies::default()));
+    #[rustfmt::skip]
+    let data = [
+        ("dependencies", "a", Ok(PathBuf::from("/node_modules/a/index.js"))),
+        ("devDependencies", "b/index.js", Ok(PathBuf::from("/node_modules/b/index.js"))),
+        ("self reference", "app", Ok(PathBuf::from("/app/index.js"))),
+        ("phantom dependency", "phantom", undeclared("phantom")),
+        ("builtin", "node:fs", Err(ResolveError::NotFound("node:fs".into()))),
+    ];
+    for (comment, request, expected) in data {
+        let resolved_path = resolver_with_dev.resolve("/app", request).map(|r| r.full_path());
+        assert_eq!(resolved_path, expected, "{comment} {request}");
+    }
+
+    let resolver_without_dev = resolver(Some(StrictDependencies {
+        dev_dependencies: false,
+        ..StrictDependencies::default()
+    }));
+    let resolved_path = resolver_without_dev.resolve("/app", "b").map(|r| r.full_path());
+    assert_eq!(resolved_path, undeclared("b"));
+
+    let resolved_path = resolver(None).resolve("/app", "phantom").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(Path::new("/node_modules/phantom/index.js").to_path_buf()));
+}
+
+#[cfg(feature = "camino")]
+#[test]
+fn resolve_utf8() {
+    let f = super::fixture();
+    let resolver = Resolver::default();
+    let directory = camino::Utf8PathBuf::from_path_buf(f).unwrap();
+    let resolved_path = resolver.resolve_utf8(&directory, "./main1?query");
+    assert_eq!(resolved_path, Ok(directory.join("main1.js?query")));
+    assert_eq!(
+        resolver.resolve_utf8(&directory, "./missing"),
+        Err(ResolveError::NotFound("./missing".into()))
+    );
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn description_files_boundary() {
+    use super::memory_fs::MemoryFS;
+    use crate::{DescriptionFilesBoundary, ResolverGeneric};
+    use std::path::PathBuf;
+
+    let resolver = |description_files_boundary| {
+        let file_system = MemoryFS::new(&[
+            ("/home/package.json", r#"{"name":"stray"}"#),
+            ("/home/project/src/index.js", ""),
+            ("/home/project/node_modules/no-manifest/index.js", ""),
+        ]);
+        ResolverGeneric::new_with_file_system(
+            file_system,
+            ResolveOptions { description_files_boundary, ..ResolveOptions::default() },
+        )
+    };
+    let package_json_path = |boundary, specifier| {
+        resolver(boundary)
+            .resolve("/home/project/src", specifier)
+            .unwrap()
+            .package_json()
+            .map(|package_json| package_json.path.clone())
+    };
+    let stray = Some(PathBuf::from("/home/package.json"));
+
+    assert_eq!(package_json_path(None, "./index.js"), stray);
+    assert_eq!(package_json_path(None, "no-manifest"), stray);
+
+    let project = DescriptionFilesBoundary::Path(PathBuf::from("/home/project"));
+    assert_eq!(package_json_path(Some(project.clone()), "./index.js"), None);
+    assert_eq!(package_json_path(Some(project), "no-manifest"), None);
+    // Importers outside of the boundary are not limited.
+    let other = DescriptionFilesBoundary::Path(PathBuf::from("/other"));
+    assert_eq!(package_json_path(Some(other), "./index.js"), stray);
+
+    let node_modules = Some(DescriptionFilesBoundary::NodeModules);
+    assert_eq!(package_json_path(node_modules.clone(), "no-manifest"), None);
+    assert_eq!(package_json_path(node_modules, "./index.js"), stray);
+}
+
+#[test]
+fn forward_slashes() {
+    let f = super::fixture();
+
+    let resolver =
+        Resolver::new(ResolveOptions { forward_slashes: true, ..ResolveOptions::default() });
+    let resolution = resolver.resolve(&f, "./main1?query").unwrap();
+    if cfg!(windows) {
+        assert!(!resolution.path().to_string_lossy().contains('\\'));
+    } else {
+        assert_eq!(resolution.path(), f.join("main1.js"));
+    }
+    assert_eq!(resolution.path_hash(), crate::cache::hash_path(resolution.path()));
+    assert_eq!(resolution.query(), Some("?query"));
+}
+
+#[test]
+fn relative_path_base() {
+    use std::path::{Path, PathBuf};
+
+    let f = super::fixture();
+
+    let resolver = Resolver::new(ResolveOptions {
+        relative_path_base: Some(f.join("node_modules/m1")),
+        ..ResolveOptions::default()
+    });
+    let resolution = resolver.resolve(&f, "./main1?query").unwrap();
+    assert_eq!(resolution.path(), f.join("main1.js"));
+    assert_eq!(resolution.relative_path(), Some(Path::new("../../main1.js")));
+    assert_eq!(resolution.relative_full_path(), Some(PathBuf::from("../../main1.js?query")));
+    let resolution = resolver.resolve(&f, "m1/a").unwrap();
+    assert_eq!(resolution.relative_path(), Some(Path::new("a.js")));
+
+    let resolution = Resolver::default().resolve(&f, "./main1").unwrap();
+    assert_eq!(resolution.relative_path(), None);
+}
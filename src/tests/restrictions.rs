@@ -1,6 +1,9 @@
 //! <https://github.com/webpack/enhanced-resolve/blob/main/test/restrictions.test.js>
 
-use crate::{ResolveError, ResolveOptions, Resolver, Restriction};
+use crate::{
+    ResolveAttempt, ResolveAttemptReason, ResolveContext, ResolveError, ResolveOptions, Resolver,
+    Restriction,
+};
 
 // TODO: regex
 // * should respect RegExp restriction
@@ -21,5 +24,29 @@ fn restriction1() {
     });
 
     let resolution = resolver.resolve(&f, "pck2");
+    let error = ResolveError::Restriction(fixture.join("c.js"), f.clone());
+    assert_eq!(resolution, Err(error.clone()));
+    assert_eq!(
+        error.to_string(),
+        format!(r#"Path "{}" is restricted by "{}""#, fixture.join("c.js").display(), f.display())
+    );
+}
+
+#[test]
+fn restricted_attempt() {
+    let fixture = super::fixture();
+    let f = fixture.join("restrictions");
+
+    let resolver = Resolver::new(ResolveOptions {
+        extensions: vec![".js".into()],
+        restrictions: vec![Restriction::Path(f.clone())],
+        ..ResolveOptions::default()
+    });
+
+    let mut ctx = ResolveContext { attempts: Some(vec![]), ..ResolveContext::default() };
+    let resolution = resolver.resolve_with_context(&f, "pck2", &mut ctx);
     assert_eq!(resolution, Err(ResolveError::Restriction(fixture.join("c.js"), f)));
+    let attempt =
+        ResolveAttempt { path: fixture.join("c.js"), reason: ResolveAttemptReason::Restricted };
+    assert_eq!(ctx.attempts.unwrap().last(), Some(&attempt));
 }
@@ -90,3 +90,63 @@ fn roots_fall_through() {
         Ok(absolute_path)
     );
 }
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn root_prefixes() {
+    use super::memory_fs::MemoryFS;
+    use crate::{ResolverGeneric, RootPrefixOptions};
+    use std::path::Path;
+
+    let file_system = MemoryFS::new(&[
+        ("/app/package.json", "{}"),
+        ("/app/src/utils.js", ""),
+        ("/app/src/page/index.js", ""),
+        ("/app/packages/nested/package.json", "{}"),
+        ("/app/packages/nested/src/utils.js", ""),
+        ("/config/src/utils.js", ""),
+        ("/absolute.js", ""),
+    ]);
+    let resolver = ResolverGeneric::<MemoryFS>::new_with_file_system(
+        file_system,
+        ResolveOptions {
+            root_prefixes: Some(RootPrefixOptions { tilde: true, slash: true, root: None }),
+            ..ResolveOptions::default()
+        },
+    );
+
+    #[rustfmt::skip]
+    let pass = [
+        ("tilde", "/app/src/page", "~/src/utils", "/app/src/utils.js"),
+        ("slash", "/app/src/page", "/src/utils", "/app/src/utils.js"),
+        ("nearest package.json", "/app/packages/nested/src", "~/src/utils.js", "/app/packages/nested/src/utils.js"),
+        ("directory", "/app", "~/src/page", "/app/src/page/index.js"),
+        ("slash falls back to the absolute path", "/app/src", "/absolute.js", "/absolute.js"),
+    ];
+
+    for (comment, path, request, expected) in pass {
+        let resolved_path = resolver.resolve(path, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(PathBuf::from(expected)), "{comment} {path} {request}");
+    }
+
+    assert_eq!(
+        resolver.resolve("/app/src", "~/missing").map(|r| r.full_path()),
+        Err(ResolveError::NotFound("~/missing".into()))
+    );
+
+    let resolver = resolver.clone_with_options(ResolveOptions {
+        root_prefixes: Some(RootPrefixOptions {
+            tilde: true,
+            slash: false,
+            root: Some(PathBuf::from("/config")),
+        }),
+        ..ResolveOptions::default()
+    });
+    let resolved_path =
+        resolver.resolve(Path::new("/app/src"), "~/src/utils").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(PathBuf::from("/config/src/utils.js")));
+    assert_eq!(
+        resolver.resolve("/app/src", "/src/utils").map(|r| r.full_path()),
+        Err(ResolveError::NotFound("/src/utils".into()))
+    );
+}
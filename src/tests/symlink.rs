@@ -1,6 +1,9 @@
 use std::{fs, io, path::Path};
 
-use crate::{ResolveOptions, Resolver};
+use crate::{
+    FileSystem, FileSystemOs, ResolveError, ResolveOptions, Resolver, SymlinkEscapeOptions,
+    SymlinkEscapePolicy,
+};
 
 #[derive(Debug, Clone, Copy)]
 enum FileType {
@@ -117,5 +120,101 @@ fn test() -> io::Result<()> {
         assert_eq!(resolved_path, Ok(path.join(request)));
     }
 
+    // Escaping the allowed roots through a symlink fails regardless of `symlinks`.
+    for symlinks in [true, false] {
+        let resolver = Resolver::new(ResolveOptions {
+            symlinks,
+            allowed_roots: vec![temp_path.clone()],
+            ..ResolveOptions::default()
+        });
+        let resolution = resolver.resolve(&temp_path, "./index.js");
+        assert_eq!(
+            resolution,
+            Err(ResolveError::OutsideAllowedRoots(root.join("lib/index.js"))),
+            "{symlinks}"
+        );
+
+        let resolver = Resolver::new(ResolveOptions {
+            symlinks,
+            allowed_roots: vec![root.clone()],
+            ..ResolveOptions::default()
+        });
+        assert!(resolver.resolve(&temp_path, "./index.js").is_ok(), "{symlinks}");
+    }
+
+    #[rustfmt::skip]
+    let policies = [
+        (SymlinkEscapePolicy::Canonical, Ok(root.join("lib/index.js"))),
+        (SymlinkEscapePolicy::Original, Ok(temp_path.join("index.js"))),
+        (SymlinkEscapePolicy::Error, Err(ResolveError::SymlinkEscape(temp_path.join("index.js"), root.join("lib/index.js")))),
+    ];
+
+    for (policy, expected) in policies {
+        let resolver = Resolver::new(ResolveOptions {
+            symlink_escape: Some(SymlinkEscapeOptions { roots: vec![temp_path.clone()], policy }),
+            ..ResolveOptions::default()
+        });
+        let resolved_path = resolver.resolve(&temp_path, "./index.js").map(|r| r.full_path());
+        assert_eq!(resolved_path, expected, "{policy:?}");
+
+        // Not an escape when the real path is inside the roots.
+        let resolver = Resolver::new(ResolveOptions {
+            symlink_escape: Some(SymlinkEscapeOptions { roots: vec![root.clone()], policy }),
+            ..ResolveOptions::default()
+        });
+        let resolved_path = resolver.resolve(&temp_path, "./index.js").map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(root.join("lib/index.js")), "{policy:?}");
+    }
+
+    // `read_link` returns the target as written in the link.
+    let fs = FileSystemOs::default();
+    assert_eq!(
+        fs.read_link(&temp_path.join("node.relative.sym.js"))?,
+        Path::new("./node.relative.js")
+    );
+    assert_eq!(fs.read_link(&temp_path.join("that"))?, temp_path.join("this"));
+    assert!(fs.read_link(&root.join("lib/index.js")).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn invalidate() -> io::Result<()> {
+    let temp_path = std::env::temp_dir().join("rspack_resolver_invalidate");
+    _ = fs::remove_dir_all(&temp_path);
+    for package in ["pkg-a", "pkg-b"] {
+        fs::create_dir_all(temp_path.join(package))?;
+        fs::write(temp_path.join(package).join("index.js"), "")?;
+    }
+    fs::create_dir(temp_path.join("node_modules"))?;
+    let link = temp_path.join("node_modules/pkg");
+    if symlink(temp_path.join("pkg-a"), &link, FileType::Dir).is_err() {
+        // Creating symlinks requires administrator privileges on Windows.
+        _ = fs::remove_dir_all(&temp_path);
+        return Ok(());
+    }
+    let temp_path = temp_path.canonicalize()?;
+    let link = temp_path.join("node_modules/pkg");
+    let relink = |target: &str| -> io::Result<()> {
+        fs::remove_file(&link).or_else(|_| fs::remove_dir(&link))?;
+        symlink(temp_path.join(target), &link, FileType::Dir)
+    };
+
+    let resolver = Resolver::default();
+    let resolve = || resolver.resolve(&temp_path, "pkg").map(|r| r.full_path());
+    assert_eq!(resolve(), Ok(temp_path.join("pkg-a/index.js")));
+
+    // The real path is cached until the link is invalidated.
+    relink("pkg-b")?;
+    assert_eq!(resolve(), Ok(temp_path.join("pkg-a/index.js")));
+    resolver.invalidate(&link);
+    assert_eq!(resolve(), Ok(temp_path.join("pkg-b/index.js")));
+
+    // The `node_modules` directory cached by its parent is looked up again.
+    relink("pkg-a")?;
+    resolver.invalidate(&temp_path.join("node_modules"));
+    assert_eq!(resolve(), Ok(temp_path.join("pkg-a/index.js")));
+
+    _ = fs::remove_dir_all(&temp_path);
     Ok(())
 }
@@ -5,8 +5,8 @@
 use std::path::{Path, PathBuf};
 
 use crate::{
-    JSONError, ResolveError, ResolveOptions, Resolver, TsConfig, TsconfigOptions,
-    TsconfigReferences,
+    AliasValue, JSONError, ResolveError, ResolveOptions, Resolver, TsConfig, TsconfigOptions,
+    TsconfigPathsPrecedence, TsconfigReferences,
 };
 
 // <https://github.com/parcel-bundler/parcel/blob/b6224fd519f95e68d8b93ba90376fd94c8b76e69/packages/utils/node-resolver-rs/src/lib.rs#L2303>
@@ -33,6 +33,7 @@ fn tsconfig() {
             tsconfig: Some(TsconfigOptions {
                 config_file: dir.join("tsconfig.json"),
                 references: TsconfigReferences::Auto,
+                content: None,
             }),
             ..ResolveOptions::default()
         });
@@ -50,6 +51,7 @@ fn tsconfig() {
         tsconfig: Some(TsconfigOptions {
             config_file: f.join("tsconfig.json"),
             references: TsconfigReferences::Auto,
+            content: None,
         }),
         ..ResolveOptions::default()
     });
@@ -67,6 +69,7 @@ fn tsconfig_fallthrough() {
         tsconfig: Some(TsconfigOptions {
             config_file: f.join("tsconfig.json"),
             references: TsconfigReferences::Auto,
+            content: None,
         }),
         ..ResolveOptions::default()
     });
@@ -83,6 +86,7 @@ fn json_with_comments() {
         tsconfig: Some(TsconfigOptions {
             config_file: f.join("tsconfig.json"),
             references: TsconfigReferences::Auto,
+            content: None,
         }),
         ..ResolveOptions::default()
     });
@@ -99,6 +103,7 @@ fn broken() {
         tsconfig: Some(TsconfigOptions {
             config_file: f.join("tsconfig_broken.json"),
             references: TsconfigReferences::Auto,
+            content: None,
         }),
         ..ResolveOptions::default()
     });
@@ -114,6 +119,211 @@ fn broken() {
     assert!(matches!(resolved_path, Err(ResolveError::JSON(_))));
 }
 
+#[test]
+fn tsconfig_provider() {
+    use std::sync::Arc;
+
+    let f = super::fixture_root().join("tsconfig");
+    let tsconfig_path = f.join("tsconfig.json");
+
+    let provided_path = tsconfig_path.clone();
+    let provider = move |path: &Path| {
+        (path == provided_path)
+            .then(|| r#"{ "compilerOptions": { "paths": { "provided": ["./foo.js"] } } }"#.into())
+    };
+    let resolver = Resolver::new(ResolveOptions {
+        tsconfig: Some(TsconfigOptions {
+            config_file: tsconfig_path,
+            references: TsconfigReferences::Auto,
+            content: None,
+        }),
+        tsconfig_provider: Some(Arc::new(provider)),
+        ..ResolveOptions::default()
+    });
+
+    let resolved_path = resolver.resolve(&f, "provided").map(|f| f.full_path());
+    assert_eq!(resolved_path, Ok(f.join("foo.js")));
+    // The paths of the tsconfig file on disk are not used.
+    let resolved_path = resolver.resolve(&f, "ts-path").map(|f| f.full_path());
+    assert_eq!(resolved_path, Err(ResolveError::NotFound("ts-path".into())));
+}
+
+#[test]
+fn tsconfig_discovery() {
+    let f = super::fixture_root().join("tsconfig");
+
+    let resolver =
+        Resolver::new(ResolveOptions { tsconfig_discovery: true, ..ResolveOptions::default() });
+
+    #[rustfmt::skip]
+    let pass = [
+        (f.clone(), "ts-path", f.join("foo.js")),
+        (f.join("nested"), "ts-path", f.join("nested/test.js")),
+        (f.join("cases/extends-paths/src"), "@/index", f.join("cases/extends-paths/src/index.js")),
+        (f.join("cases/jsconfig/src"), "@/index", f.join("cases/jsconfig/src/index.js")),
+    ];
+
+    for (path, request, expected) in pass {
+        let resolved_path = resolver.resolve(&path, request).map(|f| f.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{request} {path:?}");
+    }
+
+    // Not used when disabled.
+    let resolver = Resolver::default();
+    let resolved_path = resolver.resolve(&f, "ts-path").map(|f| f.full_path());
+    assert_eq!(resolved_path, Err(ResolveError::NotFound("ts-path".into())));
+}
+
+#[test]
+fn tsconfig_discovery_invalidate() {
+    use crate::ResolveContext;
+
+    // Canonicalized for comparing with resolved real paths, the temp directory is a symlink on macOS.
+    let dir = dunce::canonicalize(std::env::temp_dir())
+        .unwrap()
+        .join("rspack_resolver_tsconfig_discovery_invalidate");
+    _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    std::fs::create_dir_all(dir.join("lib")).unwrap();
+    std::fs::write(dir.join("lib/foo.js"), "").unwrap();
+    let tsconfig = dir.join("tsconfig.json");
+
+    let resolver =
+        Resolver::new(ResolveOptions { tsconfig_discovery: true, ..ResolveOptions::default() });
+    let mut ctx = ResolveContext::default();
+    let resolved_path =
+        resolver.resolve_with_context(dir.join("src"), "@/foo", &mut ctx).map(|f| f.full_path());
+    assert_eq!(resolved_path, Err(ResolveError::NotFound("@/foo".into())));
+    // A tsconfig created at any of these paths changes the resolution.
+    assert!(ctx.missing_dependencies.contains(&dir.join("src/tsconfig.json")));
+    assert!(ctx.missing_dependencies.contains(&dir.join("src/jsconfig.json")));
+    assert!(ctx.missing_dependencies.contains(&tsconfig));
+
+    // Discovered once the created tsconfig is invalidated.
+    std::fs::write(&tsconfig, r#"{ "compilerOptions": { "paths": { "@/*": ["./lib/*"] } } }"#)
+        .unwrap();
+    resolver.invalidate(&tsconfig);
+    let mut ctx = ResolveContext::default();
+    let resolved_path =
+        resolver.resolve_with_context(dir.join("src"), "@/foo", &mut ctx).map(|f| f.full_path());
+    assert_eq!(resolved_path, Ok(dir.join("lib/foo.js")));
+    assert!(ctx.file_dependencies.contains(&tsconfig));
+    assert!(!ctx.missing_dependencies.contains(&tsconfig));
+
+    _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn tsconfig_content() {
+    let f = super::fixture_root().join("tsconfig/cases/extends-paths");
+
+    // `config_file` does not exist, `extends` resolves against its directory.
+    let resolver = Resolver::new(ResolveOptions {
+        tsconfig: Some(TsconfigOptions {
+            config_file: f.join("tsconfig.virtual.json"),
+            references: TsconfigReferences::Auto,
+            content: Some(
+                r#"{ "extends": "./tsconfig.base.json", "compilerOptions": { "baseUrl": "src" } }"#
+                    .into(),
+            ),
+        }),
+        ..ResolveOptions::default()
+    });
+    let resolved_path = resolver.resolve(f.join("src"), "@/index").map(|f| f.full_path());
+    assert_eq!(resolved_path, Ok(f.join("src/index.js")));
+
+    // Deserializes from a tsconfig object.
+    let options: TsconfigOptions = serde_json::from_value(serde_json::json!({
+        "config_file": "tsconfig.virtual.json",
+        "content": { "compilerOptions": { "baseUrl": "src" } }
+    }))
+    .unwrap();
+    assert_eq!(options.content.as_deref(), Some(r#"{"compilerOptions":{"baseUrl":"src"}}"#));
+}
+
+#[test]
+fn tsconfig_scopes() {
+    let f = super::fixture_root().join("tsconfig");
+
+    let scope = |directory: &str, config_file: &str| {
+        let tsconfig = TsconfigOptions {
+            config_file: f.join(directory).join(config_file),
+            references: TsconfigReferences::Disabled,
+            content: None,
+        };
+        (f.join(directory), tsconfig)
+    };
+    let resolver = Resolver::new(ResolveOptions {
+        tsconfig: Some(TsconfigOptions {
+            config_file: f.join("tsconfig.json"),
+            references: TsconfigReferences::Disabled,
+            content: None,
+        }),
+        tsconfig_scopes: vec![
+            scope("cases/extends-paths", "tsconfig.json"),
+            scope("cases/jsconfig", "jsconfig.json"),
+        ],
+        ..ResolveOptions::default()
+    });
+
+    #[rustfmt::skip]
+    let pass = [
+        (f.join("cases/extends-paths/src"), "@/index", f.join("cases/extends-paths/src/index.js")),
+        (f.join("cases/jsconfig/src"), "@/index", f.join("cases/jsconfig/src/index.js")),
+        // Outside of the scopes.
+        (f.clone(), "ts-path", f.join("foo.js")),
+    ];
+
+    for (path, request, expected) in pass {
+        let resolved_path = resolver.resolve(&path, request).map(|f| f.full_path());
+        assert_eq!(resolved_path, Ok(expected), "{request} {path:?}");
+    }
+
+    // The scoped tsconfig replaces `tsconfig`.
+    let resolved_path =
+        resolver.resolve(f.join("cases/jsconfig"), "ts-path").map(|f| f.full_path());
+    assert_eq!(resolved_path, Err(ResolveError::NotFound("ts-path".into())));
+}
+
+#[test]
+fn tsconfig_paths_precedence() {
+    let f = super::fixture_root().join("tsconfig");
+
+    let options = ResolveOptions {
+        tsconfig: Some(TsconfigOptions {
+            config_file: f.join("tsconfig.json"),
+            references: TsconfigReferences::Disabled,
+            content: None,
+        }),
+        alias: vec![(
+            "ts-path".into(),
+            vec![AliasValue::Path(f.join("nested/test.js").to_string_lossy().to_string())],
+        )],
+        ..ResolveOptions::default()
+    };
+
+    // tsconfig paths win over aliases by default.
+    let resolver = Resolver::new(options.clone());
+    let resolved_path = resolver.resolve(&f, "ts-path").map(|f| f.full_path());
+    assert_eq!(resolved_path, Ok(f.join("foo.js")));
+
+    let resolver = Resolver::new(ResolveOptions {
+        tsconfig_paths_precedence: TsconfigPathsPrecedence::Fallback,
+        ..options.clone()
+    });
+    let resolved_path = resolver.resolve(&f, "ts-path").map(|f| f.full_path());
+    assert_eq!(resolved_path, Ok(f.join("nested/test.js")));
+
+    // Still used when nothing else resolves.
+    let resolver = Resolver::new(ResolveOptions {
+        alias: vec![],
+        tsconfig_paths_precedence: TsconfigPathsPrecedence::Fallback,
+        ..options
+    });
+    let resolved_path = resolver.resolve(&f, "ts-path").map(|f| f.full_path());
+    assert_eq!(resolved_path, Ok(f.join("foo.js")));
+}
+
 // <https://github.com/parcel-bundler/parcel/blob/c8f5c97a01f643b4d5c333c02d019ef2618b44a5/packages/utils/node-resolver-rs/src/tsconfig.rs#L193C12-L193C12>
 #[test]
 fn test_paths() {
@@ -229,6 +439,7 @@ fn test_template_variable() {
             tsconfig: Some(TsconfigOptions {
                 config_file: dir.join(tsconfig),
                 references: TsconfigReferences::Auto,
+                content: None,
             }),
             ..ResolveOptions::default()
         });
@@ -303,6 +514,7 @@ mod windows_test {
                 tsconfig: Some(TsconfigOptions {
                     config_file: root.join("tsconfig.json"),
                     references: TsconfigReferences::Auto,
+                    content: None,
                 }),
                 ..ResolveOptions::default()
             };
@@ -10,6 +10,7 @@ fn auto() {
         tsconfig: Some(TsconfigOptions {
             config_file: f.join("app"),
             references: TsconfigReferences::Auto,
+            content: None,
         }),
         ..ResolveOptions::default()
     });
@@ -46,6 +47,7 @@ fn disabled() {
         tsconfig: Some(TsconfigOptions {
             config_file: f.join("app"),
             references: TsconfigReferences::Disabled,
+            content: None,
         }),
         ..ResolveOptions::default()
     });
@@ -77,6 +79,7 @@ fn manual() {
         tsconfig: Some(TsconfigOptions {
             config_file: f.join("app"),
             references: TsconfigReferences::Paths(vec!["../project_a/conf.json".into()]),
+            content: None,
         }),
         ..ResolveOptions::default()
     });
@@ -118,6 +121,7 @@ fn self_reference() {
             tsconfig: Some(TsconfigOptions {
                 config_file: config_file.clone(),
                 references: TsconfigReferences::Paths(reference_paths.clone()),
+                content: None,
             }),
             ..ResolveOptions::default()
         });
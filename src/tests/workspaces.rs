@@ -0,0 +1,133 @@
+//! Tests for [crate::ResolveOptions::workspace_root]
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn workspaces() {
+    use super::memory_fs::MemoryFS;
+    use crate::{ResolveError, ResolveOptions, ResolverGeneric};
+    use std::path::{Path, PathBuf};
+
+    let f = Path::new("/repo/apps/web");
+
+    let file_system = MemoryFS::new(&[
+        (
+            "/repo/package.json",
+            r#"{ "name": "root", "workspaces": ["packages/*", "apps/**", "!packages/excluded"] }"#,
+        ),
+        ("/repo/packages/a/package.json", r#"{ "name": "@repo/a", "main": "./lib/main.js" }"#),
+        ("/repo/packages/a/lib/main.js", ""),
+        ("/repo/packages/a/util.js", ""),
+        (
+            "/repo/packages/b/package.json",
+            r#"{ "name": "b", "exports": { ".": "./src/index.js" } }"#,
+        ),
+        ("/repo/packages/b/src/index.js", ""),
+        ("/repo/packages/no-name/package.json", "{}"),
+        ("/repo/packages/excluded/package.json", r#"{ "name": "excluded" }"#),
+        ("/repo/packages/excluded/index.js", ""),
+        ("/repo/apps/web/package.json", r#"{ "name": "web" }"#),
+        ("/repo/apps/web/index.js", ""),
+        ("/repo/apps/nested/tool/package.json", r#"{ "name": "tool" }"#),
+        ("/repo/apps/nested/tool/index.js", ""),
+        ("/repo/apps/web/node_modules/b/package.json", r#"{ "name": "b" }"#),
+        ("/repo/apps/web/node_modules/b/index.js", ""),
+    ]);
+
+    let resolver = ResolverGeneric::<MemoryFS>::new_with_file_system(
+        file_system,
+        ResolveOptions {
+            workspace_root: Some(PathBuf::from("/repo")),
+            ..ResolveOptions::default()
+        },
+    );
+
+    #[rustfmt::skip]
+    let pass = [
+        ("main field", "@repo/a", "/repo/packages/a/lib/main.js"),
+        ("subpath", "@repo/a/util", "/repo/packages/a/util.js"),
+        ("exports field, before node_modules", "b", "/repo/packages/b/src/index.js"),
+        ("self", "web", "/repo/apps/web/index.js"),
+        ("nested with **", "tool", "/repo/apps/nested/tool/index.js"),
+    ];
+
+    for (comment, request, expected) in pass {
+        let resolved_path = resolver.resolve(f, request).map(|r| r.full_path());
+        assert_eq!(resolved_path, Ok(PathBuf::from(expected)), "{comment} {request}");
+    }
+
+    #[rustfmt::skip]
+    let fail = [
+        ("excluded", "excluded"),
+        ("not a workspace package", "c"),
+    ];
+
+    for (comment, request) in fail {
+        let resolution = resolver.resolve(f, request);
+        assert_eq!(resolution, Err(ResolveError::NotFound(request.into())), "{comment} {request}");
+    }
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))] // MemoryFS's path separator is always `/` so the test will not pass in windows.
+fn pnpm_workspace() {
+    use super::memory_fs::MemoryFS;
+    use crate::{ResolveError, ResolveOptions, ResolverGeneric};
+    use std::path::{Path, PathBuf};
+
+    let f = Path::new("/repo/apps/web");
+
+    let file_system = MemoryFS::new(&[
+        // `pnpm-workspace.yaml` takes precedence over the "workspaces" field.
+        ("/repo/package.json", r#"{ "name": "root", "workspaces": ["ignored/*"] }"#),
+        (
+            "/repo/pnpm-workspace.yaml",
+            "packages:\n  - 'packages/*'\n  - apps/*\ncatalog:\n  react: ^18.2.0\n",
+        ),
+        ("/repo/packages/a/package.json", r#"{ "name": "a" }"#),
+        ("/repo/packages/a/index.js", ""),
+        ("/repo/ignored/b/package.json", r#"{ "name": "b" }"#),
+        ("/repo/ignored/b/index.js", ""),
+        ("/repo/apps/web/package.json", r#"{ "name": "web" }"#),
+    ]);
+
+    let resolver = ResolverGeneric::<MemoryFS>::new_with_file_system(
+        file_system,
+        ResolveOptions {
+            workspace_root: Some(PathBuf::from("/repo")),
+            ..ResolveOptions::default()
+        },
+    );
+
+    let resolved_path = resolver.resolve(f, "a").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(PathBuf::from("/repo/packages/a/index.js")));
+
+    let resolved_path = resolver.resolve(f, "workspace:a").map(|r| r.full_path());
+    assert_eq!(resolved_path, Ok(PathBuf::from("/repo/packages/a/index.js")));
+
+    assert_eq!(resolver.resolve(f, "b"), Err(ResolveError::NotFound("b".into())));
+    assert_eq!(
+        resolver.resolve(f, "workspace:c"),
+        Err(ResolveError::NotFound("workspace:c".into()))
+    );
+
+    assert_eq!(resolver.workspace_catalog(None, "react"), Ok(Some("^18.2.0".into())));
+    assert_eq!(resolver.workspace_catalog(None, "vue"), Ok(None));
+
+    // Unsupported YAML fails the resolution instead of being misread.
+    let file_system = MemoryFS::new(&[
+        ("/repo/pnpm-workspace.yaml", "packages: [\n  'packages/*',\n]\n"),
+        ("/repo/packages/a/package.json", r#"{ "name": "a" }"#),
+    ]);
+    let resolver = ResolverGeneric::<MemoryFS>::new_with_file_system(
+        file_system,
+        ResolveOptions {
+            workspace_root: Some(PathBuf::from("/repo")),
+            ..ResolveOptions::default()
+        },
+    );
+    let error = resolver.resolve("/repo", "a").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "/repo/pnpm-workspace.yaml: unsupported YAML at line 1: multi-line flow sequences"
+    );
+}
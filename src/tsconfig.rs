@@ -1,4 +1,5 @@
 use std::{
+    fmt,
     hash::BuildHasherDefault,
     path::{Path, PathBuf},
     sync::Arc,
@@ -12,6 +13,25 @@ use crate::PathUtil;
 
 pub type CompilerOptionsPathsMap = IndexMap<String, Vec<String>, BuildHasherDefault<FxHasher>>;
 
+/// Supplies tsconfig contents the embedder already holds, e.g. the unsaved buffers of a language server,
+/// see [crate::ResolveOptions::tsconfig_provider].
+pub trait TsconfigProvider: Send + Sync {
+    /// Returns the contents of the tsconfig file at `path`, or `None` to read it from the file system.
+    fn tsconfig(&self, path: &Path) -> Option<String>;
+}
+
+impl<F: Fn(&Path) -> Option<String> + Send + Sync> TsconfigProvider for F {
+    fn tsconfig(&self, path: &Path) -> Option<String> {
+        self(path)
+    }
+}
+
+impl fmt::Debug for dyn TsconfigProvider {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("TsconfigProvider")
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
 #[serde(untagged)]
 pub enum ExtendsField {
@@ -31,6 +51,10 @@ pub struct TsConfig {
     #[serde(skip)]
     pub(crate) path: PathBuf,
 
+    /// Paths to the tsconfig files this one extends, directly or transitively.
+    #[serde(skip)]
+    extended_paths: Vec<PathBuf>,
+
     #[serde(default)]
     pub extends: Option<ExtendsField>,
 
@@ -104,6 +128,16 @@ impl TsConfig {
         self
     }
 
+    /// Paths to this tsconfig file and the tsconfig files it extends or references.
+    pub(crate) fn dependencies(&self) -> Vec<&Path> {
+        let mut dependencies = vec![self.path.as_path()];
+        dependencies.extend(self.extended_paths.iter().map(PathBuf::as_path));
+        for tsconfig in self.references.iter().filter_map(|reference| reference.tsconfig.as_ref()) {
+            dependencies.extend(tsconfig.dependencies());
+        }
+        dependencies
+    }
+
     /// Directory to `tsconfig.json`
     ///
     /// # Panics
@@ -115,6 +149,8 @@ impl TsConfig {
     }
 
     pub fn extend_tsconfig(&mut self, tsconfig: &Self) {
+        self.extended_paths.push(tsconfig.path.clone());
+        self.extended_paths.extend(tsconfig.extended_paths.iter().cloned());
         let compiler_options = &mut self.compiler_options;
         if compiler_options.paths.is_none() {
             compiler_options.paths_base = compiler_options
@@ -202,6 +238,27 @@ impl TsConfig {
             .collect()
     }
 
+    /// Approximate number of bytes held by this tsconfig, not including the tsconfigs of its references.
+    pub(crate) fn approximate_memory_usage(&self) -> usize {
+        let path_size = |path: &PathBuf| path.capacity();
+        let compiler_options = &self.compiler_options;
+        let paths = compiler_options.paths.as_ref().map_or(0, |paths| {
+            paths
+                .iter()
+                .map(|(key, values)| {
+                    key.capacity() + values.iter().map(String::capacity).sum::<usize>()
+                })
+                .sum()
+        });
+        std::mem::size_of::<Self>()
+            + path_size(&self.path)
+            + self.extended_paths.iter().map(path_size).sum::<usize>()
+            + compiler_options.base_url.as_ref().map_or(0, path_size)
+            + path_size(&compiler_options.paths_base)
+            + paths
+            + self.references.iter().map(|reference| path_size(&reference.path)).sum::<usize>()
+    }
+
     fn base_path(&self) -> &Path {
         self.compiler_options
             .base_url
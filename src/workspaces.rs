@@ -0,0 +1,570 @@
+//! Workspace packages discovered from the root of a monorepo,
+//! see [crate::ResolveOptions::workspace_root].
+//!
+//! The `packages` globs of `pnpm-workspace.yaml`, or the `workspaces` globs of the root package.json,
+//! are expanded once and indexed by package name,
+//! so workspace packages resolve to their source directories without relying on `node_modules` hoisting.
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+    cache::Cache, context::ResolveContext as Ctx, path::PathUtil, FileSystem, ResolveError,
+    ResolveOptions,
+};
+
+/// Workspace package names mapped to their directories.
+#[derive(Debug, Default)]
+pub struct Workspaces {
+    packages: FxHashMap<String, PathBuf>,
+
+    /// pnpm catalogs, the default `catalog` is named "default".
+    catalogs: FxHashMap<String, FxHashMap<String, String>>,
+}
+
+impl Workspaces {
+    /// Reads `pnpm-workspace.yaml` in `root`, or the `workspaces` field of the package.json in `root` when it does not exist.
+    pub fn new<Fs: FileSystem>(
+        cache: &Cache<Fs>,
+        options: &ResolveOptions,
+        root: &Path,
+    ) -> Result<Self, ResolveError> {
+        let yaml_path = root.join("pnpm-workspace.yaml");
+        if let Ok(yaml) = cache.fs.read_to_string(&yaml_path) {
+            let pnpm_workspace = PnpmWorkspace::parse(&yaml).map_err(|message| {
+                ResolveError::from_io_error(
+                    &yaml_path,
+                    &io::Error::new(io::ErrorKind::InvalidData, message),
+                )
+            })?;
+            let mut workspaces =
+                Self::from_patterns(cache, options, root, &pnpm_workspace.packages);
+            workspaces.catalogs = pnpm_workspace.catalogs;
+            return Ok(workspaces);
+        }
+        let mut ctx = Ctx::default();
+        let Some(package_json) =
+            cache.value(root, &mut ctx).package_json(&cache.fs, options, &mut ctx)?
+        else {
+            return Ok(Self::default());
+        };
+        let patterns = package_json.workspaces();
+        Ok(Self::from_patterns(cache, options, root, &patterns))
+    }
+
+    /// Expands `patterns` relative to `root` and indexes the named packages found.
+    ///
+    /// Patterns starting with "!" exclude directories, the first package wins for duplicated names.
+    pub fn from_patterns<Fs: FileSystem, S: AsRef<str>>(
+        cache: &Cache<Fs>,
+        options: &ResolveOptions,
+        root: &Path,
+        patterns: &[S],
+    ) -> Self {
+        let mut included = vec![];
+        let mut excluded = FxHashSet::default();
+        for pattern in patterns {
+            let pattern = pattern.as_ref();
+            if let Some(pattern) = pattern.strip_prefix('!') {
+                let mut dirs = vec![];
+                expand(&cache.fs, root, &segments(pattern), &mut dirs);
+                excluded.extend(dirs);
+            } else {
+                expand(&cache.fs, root, &segments(pattern), &mut included);
+            }
+        }
+
+        let mut ctx = Ctx::default();
+        let mut packages = FxHashMap::default();
+        for dir in included {
+            if excluded.contains(&dir) {
+                continue;
+            }
+            // Directories without a valid package.json are not workspace packages.
+            let Ok(Some(package_json)) =
+                cache.value(&dir, &mut ctx).package_json(&cache.fs, options, &mut ctx)
+            else {
+                continue;
+            };
+            if let Some(name) = &package_json.name {
+                packages.entry(name.clone()).or_insert(dir);
+            }
+        }
+        Self { packages, catalogs: FxHashMap::default() }
+    }
+
+    /// Returns the directory of the workspace package `name`.
+    pub fn get(&self, name: &str) -> Option<&Path> {
+        self.packages.get(name).map(PathBuf::as_path)
+    }
+
+    /// Returns the version of `package_name` in the pnpm catalog `catalog`, `None` for the default catalog.
+    pub fn catalog(&self, catalog: Option<&str>, package_name: &str) -> Option<&str> {
+        self.catalogs.get(catalog.unwrap_or("default"))?.get(package_name).map(String::as_str)
+    }
+}
+
+/// The subset of `pnpm-workspace.yaml` used for resolution.
+///
+/// <https://pnpm.io/pnpm-workspace_yaml>
+#[derive(Debug, Default, PartialEq, Eq)]
+struct PnpmWorkspace {
+    packages: Vec<String>,
+    catalogs: FxHashMap<String, FxHashMap<String, String>>,
+}
+
+impl PnpmWorkspace {
+    /// Parses the `packages`, `catalog` and `catalogs` keys, all other keys are skipped.
+    ///
+    /// Only block style mappings and sequences, single line flow style sequences of scalars,
+    /// and single line plain or quoted scalars are supported.
+    ///
+    /// # Errors
+    ///
+    /// * YAML outside of this subset in the parsed keys, e.g. block scalars, anchors, aliases, tags,
+    ///   flow mappings, multi-line scalars or flow sequences, or nested mappings, with the line number
+    fn parse(yaml: &str) -> Result<Self, String> {
+        let mut workspace = Self::default();
+        let mut section = "";
+        let mut catalog = String::new();
+        // Indentation of the entries of `catalog`, and of the catalog names under `catalogs`.
+        let mut entry_indent = None;
+        // Indentation of the entries of a catalog under `catalogs`.
+        let mut catalog_entry_indent = None;
+        for (line_number, line) in yaml.lines().enumerate() {
+            let line_number = line_number + 1;
+            let content = strip_comment(line).trim_end();
+            if content.trim_start().is_empty() || content.starts_with("---") {
+                continue;
+            }
+            let indent = content.len() - content.trim_start().len();
+            let content = content.trim_start();
+            // A block sequence may be indented as much as its key.
+            let is_item = content == "-" || content.starts_with("- ");
+            if indent == 0 && !(section == "packages" && is_item) {
+                let Some((key, value)) = split_key_value(content) else {
+                    section = "";
+                    continue;
+                };
+                section = match key.as_str() {
+                    "packages" => {
+                        if value.starts_with('[') {
+                            workspace.packages.extend(parse_flow_sequence(value, line_number)?);
+                        } else if !value.is_empty() {
+                            check_supported(value, line_number)?;
+                            return Err(unsupported(line_number, "scalars as packages"));
+                        }
+                        "packages"
+                    }
+                    "catalog" | "catalogs" => {
+                        check_mapping(value, line_number)?;
+                        entry_indent = None;
+                        if key == "catalog" {
+                            "catalog"
+                        } else {
+                            "catalogs"
+                        }
+                    }
+                    _ => "",
+                };
+                continue;
+            }
+            match section {
+                "packages" => {
+                    if !is_item {
+                        return Err(unsupported(line_number, "content other than sequence items"));
+                    }
+                    let item = content[1..].trim();
+                    if split_key_value(item).is_some() {
+                        return Err(unsupported(line_number, "mappings as packages"));
+                    }
+                    workspace.packages.push(parse_scalar(item, line_number)?);
+                }
+                "catalog" => {
+                    if *entry_indent.get_or_insert(indent) != indent {
+                        return Err(unsupported(line_number, "nested mappings"));
+                    }
+                    let (name, version) = parse_entry(content, line_number)?;
+                    workspace
+                        .catalogs
+                        .entry("default".to_string())
+                        .or_default()
+                        .insert(name, version);
+                }
+                "catalogs" => {
+                    let catalog_indent = *entry_indent.get_or_insert(indent);
+                    if catalog_indent >= indent {
+                        let Some((key, value)) = split_key_value(content) else {
+                            return Err(unsupported(
+                                line_number,
+                                "content other than mapping entries",
+                            ));
+                        };
+                        check_mapping(value, line_number)?;
+                        catalog = key;
+                        catalog_entry_indent = None;
+                    } else {
+                        if *catalog_entry_indent.get_or_insert(indent) != indent {
+                            return Err(unsupported(line_number, "nested mappings"));
+                        }
+                        let (name, version) = parse_entry(content, line_number)?;
+                        workspace
+                            .catalogs
+                            .entry(catalog.clone())
+                            .or_default()
+                            .insert(name, version);
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(workspace)
+    }
+}
+
+fn unsupported(line_number: usize, what: &str) -> String {
+    format!("unsupported YAML at line {line_number}: {what}")
+}
+
+/// Rejects the YAML a value may start with which is not supported, instead of misreading it.
+fn check_supported(value: &str, line_number: usize) -> Result<(), String> {
+    let unsupported_yaml = match value.chars().next() {
+        Some('|' | '>') => "block scalars",
+        Some('&' | '*') => "anchors and aliases",
+        Some('!') => "tags",
+        Some('{') => "flow mappings",
+        Some('[') => "flow sequences",
+        _ => return Ok(()),
+    };
+    Err(unsupported(line_number, unsupported_yaml))
+}
+
+/// Rejects a value on the line of a key whose value must be a block mapping of catalogs.
+fn check_mapping(value: &str, line_number: usize) -> Result<(), String> {
+    if value.is_empty() {
+        return Ok(());
+    }
+    check_supported(value, line_number)?;
+    Err(unsupported(line_number, "scalars as catalogs"))
+}
+
+/// Strips a `#` comment which is not inside quotes.
+///
+/// A quote only starts a quoted scalar at the start of a value, so `don't` is a plain scalar.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut previous = None;
+    let mut chars = line.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match (quote, c) {
+            // `\"` in a double quoted scalar and `''` in a single quoted scalar are escaped quotes.
+            (Some('"'), '\\') => {
+                chars.next();
+            }
+            (Some('\''), '\'') if chars.peek().is_some_and(|(_, c)| *c == '\'') => {
+                chars.next();
+            }
+            (Some(q), c) if q == c => quote = None,
+            (None, '\'' | '"')
+                if previous.map_or(true, |p: char| p.is_whitespace() || matches!(p, '[' | ',')) =>
+            {
+                quote = Some(c);
+            }
+            (None, '#') if previous.map_or(true, char::is_whitespace) => return &line[..i],
+            _ => {}
+        }
+        previous = Some(c);
+    }
+    line
+}
+
+/// Splits `key: value` and unquotes the key.
+fn split_key_value(content: &str) -> Option<(String, &str)> {
+    let (key, value) = if content.starts_with(['\'', '"']) {
+        let (key, rest) = split_quoted(content).ok()?;
+        (key, rest.trim_start().strip_prefix(':')?)
+    } else {
+        let (key, value) = content.split_once(':')?;
+        if !value.is_empty() && !value.starts_with(' ') {
+            return None;
+        }
+        (key.trim_end().to_string(), value)
+    };
+    Some((key, value.trim()))
+}
+
+/// Parses a `name: version` entry of a catalog.
+fn parse_entry(content: &str, line_number: usize) -> Result<(String, String), String> {
+    let Some((name, version)) = split_key_value(content) else {
+        return Err(unsupported(line_number, "content other than mapping entries"));
+    };
+    if version.is_empty() {
+        return Err(unsupported(line_number, "nested mappings"));
+    }
+    Ok((name, parse_scalar(version, line_number)?))
+}
+
+/// Parses a plain or quoted scalar spanning the whole `value`.
+fn parse_scalar(value: &str, line_number: usize) -> Result<String, String> {
+    check_supported(value, line_number)?;
+    if !value.starts_with(['\'', '"']) {
+        return Ok(value.to_string());
+    }
+    let (scalar, rest) = split_quoted(value).map_err(|what| unsupported(line_number, what))?;
+    if !rest.trim().is_empty() {
+        return Err(unsupported(line_number, "content after a quoted scalar"));
+    }
+    Ok(scalar)
+}
+
+/// Parses `[a, 'b, c', "d"]`.
+fn parse_flow_sequence(value: &str, line_number: usize) -> Result<Vec<String>, String> {
+    let mut items = vec![];
+    let mut rest = &value[1..];
+    loop {
+        rest = rest.trim_start();
+        if let Some(after) = rest.strip_prefix(']') {
+            if !after.trim().is_empty() {
+                return Err(unsupported(line_number, "content after a flow sequence"));
+            }
+            return Ok(items);
+        }
+        if rest.is_empty() {
+            return Err(unsupported(line_number, "multi-line flow sequences"));
+        }
+        let item;
+        if rest.starts_with(['\'', '"']) {
+            (item, rest) = split_quoted(rest).map_err(|what| unsupported(line_number, what))?;
+        } else {
+            let end = rest.find([',', ']']).unwrap_or(rest.len());
+            let plain = rest[..end].trim_end();
+            if plain.is_empty() {
+                return Err(unsupported(line_number, "empty flow sequence items"));
+            }
+            check_supported(plain, line_number)?;
+            (item, rest) = (plain.to_string(), &rest[end..]);
+        }
+        items.push(item);
+        rest = rest.trim_start();
+        if let Some(after) = rest.strip_prefix(',') {
+            rest = after;
+        } else if !rest.is_empty() && !rest.starts_with(']') {
+            return Err(unsupported(line_number, "content after a quoted scalar"));
+        }
+    }
+}
+
+/// Splits the quoted scalar `value` starts with from the rest, unescaping it.
+///
+/// Only the `\\`, `\"` and `\/` escapes of double quoted scalars are supported.
+fn split_quoted(value: &str) -> Result<(String, &str), &'static str> {
+    let mut chars = value.char_indices();
+    let Some((_, quote)) = chars.next() else {
+        return Err("multi-line quoted scalars");
+    };
+    let mut scalar = String::new();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' if quote == '"' => match chars.next() {
+                Some((_, c @ ('\\' | '"' | '/'))) => scalar.push(c),
+                _ => return Err("escape sequences"),
+            },
+            c if c == quote => {
+                let rest = &value[i + 1..];
+                if quote == '\'' && rest.starts_with('\'') {
+                    chars.next();
+                    scalar.push('\'');
+                    continue;
+                }
+                return Ok((scalar, rest));
+            }
+            c => scalar.push(c),
+        }
+    }
+    Err("multi-line quoted scalars")
+}
+
+fn segments(pattern: &str) -> Vec<&str> {
+    pattern.split(['/', '\\']).filter(|segment| !segment.is_empty() && *segment != ".").collect()
+}
+
+/// Expands glob `segments` into existing directories under `dir`.
+///
+/// Supports `*` wildcards within a segment and `**` for any number of directories.
+fn expand<Fs: FileSystem>(fs: &Fs, dir: &Path, segments: &[&str], dirs: &mut Vec<PathBuf>) {
+    let Some((segment, rest)) = segments.split_first() else {
+        if fs.metadata(dir).is_ok_and(|meta| meta.is_dir) {
+            dirs.push(dir.to_path_buf());
+        }
+        return;
+    };
+    match *segment {
+        "**" => {
+            expand(fs, dir, rest, dirs);
+            for child in child_dirs(fs, dir) {
+                expand(fs, &child, segments, dirs);
+            }
+        }
+        segment if segment.contains('*') => {
+            for child in child_dirs(fs, dir) {
+                if child
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| matches_segment(segment, name))
+                {
+                    expand(fs, &child, rest, dirs);
+                }
+            }
+        }
+        segment => expand(fs, &dir.normalize_with(segment), rest, dirs),
+    }
+}
+
+/// Child directories of `dir`, skipping `node_modules` and hidden directories.
+fn child_dirs<Fs: FileSystem>(fs: &Fs, dir: &Path) -> Vec<PathBuf> {
+    let mut children = fs
+        .read_dir(dir)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name != "node_modules" && !name.starts_with('.'))
+        })
+        .filter(|path| fs.metadata(path).is_ok_and(|meta| meta.is_dir))
+        .collect::<Vec<_>>();
+    // Directory entries are unordered, sort them so duplicated package names resolve deterministically.
+    children.sort_unstable();
+    children
+}
+
+/// Matches a path segment against a pattern containing `*` wildcards.
+fn matches_segment(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts = parts.collect::<Vec<_>>();
+    let last = parts.pop().unwrap_or_default();
+    for part in parts {
+        let Some(index) = rest.find(part) else {
+            return false;
+        };
+        rest = &rest[index + part.len()..];
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+#[test]
+fn pnpm_workspace() {
+    let yaml = r#"
+packages:
+  # all packages in direct subdirs of packages/
+  - 'packages/*'
+  - "apps/**"
+  - '!**/test/**' # exclude packages that are inside test directories
+
+catalog:
+  react: ^18.2.0
+  "@types/react": '^18.2.0'
+
+catalogs:
+  react17:
+    react: ^17.0.2
+    react-dom: ^17.0.2
+  react18:
+    react: ^18.2.0
+"#;
+    let workspace = PnpmWorkspace::parse(yaml).unwrap();
+    assert_eq!(workspace.packages, vec!["packages/*", "apps/**", "!**/test/**"]);
+
+    let workspaces = Workspaces { packages: FxHashMap::default(), catalogs: workspace.catalogs };
+    assert_eq!(workspaces.catalog(None, "react"), Some("^18.2.0"));
+    assert_eq!(workspaces.catalog(None, "@types/react"), Some("^18.2.0"));
+    assert_eq!(workspaces.catalog(Some("react17"), "react-dom"), Some("^17.0.2"));
+    assert_eq!(workspaces.catalog(Some("react18"), "react"), Some("^18.2.0"));
+    assert_eq!(workspaces.catalog(Some("react18"), "react-dom"), None);
+    assert_eq!(workspaces.catalog(Some("missing"), "react"), None);
+
+    let workspace = PnpmWorkspace::parse("packages: ['packages/*', apps/web]\n").unwrap();
+    assert_eq!(workspace.packages, vec!["packages/*", "apps/web"]);
+
+    // Items of a block sequence may be as indented as their key.
+    let workspace =
+        PnpmWorkspace::parse("packages:\n- packages/*\n-   'apps/*'\ncatalog:\n  react: ^18.2.0\n")
+            .unwrap();
+    assert_eq!(workspace.packages, vec!["packages/*", "apps/*"]);
+    let workspaces = Workspaces { packages: FxHashMap::default(), catalogs: workspace.catalogs };
+    assert_eq!(workspaces.catalog(None, "react"), Some("^18.2.0"));
+
+    // Commas and `#` inside quotes are part of the scalar.
+    let yaml = r#"
+packages: ['packages/{a,b}', "apps/#web", it's, 'it''s', "say \"hi\""] # comment
+catalog:
+  "@scope/a,b": '#1' # comment
+  c: "^1.0.0 # not a comment"
+  d: don't # comment
+"#;
+    let workspace = PnpmWorkspace::parse(yaml).unwrap();
+    assert_eq!(
+        workspace.packages,
+        vec!["packages/{a,b}", "apps/#web", "it's", "it's", r#"say "hi""#]
+    );
+    let workspaces = Workspaces { packages: FxHashMap::default(), catalogs: workspace.catalogs };
+    assert_eq!(workspaces.catalog(None, "@scope/a,b"), Some("#1"));
+    assert_eq!(workspaces.catalog(None, "c"), Some("^1.0.0 # not a comment"));
+    assert_eq!(workspaces.catalog(None, "d"), Some("don't"));
+
+    let workspace = PnpmWorkspace::parse("packages:\n  - 'a, b' # comment\n  - \"#c\"\n").unwrap();
+    assert_eq!(workspace.packages, vec!["a, b", "#c"]);
+
+    // Unsupported YAML in other keys is skipped.
+    let workspace =
+        PnpmWorkspace::parse("onlyBuiltDependencies: &deps\n  - esbuild\npackages:\n  - a\n")
+            .unwrap();
+    assert_eq!(workspace.packages, vec!["a"]);
+
+    #[rustfmt::skip]
+    let unsupported = [
+        ("packages: |\n  packages/*\n", "unsupported YAML at line 1: block scalars"),
+        ("packages:\n  - >-\n    packages/*\n", "unsupported YAML at line 2: block scalars"),
+        ("catalog:\n  react: |\n    ^18.2.0\n", "unsupported YAML at line 2: block scalars"),
+        ("catalogs:\n  react17:\n    react: >\n      ^17.0.2\n", "unsupported YAML at line 3: block scalars"),
+        ("packages: packages/*\n", "unsupported YAML at line 1: scalars as packages"),
+        ("packages: ['a', [b]]\n", "unsupported YAML at line 1: flow sequences"),
+        ("packages: ['a' b]\n", "unsupported YAML at line 1: content after a quoted scalar"),
+        ("packages: [a,, b]\n", "unsupported YAML at line 1: empty flow sequence items"),
+        ("packages: [a] b\n", "unsupported YAML at line 1: content after a flow sequence"),
+        ("packages:\n  - 'a\n    b'\n", "unsupported YAML at line 2: multi-line quoted scalars"),
+        ("packages:\n  - a\n    b\n", "unsupported YAML at line 3: content other than sequence items"),
+        ("packages:\n  - \"\\u0061\"\n", "unsupported YAML at line 2: escape sequences"),
+        ("packages:\n  - a: b\n", "unsupported YAML at line 2: mappings as packages"),
+        ("catalog:\n  react:\n    version: ^18.2.0\n", "unsupported YAML at line 2: nested mappings"),
+        ("catalog:\n  react ^18.2.0\n", "unsupported YAML at line 2: content other than mapping entries"),
+        ("catalog: react\n", "unsupported YAML at line 1: scalars as catalogs"),
+        ("packages: [\n  'packages/*',\n]\n", "unsupported YAML at line 1: multi-line flow sequences"),
+        ("packages:\n  - &glob packages/*\n", "unsupported YAML at line 2: anchors and aliases"),
+        ("catalog:\n  react: *version\n", "unsupported YAML at line 2: anchors and aliases"),
+        ("catalog: { react: ^18.2.0 }\n", "unsupported YAML at line 1: flow mappings"),
+        ("catalogs:\n  react17: &react17\n    react: ^17.0.2\n", "unsupported YAML at line 2: anchors and aliases"),
+        ("catalogs:\n  react17:\n    <<: *react17\n", "unsupported YAML at line 3: anchors and aliases"),
+    ];
+    for (yaml, error) in unsupported {
+        assert_eq!(PnpmWorkspace::parse(yaml), Err(error.to_string()), "{yaml}");
+    }
+}
+
+#[test]
+fn matches() {
+    assert!(matches_segment("*", "foo"));
+    assert!(matches_segment("foo-*", "foo-bar"));
+    assert!(matches_segment("*-bar", "foo-bar"));
+    assert!(matches_segment("f*o*r", "foobar"));
+    assert!(!matches_segment("foo-*", "bar-foo"));
+    assert!(!matches_segment("*-bar", "foo-baz"));
+    assert!(!matches_segment("a*a", "a"));
+}